@@ -1,6 +1,7 @@
 use std::{path::PathBuf, str::FromStr as _};
 
 use anyhow::anyhow;
+use fj_export::View;
 use fj_host::Parameters;
 use fj_kernel::algorithms::approx::Tolerance;
 use fj_math::Scalar;
@@ -10,12 +11,21 @@ use fj_math::Scalar;
 #[command(version = fj::version::VERSION_FULL.to_string())]
 pub struct Args {
     /// The model to open
+    ///
+    /// Either the directory of the model's package, a model that has been
+    /// compiled to WebAssembly (a `.wasm` file), a data file that describes
+    /// the model (a `.toml` or `.json` file), or a script that computes the
+    /// model (a `.rhai` file).
     pub model: Option<PathBuf>,
 
     /// Export model to this path
     #[arg(short, long, value_name = "PATH")]
     pub export: Option<PathBuf>,
 
+    /// Views to draw when exporting to SVG or DXF, e.g. `front,top,right`
+    #[arg(long, value_delimiter = ',', value_parser = parse_view)]
+    pub views: Option<Vec<View>>,
+
     /// Parameters for the model, each in the form `key=value`
     #[arg(short, long, value_parser = parse_parameters)]
     pub parameters: Option<Parameters>,
@@ -23,6 +33,40 @@ pub struct Args {
     /// Model deviation tolerance
     #[arg(short, long, value_parser = parse_tolerance)]
     pub tolerance: Option<Tolerance>,
+
+    /// Maximum angle between neighboring segments of curved edges, in degrees
+    #[arg(long, value_name = "DEGREES", value_parser = parse_positive)]
+    pub angular_tolerance: Option<Scalar>,
+
+    /// Maximum length of the edges of the triangle mesh
+    #[arg(long, value_name = "LENGTH", value_parser = parse_positive)]
+    pub max_edge_length: Option<Scalar>,
+
+    /// Print statistics about the triangle mesh of each face, when exporting
+    #[arg(long)]
+    pub mesh_stats: bool,
+
+    /// Evaluate the model in a separate process
+    ///
+    /// Isolates the application from crashes and infinite loops in the model.
+    #[arg(long)]
+    pub isolate: bool,
+
+    /// Stop evaluating the model after this many seconds
+    ///
+    /// This includes the time it takes to compile the model. A native model
+    /// that isn't isolated can't be stopped while it computes its shape. The
+    /// viewer reports the timeout, but only evaluates the model again once it
+    /// has finished, while exporting waits for it.
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Write a report on how long each step of processing the model took
+    ///
+    /// The report is written as JSON to this path. The viewer updates it each
+    /// time it has processed the model. It's also shown in the viewer.
+    #[arg(long, value_name = "PATH")]
+    pub profile: Option<PathBuf>,
 }
 
 impl Args {
@@ -58,6 +102,26 @@ fn parse_parameters(input: &str) -> anyhow::Result<Parameters> {
     Ok(parameters)
 }
 
+fn parse_view(input: &str) -> anyhow::Result<View> {
+    match input.trim() {
+        "front" => Ok(View::Front),
+        "top" => Ok(View::Top),
+        "right" => Ok(View::Right),
+        view => Err(anyhow!(
+            "Unknown view `{view}`, expected `front`, `top`, or `right`"
+        )),
+    }
+}
+
+fn parse_positive(input: &str) -> anyhow::Result<Scalar> {
+    let value = f64::from_str(input)?;
+    if value <= 0. {
+        return Err(anyhow!("Value must be above zero"));
+    }
+
+    Ok(Scalar::from_f64(value))
+}
+
 fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
     let tolerance = f64::from_str(input)?;
     let tolerance = Scalar::from_f64(tolerance);
@@ -1,25 +1,73 @@
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
 
-use anyhow::Context as _;
+use anyhow::{anyhow, Context as _};
+use directories::ProjectDirs;
 use figment::{
     providers::{Env, Format as _, Toml},
     Figment,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+/// The file that the list of recently opened models is persisted in
+///
+/// This is separate from `fj.toml`, so writing the list doesn't clobber the
+/// user's configuration (including any comments in there). It's stored in the
+/// user's data directory, as the list is the same, regardless of where the
+/// application is started from.
+///
+/// Returns `None`, if the user's data directory can't be determined.
+fn recent_models_file() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("app", "Fornjot", "Fornjot")?;
+    Some(dirs.data_dir().join("recent-models.toml"))
+}
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub default_path: Option<PathBuf>,
     pub default_model: Option<PathBuf>,
     pub invert_zoom: Option<bool>,
+    pub recent_models: Option<Vec<PathBuf>>,
 }
 
 impl Config {
     pub fn load() -> Result<Self, anyhow::Error> {
-        Figment::new()
-            .merge(Toml::file("fj.toml"))
+        let mut figment = Figment::new().merge(Toml::file("fj.toml"));
+        if let Some(recent_models_file) = recent_models_file() {
+            figment = figment.merge(Toml::file(recent_models_file));
+        }
+
+        figment
             .merge(Env::prefixed("FJ_"))
             .extract()
             .context("Error loading configuration")
     }
+
+    pub fn save_recent_models(
+        recent_models: &[PathBuf],
+    ) -> Result<(), anyhow::Error> {
+        #[derive(Serialize)]
+        struct RecentModels<'r> {
+            recent_models: &'r [PathBuf],
+        }
+
+        let path = recent_models_file().ok_or_else(|| {
+            anyhow!("Could not determine where to store list of recent models")
+        })?;
+
+        let recent_models = toml::to_string(&RecentModels { recent_models })
+            .context("Error serializing list of recent models")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("Error creating directory `{}`", dir.display())
+            })?;
+        }
+        fs::write(&path, recent_models).with_context(|| {
+            format!(
+                "Error writing list of recent models to `{}`",
+                path.display()
+            )
+        })?;
+
+        Ok(())
+    }
 }
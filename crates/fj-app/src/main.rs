@@ -16,35 +16,85 @@ mod args;
 mod config;
 mod path;
 
-use std::{env, error::Error};
+use std::{env, error::Error, fs, path::Path, time::Duration};
 
 use anyhow::{anyhow, Context};
-use fj_export::export;
-use fj_host::Parameters;
-use fj_operations::shape_processor::ShapeProcessor;
-use fj_window::run::run;
+use fj_export::{export, export_drawing};
+use fj_host::{profile_to_json, Model, Parameters, Profiler, Worker};
+use fj_interop::processed_shape::MeshStats;
+use fj_math::Scalar;
+use fj_operations::{cache::Cache, shape_processor::ShapeProcessor};
+use fj_window::{recent_models::RecentModels, run::run};
 use path::ModelPath;
+use tracing::warn;
 use tracing_subscriber::fmt::format;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{prelude::*, EnvFilter};
 
 use crate::{args::Args, config::Config};
 
 fn main() -> anyhow::Result<()> {
+    // When evaluating isolated models, this executable is started again as the
+    // worker process. In that case, this doesn't return.
+    fj_host::run_if_worker();
+
     // Respect `RUST_LOG`. If that's not defined, log warnings and above. Fail if it's erroneous.
-    tracing_subscriber::fmt()
-        .with_env_filter(try_default_env_filter()?)
-        .event_format(format().pretty())
+    //
+    // The filter only applies to logging. The profiler needs to see all spans,
+    // regardless of their level.
+    let profiler = Profiler::new();
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .event_format(format().pretty())
+                .with_filter(try_default_env_filter()?),
+        )
+        .with(profiler.clone())
         .init();
 
     let args = Args::parse();
     let config = Config::load()?;
     let model_path = ModelPath::from_args_and_config(&args, &config);
     let parameters = args.parameters.unwrap_or_else(Parameters::empty);
-    let shape_processor = ShapeProcessor {
+    let mut shape_processor = ShapeProcessor {
         tolerance: args.tolerance,
+        angle: args
+            .angular_tolerance
+            .map(|degrees| degrees * Scalar::PI / 180.),
+        max_edge_length: args.max_edge_length,
+        cache: Cache::new(),
     };
 
-    let model = model_path.map(|m| m.load_model(parameters)).transpose()?;
+    let timeout = args.timeout.map(Duration::from_secs);
+    let worker = args
+        .isolate
+        .then(|| {
+            Worker::current_exe().map(|worker| {
+                worker.with_timeout(timeout.unwrap_or(Worker::DEFAULT_TIMEOUT))
+            })
+        })
+        .transpose()?;
+
+    // Models that are opened from within the viewer are loaded the same way as
+    // the one the application is started with.
+    let load_model = move |path: &Path| -> Result<Model, fj_host::Error> {
+        let mut model = Model::new(path, parameters.clone())?;
+        if let Some(worker) = &worker {
+            model = model.with_worker(worker.clone());
+        }
+        if let Some(timeout) = timeout {
+            model = model.with_timeout(timeout);
+        }
+
+        Ok(model)
+    };
+
+    let (model, model_path) = match model_path {
+        Some(model_path) => {
+            let (model, path) = model_path.load_model(&load_model)?;
+            (Some(model), Some(path))
+        }
+        None => (None, None),
+    };
 
     if let Some(export_path) = args.export {
         // export only mode. just load model, process, export and exit
@@ -52,17 +102,73 @@ fn main() -> anyhow::Result<()> {
         let evaluation = model.with_context(no_model_error)?.evaluate()?;
         let shape = shape_processor.process(&evaluation.shape)?;
 
-        export(&shape.mesh, &export_path)?;
+        match args.views {
+            Some(views) => export_drawing(&shape, &views, &export_path)?,
+            None => export(&shape, &export_path)?,
+        }
+
+        if args.mesh_stats {
+            print_mesh_stats(&shape.mesh_stats);
+        }
+
+        if let Some(profile_path) = args.profile {
+            fs::write(&profile_path, profile_to_json(&profiler.take()))
+                .with_context(|| {
+                    format!(
+                        "Failed to write profile to {}",
+                        profile_path.display()
+                    )
+                })?;
+        }
 
         return Ok(());
     }
 
     let invert_zoom = config.invert_zoom.unwrap_or(false);
-    run(model, shape_processor, invert_zoom)?;
+    let mut recent_models = RecentModels::new(
+        config.recent_models.unwrap_or_default(),
+        |recent_models| {
+            Config::save_recent_models(recent_models).map_err(Into::into)
+        },
+    );
+    if let Some(model_path) = model_path {
+        if let Err(err) = recent_models.add(model_path) {
+            warn!("Failed to save list of recent models: {err}");
+        }
+    }
+    run(
+        model,
+        load_model,
+        shape_processor,
+        invert_zoom,
+        recent_models,
+        profiler,
+        args.profile,
+    )?;
 
     Ok(())
 }
 
+fn print_mesh_stats(stats: &MeshStats) {
+    println!(
+        "{:>6} {:>10} {:>14} {:>16}",
+        "Face", "Triangles", "Max deviation", "Max edge length"
+    );
+    for (i, face) in stats.faces.iter().enumerate() {
+        println!(
+            "{:>6} {:>10} {:>14.6} {:>16.6}",
+            i, face.triangles, face.max_deviation, face.max_edge_length
+        );
+    }
+    println!(
+        "{:>6} {:>10} {:>14.6} {:>16.6}",
+        "Total",
+        stats.triangles(),
+        stats.max_deviation(),
+        stats.max_edge_length()
+    );
+}
+
 fn no_model_error() -> anyhow::Error {
     anyhow!(
         "You must specify a model to start Fornjot in export only mode.\n\
@@ -4,7 +4,7 @@ use std::{
 };
 
 use anyhow::Context;
-use fj_host::{Model, Parameters};
+use fj_host::Model;
 
 use crate::{args::Args, config::Config};
 
@@ -33,7 +33,13 @@ impl ModelPath {
         })
     }
 
-    pub fn load_model(&self, parameters: Parameters) -> anyhow::Result<Model> {
+    /// Load the model, using `load`
+    ///
+    /// Returns the model, along with the path it was loaded from.
+    pub fn load_model(
+        &self,
+        load: impl FnOnce(&Path) -> Result<Model, fj_host::Error>,
+    ) -> anyhow::Result<(Model, PathBuf)> {
         let default_path = self
             .default_path
             .as_ref()
@@ -56,10 +62,10 @@ impl ModelPath {
             .unwrap_or_else(PathBuf::new)
             .join(self.model_path.path());
 
-        let model = Model::new(&path, parameters).with_context(|| {
-            load_error_context(default_path, &self.model_path, path)
+        let model = load(&path).with_context(|| {
+            load_error_context(default_path, &self.model_path, path.clone())
         })?;
-        Ok(model)
+        Ok((model, path))
     }
 }
 
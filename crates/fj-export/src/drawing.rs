@@ -0,0 +1,812 @@
+//! 2D drawings of shapes, as projections onto the standard views
+
+use std::{collections::BTreeMap, f64::consts::TAU};
+
+use fj_interop::{
+    edges::{Edge, EdgeGeometry},
+    processed_shape::ProcessedShape,
+};
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+/// One of the standard views of a technical drawing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum View {
+    /// The view from the front, looking along the positive y-axis
+    Front,
+
+    /// The view from the top, looking along the negative z-axis
+    Top,
+
+    /// The view from the right, looking along the negative x-axis
+    Right,
+}
+
+impl View {
+    /// Choose the views for a shape, if none are specified
+    ///
+    /// Flat shapes, like sketches, are shown from the top, without moving them.
+    /// That makes the drawing suitable for cutting the shape. All other shapes
+    /// are shown from all standard views.
+    pub fn default_for(shape: &ProcessedShape) -> Vec<Self> {
+        if shape.aabb.size().z == Scalar::ZERO {
+            vec![Self::Top]
+        } else {
+            vec![Self::Front, Self::Top, Self::Right]
+        }
+    }
+
+    /// Project a point onto the drawing plane of the view
+    ///
+    /// The coordinate system of the drawing plane is right-handed, as seen by
+    /// the viewer.
+    pub fn project(&self, point: Point<3>) -> Point<2> {
+        match self {
+            Self::Front => Point::from([point.x, point.z]),
+            Self::Top => Point::from([point.x, point.y]),
+            Self::Right => Point::from([point.y, point.z]),
+        }
+    }
+
+    /// The direction that points from the shape towards the viewer
+    pub fn towards_viewer(&self) -> Vector<3> {
+        match self {
+            Self::Front => Vector::from([0., -1., 0.]),
+            Self::Top => Vector::from([0., 0., 1.]),
+            Self::Right => Vector::from([1., 0., 0.]),
+        }
+    }
+
+    fn depth(&self, point: Point<3>) -> Scalar {
+        point.coords.dot(&self.towards_viewer())
+    }
+}
+
+/// A 2D drawing of a shape
+pub struct Drawing {
+    /// The elements of the drawing
+    pub elements: Vec<Element>,
+}
+
+impl Drawing {
+    /// Create a drawing of the visible edges of a shape, from the given views
+    ///
+    /// If there are multiple views, they are arranged as in a third-angle
+    /// projection: The top view is placed above the front view, the right view
+    /// to the right of it.
+    pub fn new(shape: &ProcessedShape, views: &[View]) -> Self {
+        let size = shape.aabb.size().magnitude();
+        let gap = size * 0.1;
+
+        let bounds = |view: View| {
+            let points = shape.aabb.vertices().map(|point| view.project(point));
+            Aabb::<2>::from_points(points)
+        };
+        let front = bounds(View::Front);
+
+        let mut elements = Vec::new();
+
+        for &view in views {
+            let offset = if views.len() > 1 {
+                let bounds = bounds(view);
+
+                match view {
+                    View::Front => Vector::from([0., 0.]),
+                    View::Top => {
+                        Vector::from([Scalar::ZERO, front.max.v + gap])
+                            - Vector::from([Scalar::ZERO, bounds.min.v])
+                    }
+                    View::Right => {
+                        Vector::from([front.max.u + gap, Scalar::ZERO])
+                            - Vector::from([bounds.min.u, Scalar::ZERO])
+                    }
+                }
+            } else {
+                Vector::from([0., 0.])
+            };
+
+            let visibility = Visibility::new(shape, view, size);
+            let step = size / Scalar::from(SAMPLES_PER_SIZE);
+
+            let curves = shape.edges.iter().map(Curve::from_edge).chain(
+                silhouettes(shape, view).into_iter().map(Curve::Polyline),
+            );
+
+            for curve in curves {
+                for [start, end] in visibility.visible_ranges(&curve, step) {
+                    if let Some(element) =
+                        curve.project(view, start, end, step, size)
+                    {
+                        elements.push(element.translate(offset));
+                    }
+                }
+            }
+        }
+
+        Self { elements }
+    }
+
+    /// Compute the bounding box of the drawing
+    ///
+    /// Returns `None`, if the drawing is empty.
+    pub fn bounds(&self) -> Option<Aabb<2>> {
+        self.elements
+            .iter()
+            .map(Element::bounds)
+            .reduce(|a, b| Aabb::<2>::from_points([a.min, a.max, b.min, b.max]))
+    }
+}
+
+/// An element of a 2D drawing
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Element {
+    /// A straight line
+    Line([Point<2>; 2]),
+
+    /// A full circle
+    Circle {
+        /// The center of the circle
+        center: Point<2>,
+
+        /// The radius of the circle
+        radius: Scalar,
+    },
+
+    /// A part of a circle, running counterclockwise from start to end
+    Arc {
+        /// The center of the circle
+        center: Point<2>,
+
+        /// The radius of the circle
+        radius: Scalar,
+
+        /// The angle at which the arc starts, in radians
+        start_angle: Scalar,
+
+        /// The angle at which the arc ends, in radians
+        end_angle: Scalar,
+    },
+
+    /// A chain of straight lines, approximating a curve
+    Polyline(Vec<Point<2>>),
+}
+
+impl Element {
+    fn translate(self, offset: Vector<2>) -> Self {
+        match self {
+            Self::Line(points) => {
+                Self::Line(points.map(|point| point + offset))
+            }
+            Self::Circle { center, radius } => Self::Circle {
+                center: center + offset,
+                radius,
+            },
+            Self::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => Self::Arc {
+                center: center + offset,
+                radius,
+                start_angle,
+                end_angle,
+            },
+            Self::Polyline(points) => Self::Polyline(
+                points.into_iter().map(|point| point + offset).collect(),
+            ),
+        }
+    }
+
+    fn bounds(&self) -> Aabb<2> {
+        match self {
+            Self::Line(points) => Aabb::<2>::from_points(*points),
+            Self::Circle { center, radius }
+            | Self::Arc { center, radius, .. } => {
+                // This is conservative for arcs, which is good enough here.
+                let radius = Vector::from([*radius, *radius]);
+                Aabb::<2>::from_points([*center - radius, *center + radius])
+            }
+            Self::Polyline(points) => Aabb::<2>::from_points(points.clone()),
+        }
+    }
+}
+
+/// An edge or silhouette, parametrized over `0..=1`
+enum Curve<'r> {
+    Segment([Point<3>; 2]),
+    Arc {
+        center: Point<3>,
+        radius: Scalar,
+        normal: Vector<3>,
+        axes: [Vector<3>; 2],
+        angle: Scalar,
+
+        /// The approximation of the arc, that the triangle mesh is built from
+        approximation: &'r Edge,
+    },
+    Polyline(Vec<Point<3>>),
+}
+
+impl<'r> Curve<'r> {
+    fn from_edge(edge: &'r Edge) -> Self {
+        match (edge.geometry, edge.points.first()) {
+            (Some(EdgeGeometry::Segment(segment)), _) => {
+                Self::Segment(segment.points())
+            }
+            (
+                Some(EdgeGeometry::Arc {
+                    center,
+                    radius,
+                    normal,
+                    angle,
+                }),
+                Some(&start),
+            ) => {
+                let a = (start - center).normalize();
+                let b = normal.cross(&a);
+
+                Self::Arc {
+                    center,
+                    radius,
+                    normal,
+                    axes: [a, b],
+                    angle,
+                    approximation: edge,
+                }
+            }
+            _ => Self::Polyline(edge.points.clone()),
+        }
+    }
+
+    fn point_at(&self, s: Scalar) -> Point<3> {
+        match self {
+            Self::Segment([a, b]) => *a + (*b - *a) * s,
+            Self::Arc {
+                center,
+                radius,
+                axes: [a, b],
+                angle,
+                ..
+            } => {
+                let (sin, cos) = (*angle * s).sin_cos();
+                *center + (*a * cos + *b * sin) * *radius
+            }
+            Self::Polyline(points) => {
+                let Some(last) = points.len().checked_sub(1) else {
+                    return Point::origin();
+                };
+
+                let s = s.max(Scalar::ZERO).min(Scalar::ONE) * last as f64;
+                let i = (s.floor().into_f64() as usize).min(last);
+                match points.get(i + 1) {
+                    Some(&next) => {
+                        points[i] + (next - points[i]) * (s - i as f64)
+                    }
+                    None => points[i],
+                }
+            }
+        }
+    }
+
+    /// The point at which to check the curve for visibility
+    ///
+    /// The exact points of an arc can lie outside of the triangle mesh, which
+    /// only approximates the shape. Points of the approximation are checked
+    /// instead, so arcs aren't hidden by the surfaces they bound.
+    fn visibility_point_at(&self, s: Scalar) -> Point<3> {
+        let point = self.point_at(s);
+
+        match self {
+            Self::Arc { approximation, .. } => {
+                approximation.closest_point(&point).unwrap_or(point)
+            }
+            Self::Segment(_) | Self::Polyline(_) => point,
+        }
+    }
+
+    /// The parameters at which to sample the curve
+    ///
+    /// Includes all points of polylines, so no corners are cut off.
+    fn samples(&self, step: Scalar) -> Vec<Scalar> {
+        let (length, pieces) = match self {
+            Self::Segment([a, b]) => (a.distance_to(b), 1),
+            Self::Arc { radius, angle, .. } => (*radius * *angle, 1),
+            Self::Polyline(points) => (
+                points
+                    .windows(2)
+                    .map(|points| points[0].distance_to(&points[1]))
+                    .fold(Scalar::ZERO, |sum, length| sum + length),
+                points.len().saturating_sub(1).max(1),
+            ),
+        };
+
+        let per_piece =
+            (length / step / pieces as f64).ceil().into_f64().max(1.) as usize;
+        let n = pieces * per_piece;
+
+        (0..=n).map(|i| Scalar::from(i as f64 / n as f64)).collect()
+    }
+
+    /// Project part of the curve onto the drawing plane of a view
+    ///
+    /// Returns `None`, if that part of the curve is degenerate in this view.
+    fn project(
+        &self,
+        view: View,
+        start: Scalar,
+        end: Scalar,
+        step: Scalar,
+        size: Scalar,
+    ) -> Option<Element> {
+        let epsilon = size * EPSILON;
+
+        let element = match self {
+            Self::Segment(_) => Element::Line(
+                [start, end].map(|s| view.project(self.point_at(s))),
+            ),
+            Self::Arc {
+                center,
+                radius,
+                normal,
+                angle,
+                ..
+            } if normal.dot(&view.towards_viewer()).abs()
+                > Scalar::ONE - Scalar::from(EPSILON) =>
+            {
+                let center = view.project(*center);
+
+                if *angle * (end - start)
+                    >= Scalar::from(TAU) - Scalar::from(EPSILON)
+                {
+                    Element::Circle {
+                        center,
+                        radius: *radius,
+                    }
+                } else {
+                    let [start_angle, end_angle] = [start, end].map(|s| {
+                        let direction = view.project(self.point_at(s)) - center;
+                        direction.v.atan2(direction.u)
+                    });
+
+                    // Arcs that run clockwise, as seen from the viewer, run
+                    // counterclockwise from end to start.
+                    let [start_angle, end_angle] =
+                        if normal.dot(&view.towards_viewer()).is_positive() {
+                            [start_angle, end_angle]
+                        } else {
+                            [end_angle, start_angle]
+                        };
+
+                    Element::Arc {
+                        center,
+                        radius: *radius,
+                        start_angle,
+                        end_angle,
+                    }
+                }
+            }
+            Self::Arc { normal, .. }
+                if normal.dot(&view.towards_viewer()).abs()
+                    < Scalar::from(EPSILON) =>
+            {
+                // Arcs that are seen edge-on, are straight lines.
+                let direction = view.project(
+                    Point::origin() + normal.cross(&view.towards_viewer()),
+                ) - Point::origin();
+                let points = self.projected_points(view, start, end, step);
+
+                let along = |point: &&Point<2>| {
+                    (*point - Point::origin()).dot(&direction)
+                };
+                let (Some(&min), Some(&max)) = (
+                    points.iter().min_by_key(along),
+                    points.iter().max_by_key(along),
+                ) else {
+                    return None;
+                };
+
+                Element::Line([min, max])
+            }
+            Self::Arc { .. } | Self::Polyline(_) => {
+                let points = simplify(
+                    self.projected_points(view, start, end, step),
+                    epsilon,
+                );
+
+                match points.as_slice() {
+                    &[a, b] => Element::Line([a, b]),
+                    _ => Element::Polyline(points),
+                }
+            }
+        };
+
+        let bounds = element.bounds();
+        let size = bounds.max - bounds.min;
+        if size.u < epsilon && size.v < epsilon {
+            return None;
+        }
+
+        Some(element)
+    }
+
+    fn projected_points(
+        &self,
+        view: View,
+        start: Scalar,
+        end: Scalar,
+        step: Scalar,
+    ) -> Vec<Point<2>> {
+        let mut points = vec![view.project(self.point_at(start))];
+        for s in self.samples(step) {
+            if s > start && s < end {
+                points.push(view.project(self.point_at(s)));
+            }
+        }
+        points.push(view.project(self.point_at(end)));
+
+        points
+    }
+}
+
+/// Remove the points of a polyline, that lie on a straight line between their
+/// neighbors
+fn simplify(points: Vec<Point<2>>, epsilon: Scalar) -> Vec<Point<2>> {
+    let mut simplified: Vec<Point<2>> = Vec::new();
+
+    for point in points {
+        if let [.., a, b] = simplified.as_slice() {
+            let line = point - *a;
+            let is_straight = line.magnitude() > epsilon
+                && (line.cross2d(&(*b - *a)) / line.magnitude()).abs()
+                    <= epsilon
+                && (*b - *a).dot(&line) >= Scalar::ZERO
+                && (*b - *a).magnitude() <= line.magnitude();
+
+            if is_straight {
+                simplified.pop();
+            }
+        }
+
+        simplified.push(point);
+    }
+
+    simplified
+}
+
+/// Decides which points of the shape are visible from a view
+struct Visibility {
+    view: View,
+    triangles: Vec<ProjectedTriangle>,
+    epsilon: Scalar,
+}
+
+impl Visibility {
+    fn new(shape: &ProcessedShape, view: View, size: Scalar) -> Self {
+        let epsilon = size * EPSILON;
+
+        let triangles = shape
+            .mesh
+            .triangles()
+            .filter_map(|triangle| {
+                let points = triangle.inner.points();
+                let projected = points.map(|point| view.project(point));
+
+                // Triangles that are seen edge-on can't hide anything.
+                let [a, b, c] = projected;
+                let area = (b - a).cross2d(&(c - a));
+                if area.abs() <= epsilon * epsilon {
+                    return None;
+                }
+
+                Some(ProjectedTriangle {
+                    points: projected,
+                    depths: points.map(|point| view.depth(point)),
+                    area,
+                    bounds: Aabb::<2>::from_points(projected),
+                })
+            })
+            .collect();
+
+        Self {
+            view,
+            triangles,
+            epsilon: size * DEPTH_EPSILON,
+        }
+    }
+
+    fn is_visible(&self, point: Point<3>) -> bool {
+        let depth = self.view.depth(point);
+        let point = self.view.project(point);
+
+        !self.triangles.iter().any(|triangle| {
+            triangle
+                .depth_at(point, self.epsilon)
+                .map(|d| d > depth + self.epsilon)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Find the parameter ranges, in which the curve is visible
+    fn visible_ranges(&self, curve: &Curve, step: Scalar) -> Vec<[Scalar; 2]> {
+        let mut ranges = Vec::new();
+        let mut start = None;
+
+        let samples = curve.samples(step);
+        let mut previous: Option<(Scalar, bool)> = None;
+
+        for s in samples {
+            let visible = self.is_visible(curve.visibility_point_at(s));
+
+            match previous {
+                None => {
+                    if visible {
+                        start = Some(s);
+                    }
+                }
+                Some((previous, was_visible)) if was_visible != visible => {
+                    let boundary =
+                        self.find_boundary(curve, [previous, s], was_visible);
+
+                    if visible {
+                        start = Some(boundary);
+                    } else if let Some(start) = start.take() {
+                        ranges.push([start, boundary]);
+                    }
+                }
+                Some(_) => {}
+            }
+
+            previous = Some((s, visible));
+        }
+
+        if let (Some(start), Some((end, true))) = (start, previous) {
+            ranges.push([start, end]);
+        }
+
+        ranges
+    }
+
+    /// Find the point between two samples, where visibility changes
+    fn find_boundary(
+        &self,
+        curve: &Curve,
+        [mut a, mut b]: [Scalar; 2],
+        a_is_visible: bool,
+    ) -> Scalar {
+        for _ in 0..BISECTION_STEPS {
+            let middle = (a + b) / 2.;
+            if self.is_visible(curve.visibility_point_at(middle))
+                == a_is_visible
+            {
+                a = middle;
+            } else {
+                b = middle;
+            }
+        }
+
+        (a + b) / 2.
+    }
+}
+
+struct ProjectedTriangle {
+    points: [Point<2>; 3],
+    depths: [Scalar; 3],
+    area: Scalar,
+    bounds: Aabb<2>,
+}
+
+impl ProjectedTriangle {
+    /// The depth of the triangle at a point, if the triangle covers the point
+    fn depth_at(&self, point: Point<2>, epsilon: Scalar) -> Option<Scalar> {
+        let [min, max] = [self.bounds.min, self.bounds.max];
+        if point.u < min.u - epsilon
+            || point.u > max.u + epsilon
+            || point.v < min.v - epsilon
+            || point.v > max.v + epsilon
+        {
+            return None;
+        }
+
+        let [a, b, c] = self.points;
+        let weights = [
+            (c - b).cross2d(&(point - b)) / self.area,
+            (a - c).cross2d(&(point - c)) / self.area,
+            (b - a).cross2d(&(point - a)) / self.area,
+        ];
+
+        if weights
+            .iter()
+            .any(|&weight| weight < -Scalar::from(EPSILON))
+        {
+            return None;
+        }
+
+        let [wa, wb, wc] = weights;
+        let [da, db, dc] = self.depths;
+        Some(da * wa + db * wb + dc * wc)
+    }
+}
+
+/// Find the silhouettes of the shape's curved surfaces, as seen from a view
+///
+/// Silhouettes are where the surface turns away from the viewer. They are not
+/// edges of the shape, but are needed to draw curved surfaces.
+fn silhouettes(shape: &ProcessedShape, view: View) -> Vec<Vec<Point<3>>> {
+    let vertices = shape.mesh.vertices().collect::<Vec<_>>();
+    let indices = shape.mesh.indices().collect::<Vec<_>>();
+
+    // Which way each triangle faces, for each edge of the triangles.
+    let mut facings = BTreeMap::new();
+    for triangle in indices.chunks(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+        let [pa, pb, pc] = [a, b, c].map(|i| vertices[i as usize]);
+
+        let normal = (pb - pa).cross(&(pc - pa));
+        let magnitude = normal.magnitude();
+        if magnitude == Scalar::ZERO {
+            continue;
+        }
+        let facing = normal.dot(&view.towards_viewer()) / magnitude;
+
+        for [i, j] in [[a, b], [b, c], [c, a]] {
+            facings
+                .entry([i.min(j), i.max(j)])
+                .or_insert_with(Vec::new)
+                .push(facing);
+        }
+    }
+
+    // Surfaces that are seen edge-on are not drawn, so silhouettes are where
+    // surfaces that face the viewer border those that don't.
+    let threshold = Scalar::from(EPSILON);
+    let epsilon = shape.aabb.size().magnitude() * DEPTH_EPSILON;
+    let segments = facings
+        .into_iter()
+        .filter_map(|(edge, facings)| match facings.as_slice() {
+            [a, b] if (*a > threshold) != (*b > threshold) => Some(edge),
+            _ => None,
+        })
+        .filter(|[a, b]| {
+            // Where a silhouette coincides with an edge, only the edge is
+            // drawn.
+            let middle = vertices[*a as usize]
+                + (vertices[*b as usize] - vertices[*a as usize]) / 2.;
+            !shape.edges.iter().any(|edge| {
+                edge.closest_point(&middle)
+                    .map(|closest| closest.distance_to(&middle) <= epsilon)
+                    .unwrap_or(false)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    chain(&segments)
+        .into_iter()
+        .map(|chain| chain.into_iter().map(|i| vertices[i as usize]).collect())
+        .collect()
+}
+
+/// Join segments that share vertices into chains
+fn chain(segments: &[[u32; 2]]) -> Vec<Vec<u32>> {
+    let mut neighbors = BTreeMap::new();
+    for &[a, b] in segments {
+        neighbors.entry(a).or_insert_with(Vec::new).push(b);
+        neighbors.entry(b).or_insert_with(Vec::new).push(a);
+    }
+
+    let mut chains = Vec::new();
+
+    // Start at the ends of open chains first, so they are not split. Whatever
+    // is left afterwards, is closed.
+    let mut starts = neighbors
+        .iter()
+        .filter(|(_, neighbors)| neighbors.len() != 2)
+        .map(|(&vertex, _)| vertex)
+        .collect::<Vec<_>>();
+    starts.extend(neighbors.keys().copied());
+
+    for start in starts {
+        let mut chain = vec![start];
+        let mut current = start;
+
+        while let Some(next) = take_neighbor(&mut neighbors, current) {
+            chain.push(next);
+            current = next;
+        }
+
+        if chain.len() > 1 {
+            chains.push(chain);
+        }
+    }
+
+    chains
+}
+
+fn take_neighbor(
+    neighbors: &mut BTreeMap<u32, Vec<u32>>,
+    vertex: u32,
+) -> Option<u32> {
+    let next = neighbors.get_mut(&vertex)?.pop()?;
+
+    if let Some(back) = neighbors.get_mut(&next) {
+        if let Some(i) = back.iter().position(|&v| v == vertex) {
+            back.remove(i);
+        }
+    }
+
+    Some(next)
+}
+
+/// Tolerance for degenerate geometry, relative to the size of the shape
+const EPSILON: f64 = 1e-9;
+
+/// How far a surface needs to be in front of a point, to hide it
+///
+/// This is relative to the size of the shape, and is larger than [`EPSILON`],
+/// so points are not hidden by the surfaces they are on.
+const DEPTH_EPSILON: f64 = 1e-6;
+
+/// How many samples are checked for visibility, over the size of the shape
+const SAMPLES_PER_SIZE: f64 = 200.;
+
+/// How often to bisect, to find where an edge becomes hidden
+const BISECTION_STEPS: usize = 12;
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{
+        debug::DebugInfo,
+        edges::{Edge, EdgeGeometry},
+        mesh::{Color, Mesh},
+        processed_shape::{MeshStats, ProcessedShape},
+    };
+    use fj_math::{Aabb, Point, Segment};
+
+    use super::{Drawing, Element, View};
+
+    #[test]
+    fn hidden_lines() {
+        // A square in the xz-plane, with a line behind it, that sticks out on
+        // both sides.
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 0., 1.], [0., 0., 1.]]
+                .map(Point::from);
+        let line = [[-1., 1., 0.5], [2., 1., 0.5]].map(Point::from);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([a, c, d], Color::default());
+
+        let shape = ProcessedShape {
+            aabb: Aabb::<3>::from_points([a, c, line[0], line[1]]),
+            mesh,
+            mesh_stats: MeshStats::default(),
+            edges: vec![Edge {
+                geometry: Some(EdgeGeometry::Segment(Segment::from_points(
+                    line,
+                ))),
+                points: line.to_vec(),
+            }],
+            annotations: Vec::new(),
+            references: Vec::new(),
+            debug_info: DebugInfo::new(),
+        };
+
+        let drawing = Drawing::new(&shape, &[View::Front]);
+        let ends = drawing
+            .elements
+            .iter()
+            .map(|element| match element {
+                Element::Line([a, b]) => [a.u, b.u].map(|u| {
+                    // Round away the imprecision of finding the boundaries.
+                    (u.into_f64() * 100.).round() / 100.
+                }),
+                element => panic!("Expected line, got {element:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ends, [[-1., 0.], [1., 2.]]);
+
+        // From the top, nothing is hidden.
+        let drawing = Drawing::new(&shape, &[View::Top]);
+        assert_eq!(drawing.elements.len(), 1);
+    }
+}
@@ -0,0 +1,97 @@
+//! Export of 2D drawings to DXF
+
+use std::f64::consts::TAU;
+
+use fj_math::Scalar;
+
+use crate::drawing::{Drawing, Element};
+
+/// Write a drawing as an ASCII DXF file
+///
+/// This writes the AutoCAD R12 format, which only needs the entities section
+/// and is understood by pretty much any application that reads DXF.
+pub fn write(drawing: &Drawing) -> String {
+    let mut dxf = Dxf::default();
+
+    dxf.group(0, "SECTION");
+    dxf.group(2, "HEADER");
+    dxf.group(9, "$ACADVER");
+    dxf.group(1, "AC1009");
+    dxf.group(0, "ENDSEC");
+
+    dxf.group(0, "SECTION");
+    dxf.group(2, "ENTITIES");
+    for element in &drawing.elements {
+        dxf.element(element);
+    }
+    dxf.group(0, "ENDSEC");
+
+    dxf.group(0, "EOF");
+
+    dxf.0
+}
+
+#[derive(Default)]
+struct Dxf(String);
+
+impl Dxf {
+    fn group(&mut self, code: u32, value: impl ToString) {
+        self.0.push_str(&format!("{code}\n{}\n", value.to_string()));
+    }
+
+    fn entity(&mut self, kind: &str) {
+        self.group(0, kind);
+        self.group(8, LAYER);
+    }
+
+    fn point(&mut self, code: u32, [x, y]: [Scalar; 2]) {
+        self.group(code, x.into_f64());
+        self.group(code + 10, y.into_f64());
+        self.group(code + 20, 0.);
+    }
+
+    fn element(&mut self, element: &Element) {
+        match element {
+            Element::Line([a, b]) => {
+                self.entity("LINE");
+                self.point(10, [a.u, a.v]);
+                self.point(11, [b.u, b.v]);
+            }
+            Element::Circle { center, radius } => {
+                self.entity("CIRCLE");
+                self.point(10, [center.u, center.v]);
+                self.group(40, radius.into_f64());
+            }
+            Element::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => {
+                let degrees = |angle: Scalar| {
+                    angle.into_f64().rem_euclid(TAU).to_degrees()
+                };
+
+                // Arcs run counterclockwise, same as in the drawing.
+                self.entity("ARC");
+                self.point(10, [center.u, center.v]);
+                self.group(40, radius.into_f64());
+                self.group(50, degrees(*start_angle));
+                self.group(51, degrees(*end_angle));
+            }
+            Element::Polyline(points) => {
+                self.entity("POLYLINE");
+                self.group(66, 1);
+                self.point(10, [Scalar::ZERO, Scalar::ZERO]);
+                for point in points {
+                    self.entity("VERTEX");
+                    self.point(10, [point.u, point.v]);
+                }
+                self.entity("SEQEND");
+            }
+        }
+    }
+}
+
+/// The layer that all entities are placed on
+const LAYER: &str = "0";
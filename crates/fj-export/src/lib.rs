@@ -14,35 +14,113 @@
 
 #![warn(missing_docs)]
 
-use std::{fs::File, path::Path};
+mod drawing;
+mod dxf;
+mod svg;
 
+pub use self::drawing::View;
+
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::Path,
+};
+
+use quick_xml::{
+    events::{BytesText, Event},
+    Reader, Writer,
+};
 use thiserror::Error;
 
-use fj_interop::mesh::Mesh;
+use fj_interop::{
+    annotations::Annotation, mesh::Mesh, processed_shape::ProcessedShape,
+};
 use fj_math::{Point, Triangle};
 
-/// Export the provided mesh to the file at the given path.
+use self::drawing::Drawing;
+
+/// Export the provided shape to the file at the given path.
 ///
 /// This function will create a file if it does not exist, and will truncate it if it does.
 ///
-/// Currently 3MF & STL file types are supported. The case insensitive file extension of
-/// the provided path is used to switch between supported types.
-pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    match path.extension() {
-        Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
-            export_3mf(mesh, path)
-        }
-        Some(extension) if extension.to_ascii_uppercase() == "STL" => {
-            export_stl(mesh, path)
+/// Currently 3MF, STL, SVG & DXF file types are supported. The case insensitive file
+/// extension of the provided path is used to switch between supported types.
+///
+/// The annotations are written to 3MF files as metadata. STL files don't
+/// support anything like that, and the annotations are ignored.
+///
+/// Reference bodies are included in 3MF and STL files, if the model marked them
+/// for export.
+///
+/// SVG and DXF files contain a 2D drawing of the shape, from the views that
+/// [`View::default_for`] chooses. Use [`export_drawing`] to choose the views.
+pub fn export(shape: &ProcessedShape, path: &Path) -> Result<(), Error> {
+    match extension(path)?.as_str() {
+        "3MF" => export_3mf(&exported_mesh(shape), &shape.annotations, path),
+        "STL" => export_stl(&exported_mesh(shape), path),
+        "SVG" | "DXF" => export_drawing(shape, &View::default_for(shape), path),
+        _ => Err(invalid_extension(path)),
+    }
+}
+
+/// Export a 2D drawing of the provided shape to the file at the given path.
+///
+/// The drawing shows the visible edges of the shape, projected onto the given
+/// views. Reference bodies are not part of the drawing. Edges that are lines,
+/// circles, or arcs in the shape, and are seen head-on, are written as such,
+/// without approximating them.
+///
+/// SVG & DXF file types are supported, chosen by the case insensitive file
+/// extension of the provided path.
+pub fn export_drawing(
+    shape: &ProcessedShape,
+    views: &[View],
+    path: &Path,
+) -> Result<(), Error> {
+    let write = match extension(path)?.as_str() {
+        "SVG" => svg::write,
+        "DXF" => dxf::write,
+        _ => return Err(invalid_extension(path)),
+    };
+
+    let drawing = Drawing::new(shape, views);
+    fs::write(path, write(&drawing))?;
+
+    Ok(())
+}
+
+/// Merge the reference bodies that are marked for export into the shape's mesh
+fn exported_mesh(shape: &ProcessedShape) -> Mesh<Point<3>> {
+    let mut mesh = shape.mesh.clone();
+
+    let references = shape
+        .references
+        .iter()
+        .filter(|reference| reference.is_exported);
+    for reference in references {
+        for triangle in reference.mesh.triangles() {
+            mesh.push_triangle(triangle.inner, triangle.color);
         }
-        Some(extension) => Err(Error::InvalidExtension(
-            extension.to_string_lossy().into_owned(),
-        )),
-        None => Err(Error::NoExtension),
     }
+
+    mesh
 }
 
-fn export_3mf(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+fn extension(path: &Path) -> Result<String, Error> {
+    let extension = path.extension().ok_or(Error::NoExtension)?;
+    Ok(extension.to_string_lossy().to_ascii_uppercase())
+}
+
+fn invalid_extension(path: &Path) -> Error {
+    let extension = path.extension().unwrap_or_default();
+    Error::InvalidExtension(extension.to_string_lossy().into_owned())
+}
+
+fn export_3mf(
+    mesh: &Mesh<Point<3>>,
+    annotations: &[Annotation],
+    path: &Path,
+) -> Result<(), Error> {
     let vertices = mesh.vertices().map(Into::into).collect();
 
     let indices: Vec<_> = mesh.indices().collect();
@@ -64,9 +142,125 @@ fn export_3mf(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
 
     threemf::write(path, &mesh)?;
 
+    if !annotations.is_empty() {
+        add_3mf_metadata(annotations, path)?;
+    }
+
+    Ok(())
+}
+
+/// Add annotations to the model of an existing 3MF file, as metadata
+///
+/// `threemf` doesn't support writing metadata, so this rewrites the archive
+/// that it created, inserting the metadata into the model part.
+fn add_3mf_metadata(
+    annotations: &[Annotation],
+    path: &Path,
+) -> Result<(), Error> {
+    let mut parts = Vec::new();
+
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+    for i in 0..archive.len() {
+        let mut part = archive.by_index(i)?;
+
+        let mut content = Vec::new();
+        part.read_to_end(&mut content)?;
+
+        parts.push((part.name().to_owned(), content));
+    }
+    drop(archive);
+
+    let mut archive = zip::ZipWriter::new(File::create(path)?);
+    for (name, content) in parts {
+        let content = if name.ends_with(".model") {
+            let model = String::from_utf8_lossy(&content);
+            insert_3mf_metadata(&model, annotations)
+                .ok_or(Error::InvalidModelPart)?
+                .into_bytes()
+        } else {
+            content
+        };
+
+        archive.start_file(name, zip::write::FileOptions::default())?;
+        archive.write_all(&content)?;
+    }
+    archive.finish()?;
+
     Ok(())
 }
 
+/// Insert annotations as metadata elements into a 3MF model
+///
+/// Metadata names outside of the ones defined by the 3MF specification need to
+/// be qualified with a namespace, which this also declares.
+///
+/// Returns `None`, if `model` is not valid XML or has no `model` element.
+fn insert_3mf_metadata(
+    model: &str,
+    annotations: &[Annotation],
+) -> Option<String> {
+    let mut reader = Reader::from_str(model);
+    let mut writer = Writer::new(Vec::new());
+    let mut has_model = false;
+
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(mut start)
+                if !has_model && start.local_name().as_ref() == b"model" =>
+            {
+                has_model = true;
+
+                start.push_attribute(("xmlns:fj", FJ_3MF_NAMESPACE));
+                writer.write_event(Event::Start(start)).ok()?;
+
+                for (i, annotation) in annotations.iter().enumerate() {
+                    let name = format!("fj:annotation{i}");
+                    let description = describe_annotation(annotation);
+
+                    writer
+                        .create_element("metadata")
+                        .with_attribute(("name", name.as_str()))
+                        .write_text_content(BytesText::new(&description))
+                        .ok()?;
+                }
+            }
+            Event::Eof => break,
+            event => writer.write_event(event).ok()?,
+        }
+    }
+
+    if !has_model {
+        return None;
+    }
+
+    String::from_utf8(writer.into_inner()).ok()
+}
+
+fn describe_annotation(annotation: &Annotation) -> String {
+    let point = |point: &Point<3>| {
+        let [x, y, z] = point.coords.components.map(|s| s.into_f64());
+        format!("[{x}, {y}, {z}]")
+    };
+
+    match annotation {
+        Annotation::Dimension { name, from, to } => format!(
+            "dimension \"{name}\" = {} from {} to {}",
+            from.distance_to(to).into_f64(),
+            point(from),
+            point(to),
+        ),
+        Annotation::Label { text, position } => {
+            format!("label \"{text}\" at {}", point(position))
+        }
+        Annotation::ReferencePoint { name, position } => {
+            format!("point \"{name}\" at {}", point(position))
+        }
+    }
+}
+
+/// The XML namespace of the metadata that Fornjot writes to 3MF files
+const FJ_3MF_NAMESPACE: &str = "http://www.fornjot.app/3mf/annotations";
+
 fn export_stl(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     let points = mesh
         .triangles()
@@ -134,4 +328,42 @@ pub enum Error {
     /// Threemf error whilst exporting to 3MF file
     #[error("threemf error whilst exporting to 3MF file")]
     ThreeMF(#[from] threemf::Error),
+
+    /// Zip error whilst adding metadata to 3MF file
+    #[error("zip error whilst adding metadata to 3MF file")]
+    Zip(#[from] zip::result::ZipError),
+
+    /// The 3MF file has a model part without a model
+    #[error("3MF file has a model part without a model")]
+    InvalidModelPart,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::annotations::Annotation;
+    use fj_math::Point;
+
+    use super::insert_3mf_metadata;
+
+    #[test]
+    fn insert_metadata() {
+        let model = "<?xml version=\"1.0\"?><model unit=\"millimeter\">\
+            <resources/></model>";
+        let annotations = [Annotation::ReferencePoint {
+            name: String::from("<a>"),
+            position: Point::from([1., 2., 3.]),
+        }];
+
+        assert_eq!(
+            insert_3mf_metadata(model, &annotations).as_deref(),
+            Some(
+                "<?xml version=\"1.0\"?><model unit=\"millimeter\" \
+                xmlns:fj=\"http://www.fornjot.app/3mf/annotations\">\
+                <metadata name=\"fj:annotation0\">\
+                point &quot;&lt;a&gt;&quot; at [1, 2, 3]</metadata>\
+                <resources/></model>"
+            ),
+        );
+        assert_eq!(insert_3mf_metadata("<resources/>", &annotations), None);
+    }
 }
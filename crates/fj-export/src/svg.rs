@@ -0,0 +1,90 @@
+//! Export of 2D drawings to SVG
+
+use std::{f64::consts::PI, fmt::Write as _};
+
+use fj_math::{Point, Scalar};
+
+use crate::drawing::{Drawing, Element};
+
+/// Write a drawing as an SVG document
+///
+/// The drawing's units are written as millimeters. SVG's y-axis points down, so
+/// the drawing is mirrored, to keep it the right way up.
+pub fn write(drawing: &Drawing) -> String {
+    let (min, max) = match drawing.bounds() {
+        Some(bounds) => (bounds.min, bounds.max),
+        None => (Point::origin(), Point::origin()),
+    };
+    let [width, height] = [max.u - min.u, max.v - min.v].map(Scalar::into_f64);
+    let stroke_width = width.max(height) / 500.;
+
+    let mut svg = String::new();
+
+    let _ = writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}mm" height="{height}mm" viewBox="{} {} {width} {height}">"#,
+        min.u.into_f64(),
+        -max.v.into_f64(),
+    );
+    let _ = writeln!(
+        svg,
+        r#"<g transform="scale(1 -1)" fill="none" stroke="black" stroke-width="{stroke_width}">"#,
+    );
+
+    for element in &drawing.elements {
+        let _ = writeln!(svg, "{}", write_element(element));
+    }
+
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, "</svg>");
+
+    svg
+}
+
+fn write_element(element: &Element) -> String {
+    match element {
+        Element::Line([a, b]) => {
+            let [x1, y1, x2, y2] = [a.u, a.v, b.u, b.v].map(Scalar::into_f64);
+            format!(r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}"/>"#)
+        }
+        Element::Circle { center, radius } => {
+            let [cx, cy, r] =
+                [center.u, center.v, *radius].map(Scalar::into_f64);
+            format!(r#"<circle cx="{cx}" cy="{cy}" r="{r}"/>"#)
+        }
+        Element::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        } => {
+            let point_at = |angle: Scalar| {
+                let (sin, cos) = angle.into_f64().sin_cos();
+                let r = radius.into_f64();
+                [center.u.into_f64() + r * cos, center.v.into_f64() + r * sin]
+            };
+            let [x1, y1] = point_at(*start_angle);
+            let [x2, y2] = point_at(*end_angle);
+
+            let sweep =
+                (*end_angle - *start_angle).into_f64().rem_euclid(2. * PI);
+            let large_arc = u8::from(sweep > PI);
+
+            // Within the mirrored group, the positive angle direction is
+            // counterclockwise.
+            format!(
+                r#"<path d="M {x1} {y1} A {r} {r} 0 {large_arc} 1 {x2} {y2}"/>"#,
+                r = radius.into_f64(),
+            )
+        }
+        Element::Polyline(points) => {
+            let points = points
+                .iter()
+                .map(|point| format!("{},{}", point.u, point.v))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(r#"<polyline points="{points}"/>"#)
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    process::{Child, ExitStatus},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::Sender;
+
+use crate::Error;
+
+/// How often to check whether a child process has exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Controls an evaluation that is in progress
+///
+/// Tells the evaluation when to stop, and receives the progress it reports.
+#[derive(Default)]
+pub(crate) struct Control {
+    cancelled: Arc<AtomicBool>,
+    timeout: Option<(Duration, Instant)>,
+    progress: Option<Sender<Stage>>,
+}
+
+impl Control {
+    /// Stop the evaluation, if it takes longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout.map(|timeout| (timeout, Instant::now()));
+        self
+    }
+
+    /// Report the progress of the evaluation through `progress`
+    pub fn with_progress(mut self, progress: Sender<Stage>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// The point in time after which the evaluation times out
+    pub fn deadline(&self) -> Option<Instant> {
+        self.timeout.map(|(timeout, started)| started + timeout)
+    }
+
+    /// Access the flag that cancels the evaluation, once it is set
+    pub fn cancellation(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+
+    /// Report that the evaluation has entered a new stage
+    pub fn report(&self, stage: Stage) {
+        if let Some(progress) = &self.progress {
+            // Nobody might be interested in the progress anymore. That's fine.
+            let _ = progress.send(stage);
+        }
+    }
+
+    /// Return an error, if the evaluation should stop
+    pub fn check(&self) -> Result<(), Error> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(Error::Cancelled);
+        }
+
+        if let Some((timeout, started)) = self.timeout {
+            if started.elapsed() > timeout {
+                return Err(Error::Timeout(timeout));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wait for a child process to exit
+    ///
+    /// If the evaluation should stop in the meantime, the process is killed.
+    pub fn wait(&self, child: &mut Child) -> Result<ExitStatus, Error> {
+        wait(child, || self.check())
+    }
+}
+
+/// Wait for a child process to exit, unless `check` returns an error first
+///
+/// In the latter case, the process is killed, and the error is returned.
+pub(crate) fn wait(
+    child: &mut Child,
+    mut check: impl FnMut() -> Result<(), Error>,
+) -> Result<ExitStatus, Error> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+
+        if let Err(err) = check() {
+            // The process might have exited in the meantime. Then there's
+            // nothing left to kill, which is fine.
+            let _ = child.kill();
+            let _ = child.wait();
+
+            return Err(err);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// A stage of the evaluation of a model
+///
+/// See [`ModelEvent::Progress`].
+///
+/// [`ModelEvent::Progress`]: crate::ModelEvent::Progress
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stage {
+    /// The model is being compiled
+    Compiling,
+
+    /// The compiled model is being loaded
+    Loading,
+
+    /// The model is computing its shape
+    Evaluating,
+
+    /// The shape of the model is being meshed
+    ///
+    /// The host doesn't mesh shapes, so it never reports this stage itself.
+    /// Whoever meshes the shape of an [`Evaluation`] can use it to report
+    /// their progress in the same way.
+    ///
+    /// [`Evaluation`]: crate::Evaluation
+    Meshing,
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        process::Command,
+        sync::atomic::Ordering,
+        time::{Duration, Instant},
+    };
+
+    use crate::Error;
+
+    use super::Control;
+
+    #[test]
+    fn cancel_child_process() {
+        let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+
+        let control = Control::default();
+        control.cancellation().store(true, Ordering::Relaxed);
+
+        let started = Instant::now();
+        let result = control.wait(&mut child);
+
+        assert!(matches!(result, Err(Error::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(child.try_wait().unwrap().is_some());
+    }
+
+    #[test]
+    fn time_out() {
+        let control =
+            Control::default().with_timeout(Some(Duration::from_millis(10)));
+        assert!(control.check().is_ok());
+
+        let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+        let result = control.wait(&mut child);
+
+        assert!(matches!(result, Err(Error::Timeout(_))));
+    }
+}
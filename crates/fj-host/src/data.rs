@@ -0,0 +1,198 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tracing::info_span;
+
+use crate::{
+    control::{Control, Stage},
+    Error, Parameters,
+};
+
+/// The formats that a model can be described in, by file extension
+pub(crate) const EXTENSIONS: &[&str] = &["json", "toml"];
+
+/// Load a model that is described in a data file
+///
+/// The file contains the serialized form of an [`fj::Shape`], in JSON or TOML.
+/// It can define parameters, with their default values, which can then be
+/// used in place of any value of the shape:
+///
+/// ``` toml
+/// [parameters]
+/// size = 2.0
+///
+/// [shape.Shape2d.Sketch]
+/// chain.Circle.radius = "$size"
+/// color = [255, 0, 0, 255]
+/// ```
+///
+/// A string that starts with `$` is replaced by the value of the parameter it
+/// names. To write a string that starts with `$`, start it with `$$` instead.
+pub(crate) fn load_shape(
+    path: &Path,
+    parameters: &Parameters,
+    control: &Control,
+) -> Result<fj::Shape, Error> {
+    control.check()?;
+    control.report(Stage::Loading);
+    let _span = info_span!("load_model").entered();
+
+    let source = fs::read_to_string(path)?;
+    let model: DataModel = match path.extension() {
+        Some(extension) if extension == "toml" => toml::from_str(&source)
+            .map_err(|err| Error::ParsingData(err.into()))?,
+        _ => serde_json::from_str(&source)
+            .map_err(|err| Error::ParsingData(err.into()))?,
+    };
+
+    let parameters = resolve_parameters(model.parameters, parameters)?;
+
+    let mut shape = model.shape;
+    substitute_parameters(&mut shape, &parameters)?;
+
+    serde_json::from_value(shape).map_err(Error::DataShape)
+}
+
+/// The contents of a data file that describes a model
+#[derive(Deserialize)]
+struct DataModel {
+    /// The parameters of the model, with their default values
+    #[serde(default)]
+    parameters: Map<String, Value>,
+
+    /// The shape of the model, possibly referring to parameters
+    shape: Value,
+}
+
+/// Determine the value of each parameter that the model defines
+///
+/// Parameters that have a string as their default value take the value that
+/// is passed for them as-is. Values that are passed for any other parameter are
+/// parsed as JSON, which covers numbers and booleans.
+fn resolve_parameters(
+    defaults: Map<String, Value>,
+    parameters: &Parameters,
+) -> Result<HashMap<String, Value>, Error> {
+    defaults
+        .into_iter()
+        .map(|(name, default)| {
+            let value = match (parameters.get(&name), default) {
+                (None, default) => default,
+                (Some(value), Value::String(_)) => Value::String(value.clone()),
+                (Some(value), _) => {
+                    serde_json::from_str(value).map_err(|source| {
+                        Error::InvalidParameter {
+                            name: name.clone(),
+                            value: value.clone(),
+                            source,
+                        }
+                    })?
+                }
+            };
+
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Replace all references to parameters with their values
+fn substitute_parameters(
+    value: &mut Value,
+    parameters: &HashMap<String, Value>,
+) -> Result<(), Error> {
+    match value {
+        Value::String(string) => {
+            if let Some(escaped) = string.strip_prefix("$$") {
+                *string = format!("${escaped}");
+            } else if let Some(name) = string.strip_prefix('$') {
+                *value = parameters
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(|| Error::UnknownParameter(name.to_string()))?;
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                substitute_parameters(value, parameters)?;
+            }
+        }
+        Value::Object(values) => {
+            for value in values.values_mut() {
+                substitute_parameters(value, parameters)?;
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::{control::Control, Error, Parameters};
+
+    #[test]
+    fn substitute_parameters() {
+        let path = env::temp_dir()
+            .join(format!("fj-host-data-model-{}.toml", process::id()));
+        fs::write(
+            &path,
+            r#"
+                [parameters]
+                radius = 1.0
+
+                [shape.Shape2d.Sketch]
+                chain.Circle.radius = "$radius"
+                color = [255, 0, 0, 255]
+            "#,
+        )
+        .unwrap();
+
+        let load = |parameters: &Parameters| {
+            super::load_shape(&path, parameters, &Control::default())
+        };
+        let circle = |radius: f64| -> fj::Shape {
+            fj::Sketch::from_circle(fj::Circle::from_radius(radius)).into()
+        };
+
+        let default = load(&Parameters::empty());
+        let overridden = load(Parameters::empty().insert("radius", 2));
+        let invalid = load(Parameters::empty().insert("radius", "large"));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(default.unwrap(), circle(1.));
+        assert_eq!(overridden.unwrap(), circle(2.));
+        assert!(matches!(invalid, Err(Error::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn unknown_parameter() {
+        let path = env::temp_dir()
+            .join(format!("fj-host-data-model-{}.json", process::id()));
+        fs::write(
+            &path,
+            r#"{
+                "shape": {
+                    "Shape2d": {
+                        "Sketch": {
+                            "chain": { "Circle": { "radius": "$radius" } },
+                            "color": [255, 0, 0, 255]
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let result =
+            super::load_shape(&path, &Parameters::empty(), &Control::default());
+        let _ = fs::remove_file(&path);
+
+        assert!(
+            matches!(result, Err(Error::UnknownParameter(name)) if name == "radius")
+        );
+    }
+}
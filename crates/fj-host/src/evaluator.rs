@@ -1,10 +1,31 @@
-use std::thread;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Instant,
+};
 
-use crossbeam_channel::{Receiver, SendError, Sender};
+use crossbeam_channel::{select, Receiver, SendError, Sender};
 
-use crate::{Error, Evaluation, Model};
+use crate::{
+    control::{Control, Stage},
+    Error, Evaluation, Model,
+};
 
 /// Evaluates a model in a background thread
+///
+/// An evaluation that is still in progress when the next one is triggered is
+/// cancelled, as its result would be outdated anyway. If the model has a
+/// timeout (see [`Model::with_timeout`]), evaluations that take longer than
+/// that are cancelled too.
+///
+/// A native model that is loaded into the current process can't be stopped.
+/// Instead of starting another evaluation alongside it, the next one is delayed
+/// until it has finished. If it keeps running after it has timed out, or has
+/// been cancelled already, further triggers are answered with
+/// [`Error::StillRunning`].
 pub struct Evaluator {
     trigger_tx: Sender<TriggerEvaluation>,
     event_rx: Receiver<ModelEvent>,
@@ -17,35 +38,123 @@ impl Evaluator {
         let (trigger_tx, trigger_rx) = crossbeam_channel::bounded(0);
 
         thread::spawn(move || {
-            while matches!(trigger_rx.recv(), Ok(TriggerEvaluation)) {
-                if let Err(SendError(_)) =
-                    event_tx.send(ModelEvent::ChangeDetected)
-                {
-                    break;
-                }
+            let model = Arc::new(model);
+            let mut job: Option<Job> = None;
+
+            // A job that has been cancelled, but can't be stopped, and whether
+            // another evaluation is waiting for it to finish.
+            let mut stuck: Option<Job> = None;
+            let mut pending = false;
+
+            loop {
+                let (progress, result, timeout) = match &job {
+                    Some(job) => (
+                        job.progress.clone(),
+                        job.result.clone(),
+                        job.deadline.map_or_else(
+                            crossbeam_channel::never,
+                            crossbeam_channel::at,
+                        ),
+                    ),
+                    None => (
+                        crossbeam_channel::never(),
+                        crossbeam_channel::never(),
+                        crossbeam_channel::never(),
+                    ),
+                };
+                let stuck_result = match &stuck {
+                    Some(job) => job.result.clone(),
+                    None => crossbeam_channel::never(),
+                };
 
-                let evaluation = match model.evaluate() {
-                    Ok(evaluation) => evaluation,
-                    Err(err) => {
-                        if let Err(SendError(_)) =
-                            event_tx.send(ModelEvent::Error(err))
-                        {
+                let event = select! {
+                    recv(trigger_rx) -> trigger => {
+                        if !matches!(trigger, Ok(TriggerEvaluation)) {
                             break;
                         }
-                        continue;
+
+                        let was_stuck = stuck.is_some();
+
+                        if let Some(job) = job.take() {
+                            job.cancel();
+
+                            if !model.can_be_stopped() {
+                                stuck = Some(job);
+                            }
+                        }
+
+                        if stuck.is_some() {
+                            pending = true;
+
+                            if was_stuck {
+                                ModelEvent::Error(Error::StillRunning)
+                            } else {
+                                ModelEvent::ChangeDetected
+                            }
+                        } else {
+                            job = Some(Job::start(model.clone()));
+                            ModelEvent::ChangeDetected
+                        }
+                    }
+                    recv(progress) -> stage => {
+                        match stage {
+                            Ok(stage) => ModelEvent::Progress(stage),
+                            // The result has been sent already.
+                            Err(_) => continue,
+                        }
+                    }
+                    recv(result) -> result => {
+                        job = None;
+
+                        match result {
+                            Ok(Ok(evaluation)) => {
+                                ModelEvent::Evaluation(evaluation)
+                            }
+                            Ok(Err(err)) => ModelEvent::Error(err),
+                            Err(_) => unreachable!(
+                                "Evaluation ended without sending result"
+                            ),
+                        }
+                    }
+                    recv(stuck_result) -> _ => {
+                        // The result of the cancelled job is outdated.
+                        stuck = None;
+
+                        if !pending {
+                            continue;
+                        }
+
+                        pending = false;
+                        job = Some(Job::start(model.clone()));
+                        ModelEvent::ChangeDetected
+                    }
+                    recv(timeout) -> _ => {
+                        if let Some(job) = job.take() {
+                            job.cancel();
+
+                            if !model.can_be_stopped() {
+                                stuck = Some(job);
+                            }
+                        }
+
+                        let timeout = model
+                            .timeout()
+                            .expect("Only jobs with a timeout time out");
+                        ModelEvent::Error(Error::Timeout(timeout))
                     }
                 };
 
-                if let Err(SendError(_)) =
-                    event_tx.send(ModelEvent::Evaluation(evaluation))
-                {
+                if let Err(SendError(_)) = event_tx.send(event) {
                     break;
-                };
+                }
             }
 
-            // The channel is disconnected, which means this instance of
+            // Either the channel is disconnected, which means this instance of
             // `Evaluator`, as well as all `Sender`s created from it, have been
-            // dropped. We're done.
+            // dropped; or nobody is listening to events anymore. We're done.
+            if let Some(job) = job {
+                job.cancel();
+            }
         });
 
         Self {
@@ -65,14 +174,67 @@ impl Evaluator {
     }
 }
 
+/// An evaluation that runs in its own thread
+struct Job {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    progress: Receiver<Stage>,
+    result: Receiver<Result<Evaluation, Error>>,
+}
+
+impl Job {
+    fn start(model: Arc<Model>) -> Self {
+        let (progress_tx, progress) = crossbeam_channel::unbounded();
+        let (result_tx, result) = crossbeam_channel::bounded(1);
+
+        let control = Control::default()
+            .with_timeout(model.timeout())
+            .with_progress(progress_tx);
+        let cancelled = control.cancellation();
+        let deadline = control.deadline();
+
+        thread::spawn(move || {
+            let result = model.evaluate_with(&control);
+
+            // Dropping the control closes the progress channel, which must
+            // only happen after the result has been sent.
+            let _ = result_tx.send(result);
+            drop(control);
+        });
+
+        Self {
+            cancelled,
+            deadline,
+            progress,
+            result,
+        }
+    }
+
+    /// Cancel the evaluation
+    ///
+    /// Compiling the model, and evaluating it in a worker process, stop right
+    /// away. A native model that is evaluated in the current process can't be
+    /// stopped. See [`Model::can_be_stopped`].
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
 /// Command received by [`Evaluator`] through its channel
 pub struct TriggerEvaluation;
 
 /// An event emitted by [`Evaluator`]
 pub enum ModelEvent {
     /// A change in the model has been detected
+    ///
+    /// An evaluation is starting. If another one was still in progress, it has
+    /// been cancelled. If it can't be stopped, the new evaluation starts once
+    /// it has finished.
     ChangeDetected,
 
+    /// The evaluation has entered a new stage
+    Progress(Stage),
+
     /// The model has been evaluated
     Evaluation(Evaluation),
 
@@ -1,6 +1,10 @@
+use std::thread;
+
 use crossbeam_channel::Receiver;
 
-use crate::{Error, Evaluator, Model, ModelEvent, Watcher};
+use crate::{
+    evaluator::TriggerEvaluation, Error, Evaluator, Model, ModelEvent, Watcher,
+};
 
 /// A Fornjot model host
 pub struct Host {
@@ -14,9 +18,9 @@ impl Host {
     /// This is only useful, if you want to continuously watch the model for
     /// changes. If you don't, just keep using `Model`.
     pub fn from_model(model: Model) -> Result<Self, Error> {
-        let watch_path = model.watch_path();
+        let watch_paths = model.watch_paths().to_vec();
         let evaluator = Evaluator::from_model(model);
-        let watcher = Watcher::watch_model(watch_path, &evaluator)?;
+        let watcher = Watcher::watch_model(watch_paths, &evaluator)?;
 
         Ok(Self {
             evaluator,
@@ -24,6 +28,22 @@ impl Host {
         })
     }
 
+    /// Evaluate the model again, even if it didn't change
+    ///
+    /// The result is reported through [`Host::events`], like any other
+    /// evaluation.
+    pub fn reload(&self) {
+        let trigger_tx = self.evaluator.trigger();
+
+        // The channel is bounded and has no buffer, so sending blocks until
+        // the evaluator is ready. Don't make the caller wait for that.
+        thread::spawn(move || {
+            // This only fails, if the evaluator is gone. Then there's nothing
+            // left to reload.
+            let _ = trigger_tx.send(TriggerEvaluation);
+        });
+    }
+
     /// Access a channel with evaluation events
     pub fn events(&self) -> Receiver<ModelEvent> {
         self.evaluator.events()
@@ -15,17 +15,26 @@
 
 #![warn(missing_docs)]
 
+mod control;
+mod data;
 mod evaluator;
 mod host;
 mod model;
 mod parameters;
 mod platform;
+mod profile;
+mod script;
+mod wasm;
 mod watcher;
+mod worker;
 
 pub use self::{
+    control::Stage,
     evaluator::{Evaluator, ModelEvent},
     host::Host,
     model::{Error, Evaluation, Model},
     parameters::Parameters,
+    profile::{profile_to_json, Profiler},
     watcher::Watcher,
+    worker::{run_if_worker, Worker},
 };
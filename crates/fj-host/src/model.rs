@@ -1,21 +1,29 @@
 use std::{
-    io,
+    collections::BTreeSet,
+    io::{self, Read},
     path::{Path, PathBuf},
-    process::Command,
-    str,
+    process::{Command, ExitStatus, Stdio},
+    str, thread,
+    time::Duration,
 };
 
 use fj::{abi, version::Version};
-use tracing::{debug, warn};
+use tracing::{debug, info_span, warn};
 
-use crate::{platform::HostPlatform, Parameters};
+use crate::{
+    control::{Control, Stage},
+    data,
+    platform::HostPlatform,
+    script, wasm, Parameters, Worker,
+};
 
 /// Represents a Fornjot model
 pub struct Model {
-    src_path: PathBuf,
-    lib_path: PathBuf,
-    manifest_path: PathBuf,
+    watch_paths: Vec<PathBuf>,
+    source: Source,
     parameters: Parameters,
+    worker: Option<Worker>,
+    timeout: Option<Duration>,
 }
 
 impl Model {
@@ -23,12 +31,48 @@ impl Model {
     ///
     /// The path expected here is the root directory of the model's Cargo
     /// package, that is the folder containing `Cargo.toml`.
+    ///
+    /// Alternatively, the path can refer to a model that has already been
+    /// compiled to WebAssembly (a `.wasm` file). Such a model isn't compiled
+    /// by the host, but runs in an embedded WebAssembly runtime, isolated from
+    /// the rest of the system.
+    ///
+    /// It can also refer to a data file (`.json` or `.toml`), which describes
+    /// the shape of the model, using the serialized form of [`fj::Shape`].
+    /// Such a model doesn't need to be compiled or run at all. The data file
+    /// can define parameters, and refer to them instead of using fixed values:
+    /// a string like `"$width"` is replaced by the value of the parameter
+    /// `width`.
+    ///
+    /// Finally, it can refer to a script (`.rhai`), which computes the shape
+    /// of the model using the same operations as a model written in Rust.
+    /// Scripts run in an embedded interpreter, and don't need to be compiled,
+    /// which makes them quick to iterate on.
     pub fn new(
         path: impl AsRef<Path>,
         parameters: Parameters,
     ) -> Result<Self, Error> {
         let path = path.as_ref();
 
+        if is_wasm(path) || is_data(path) || is_script(path) {
+            let file = path.canonicalize()?;
+            let source = if is_wasm(&file) {
+                Source::Wasm(file.clone())
+            } else if is_data(&file) {
+                Source::Data(file.clone())
+            } else {
+                Source::Script(file.clone())
+            };
+
+            return Ok(Self {
+                watch_paths: vec![file],
+                source,
+                parameters,
+                worker: None,
+                timeout: None,
+            });
+        }
+
         let crate_dir = path.canonicalize()?;
 
         let metadata = cargo_metadata::MetadataCommand::new()
@@ -36,7 +80,7 @@ impl Model {
             .exec()?;
 
         let pkg = package_associated_with_directory(&metadata, &crate_dir)?;
-        let src_path = crate_dir.join("src");
+        let watch_paths = watch_paths(&metadata, pkg);
 
         let lib_path = {
             let name = pkg.name.replace('-', "_");
@@ -47,124 +91,193 @@ impl Model {
         };
 
         Ok(Self {
-            src_path,
-            lib_path,
-            manifest_path: pkg.manifest_path.as_std_path().to_path_buf(),
+            watch_paths,
+            source: Source::Package {
+                manifest_path: pkg.manifest_path.as_std_path().to_path_buf(),
+                lib_path,
+            },
             parameters,
+            worker: None,
+            timeout: None,
         })
     }
 
-    /// Access the path that needs to be watched for changes
-    pub fn watch_path(&self) -> PathBuf {
-        self.src_path.clone()
+    /// Evaluate the model in a worker process, instead of loading it into the
+    /// current process
+    ///
+    /// This isolates the current process from anything that goes wrong in the
+    /// model, like memory corruption or infinite loops. See [`Worker`].
+    ///
+    /// Models that are described in a data file don't run any code, and
+    /// scripts run in an embedded interpreter, which can only access what the
+    /// host provides. Both are always evaluated in the current process.
+    pub fn with_worker(mut self, worker: Worker) -> Self {
+        self.worker = Some(worker);
+        self
     }
 
-    /// Evaluate the model
-    pub fn evaluate(&self) -> Result<Evaluation, Error> {
-        let manifest_path = self.manifest_path.display().to_string();
+    /// Stop evaluating the model, if that takes longer than `timeout`
+    ///
+    /// The timeout covers compiling the model, as well as computing its shape.
+    /// The compiler and the worker process (see [`Model::with_worker`]) are
+    /// killed when it runs out.
+    ///
+    /// A model that is loaded into the current process can't be stopped
+    /// though, unless it is a script, or compiled to WebAssembly. In that
+    /// case, [`Model::evaluate`] waits for it to finish. [`Evaluator`] reports
+    /// the timeout right away, but doesn't evaluate the model again, until the
+    /// evaluation has finished.
+    ///
+    /// [`Evaluator`]: crate::Evaluator
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 
-        let cargo_output = Command::new("cargo")
-            .arg("rustc")
-            .args(["--manifest-path", &manifest_path])
-            .args(["--crate-type", "cdylib"])
-            .output()?;
+    /// Access the timeout, if one was configured
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 
-        if !cargo_output.status.success() {
-            let output =
-                String::from_utf8(cargo_output.stderr).unwrap_or_else(|_| {
-                    String::from("Failed to fetch command output")
-                });
+    /// Whether an evaluation of the model can be stopped at any time
+    ///
+    /// That is not the case for a native model that is loaded into the
+    /// current process. See [`Model::with_timeout`].
+    pub(crate) fn can_be_stopped(&self) -> bool {
+        self.worker.is_some() || !matches!(self.source, Source::Package { .. })
+    }
 
-            return Err(Error::Compile { output });
-        }
+    /// Access the paths that need to be watched for changes
+    ///
+    /// Those are the sources and manifests of the model's package, as well as
+    /// those of all local packages it depends on (path dependencies). A path
+    /// can refer to a directory, which needs to be watched recursively, or to
+    /// a single file.
+    ///
+    /// For a model that has been compiled to WebAssembly, or that is described
+    /// in a data file or a script, that's just the file itself.
+    pub fn watch_paths(&self) -> &[PathBuf] {
+        &self.watch_paths
+    }
 
-        let seconds_taken = str::from_utf8(&cargo_output.stderr)
-            .unwrap()
-            .rsplit_once(' ')
-            .unwrap()
-            .1
-            .trim();
-
-        // So, strictly speaking this is all unsound:
-        // - `Library::new` requires us to abide by the arbitrary requirements
-        //   of any library initialization or termination routines.
-        // - `Library::get` requires us to specify the correct type for the
-        //   model function.
-        // - The model function itself is `unsafe`, because it is a function
-        //   from across an FFI interface.
-        //
-        // Typical models won't have initialization or termination routines (I
-        // think), should abide by the `ModelFn` signature, and might not do
-        // anything unsafe. But we have no way to know that the library the user
-        // told us to load actually does (I think).
-        //
-        // I don't know of a way to fix this. We should take this as motivation
-        // to switch to a better technique:
-        // https://github.com/hannobraun/Fornjot/issues/71
-        let shape = unsafe {
-            let lib = libloading::Library::new(&self.lib_path)
-                .map_err(Error::LoadingLibrary)?;
-
-            let version_pkg_host = fj::version::VERSION_PKG.to_string();
-
-            let version_pkg_model: libloading::Symbol<*const Version> =
-                lib.get(b"VERSION_PKG").map_err(Error::LoadingVersion)?;
-            let version_pkg_model = (**version_pkg_model).to_string();
-
-            debug!(
-                "Comparing package versions (host: {}, model: {})",
-                version_pkg_host, version_pkg_model
-            );
-            if version_pkg_host != version_pkg_model {
-                let host = String::from_utf8_lossy(version_pkg_host.as_bytes())
-                    .into_owned();
-                let model = version_pkg_model;
-
-                return Err(Error::VersionMismatch { host, model });
-            }
+    /// Evaluate the model
+    pub fn evaluate(&self) -> Result<Evaluation, Error> {
+        self.evaluate_with(&Control::default().with_timeout(self.timeout))
+    }
 
-            let version_full_host = fj::version::VERSION_FULL.to_string();
+    /// Evaluate the model, under the control of an [`Evaluator`]
+    ///
+    /// [`Evaluator`]: crate::Evaluator
+    pub(crate) fn evaluate_with(
+        &self,
+        control: &Control,
+    ) -> Result<Evaluation, Error> {
+        let _span = info_span!("evaluate_model").entered();
+
+        let (shape, compile_time) = match &self.source {
+            Source::Package {
+                manifest_path,
+                lib_path,
+            } => {
+                let compile_time = compile(manifest_path, control)?;
+                (self.load_compiled(lib_path, control)?, Some(compile_time))
+            }
+            Source::Wasm(wasm_path) => {
+                (self.load_compiled(wasm_path, control)?, None)
+            }
+            Source::Data(data_path) => {
+                let shape =
+                    data::load_shape(data_path, &self.parameters, control)?;
+                (shape, None)
+            }
+            Source::Script(script_path) => {
+                let shape =
+                    script::load_shape(script_path, &self.parameters, control)?;
+                (shape, None)
+            }
+        };
 
-            let version_full_model: libloading::Symbol<*const Version> =
-                lib.get(b"VERSION_FULL").map_err(Error::LoadingVersion)?;
-            let version_full_model = (**version_full_model).to_string();
+        Ok(Evaluation {
+            shape,
+            compile_time,
+        })
+    }
 
-            debug!(
-                "Comparing full versions (host: {}, model: {})",
-                version_full_host, version_full_model
-            );
-            if version_full_host != version_full_model {
-                let host =
-                    String::from_utf8_lossy(version_full_host.as_bytes())
-                        .into_owned();
-                let model = version_full_model;
+    /// Load a compiled model, in a worker process, if one was configured
+    fn load_compiled(
+        &self,
+        path: &Path,
+        control: &Control,
+    ) -> Result<fj::Shape, Error> {
+        match &self.worker {
+            Some(worker) => worker.evaluate(path, &self.parameters, control),
+            None => load_shape(path, &self.parameters, control),
+        }
+    }
+}
 
-                warn!("{}", Error::VersionMismatch { host, model });
-            }
+/// Where the shape of a model comes from
+enum Source {
+    /// A Cargo package, which the host compiles into a dynamic library
+    Package {
+        manifest_path: PathBuf,
+        lib_path: PathBuf,
+    },
 
-            let init: libloading::Symbol<abi::InitFunction> = lib
-                .get(abi::INIT_FUNCTION_NAME.as_bytes())
-                .map_err(Error::LoadingInit)?;
+    /// A model that has been compiled to WebAssembly
+    Wasm(PathBuf),
 
-            let mut host = Host::new(&self.parameters);
+    /// A data file that describes the shape of the model
+    Data(PathBuf),
 
-            match init(&mut abi::Host::from(&mut host)) {
-                abi::ffi_safe::Result::Ok(_metadata) => {}
-                abi::ffi_safe::Result::Err(e) => {
-                    return Err(Error::InitializeModel(e.into()));
-                }
-            }
+    /// A script that computes the shape of the model
+    Script(PathBuf),
+}
 
-            let model = host.take_model().ok_or(Error::NoModelRegistered)?;
+/// Compile the model's package into a dynamic library
+///
+/// Returns the time it took to compile the package, from the Cargo output.
+fn compile(manifest_path: &Path, control: &Control) -> Result<String, Error> {
+    control.check()?;
+    control.report(Stage::Compiling);
+    let _span = info_span!("compile_model").entered();
+
+    let manifest_path = manifest_path.display().to_string();
+
+    let mut cargo = Command::new("cargo")
+        .arg("rustc")
+        .args(["--manifest-path", &manifest_path])
+        .args(["--crate-type", "cdylib"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Cargo might block, if nobody reads its output. Read it in the
+    // background, while waiting for Cargo to finish.
+    let mut stderr = cargo.stderr.take().expect("Output is piped");
+    let stderr = thread::spawn(move || {
+        let mut output = Vec::new();
+        stderr.read_to_end(&mut output).map(|_| output)
+    });
+
+    let status = control.wait(&mut cargo)?;
+    let stderr = stderr.join().expect("Reading output doesn't panic")?;
+
+    if !status.success() {
+        let output = String::from_utf8(stderr)
+            .unwrap_or_else(|_| String::from("Failed to fetch command output"));
+
+        return Err(Error::Compile { output });
+    }
 
-            model.shape(&host).map_err(Error::Shape)?
-        };
+    let seconds_taken = str::from_utf8(&stderr)
+        .unwrap()
+        .rsplit_once(' ')
+        .unwrap()
+        .1
+        .trim();
 
-        Ok(Evaluation {
-            shape,
-            compile_time: seconds_taken.into(),
-        })
-    }
+    Ok(seconds_taken.into())
 }
 
 /// The result of evaluating a model
@@ -175,7 +288,107 @@ pub struct Evaluation {
     pub shape: fj::Shape,
 
     /// The time it took to compile the shape, from the Cargo output
-    pub compile_time: String,
+    ///
+    /// This is `None` for models that the host doesn't compile, like models
+    /// that have been compiled to WebAssembly already.
+    pub compile_time: Option<String>,
+}
+
+/// Load the compiled model library and compute the shape of the model
+///
+/// Models that have been compiled to WebAssembly are loaded into a WebAssembly
+/// runtime, instead of being loaded as a dynamic library.
+pub(crate) fn load_shape(
+    lib_path: &Path,
+    parameters: &Parameters,
+    control: &Control,
+) -> Result<fj::Shape, Error> {
+    if is_wasm(lib_path) {
+        return wasm::load_shape(lib_path, parameters, control);
+    }
+
+    control.check()?;
+    control.report(Stage::Loading);
+    let loading = info_span!("load_model").entered();
+
+    // So, strictly speaking this is all unsound:
+    // - `Library::new` requires us to abide by the arbitrary requirements
+    //   of any library initialization or termination routines.
+    // - `Library::get` requires us to specify the correct type for the
+    //   model function.
+    // - The model function itself is `unsafe`, because it is a function
+    //   from across an FFI interface.
+    //
+    // Typical models won't have initialization or termination routines (I
+    // think), should abide by the `ModelFn` signature, and might not do
+    // anything unsafe. But we have no way to know that the library the user
+    // told us to load actually does (I think).
+    //
+    // I don't know of a way to fix this. We should take this as motivation
+    // to switch to a better technique:
+    // https://github.com/hannobraun/Fornjot/issues/71
+    unsafe {
+        let lib = libloading::Library::new(lib_path)
+            .map_err(Error::LoadingLibrary)?;
+
+        let version_pkg_host = fj::version::VERSION_PKG.to_string();
+
+        let version_pkg_model: libloading::Symbol<*const Version> =
+            lib.get(b"VERSION_PKG").map_err(Error::LoadingVersion)?;
+        let version_pkg_model = (**version_pkg_model).to_string();
+
+        debug!(
+            "Comparing package versions (host: {}, model: {})",
+            version_pkg_host, version_pkg_model
+        );
+        if version_pkg_host != version_pkg_model {
+            let host = String::from_utf8_lossy(version_pkg_host.as_bytes())
+                .into_owned();
+            let model = version_pkg_model;
+
+            return Err(Error::VersionMismatch { host, model });
+        }
+
+        let version_full_host = fj::version::VERSION_FULL.to_string();
+
+        let version_full_model: libloading::Symbol<*const Version> =
+            lib.get(b"VERSION_FULL").map_err(Error::LoadingVersion)?;
+        let version_full_model = (**version_full_model).to_string();
+
+        debug!(
+            "Comparing full versions (host: {}, model: {})",
+            version_full_host, version_full_model
+        );
+        if version_full_host != version_full_model {
+            let host = String::from_utf8_lossy(version_full_host.as_bytes())
+                .into_owned();
+            let model = version_full_model;
+
+            warn!("{}", Error::VersionMismatch { host, model });
+        }
+
+        let init: libloading::Symbol<abi::InitFunction> = lib
+            .get(abi::INIT_FUNCTION_NAME.as_bytes())
+            .map_err(Error::LoadingInit)?;
+
+        let mut host = Host::new(parameters);
+
+        match init(&mut abi::Host::from(&mut host)) {
+            abi::ffi_safe::Result::Ok(_metadata) => {}
+            abi::ffi_safe::Result::Err(e) => {
+                return Err(Error::InitializeModel(e.into()));
+            }
+        }
+
+        let model = host.take_model().ok_or(Error::NoModelRegistered)?;
+
+        drop(loading);
+        control.check()?;
+        control.report(Stage::Evaluating);
+        let _span = info_span!("compute_shape").entered();
+
+        model.shape(&host).map_err(Error::Shape)
+    }
 }
 
 pub struct Host<'a> {
@@ -208,6 +421,24 @@ impl<'a> fj::models::Context for Host<'a> {
     }
 }
 
+/// Whether a path refers to a model that has been compiled to WebAssembly
+fn is_wasm(path: &Path) -> bool {
+    matches!(path.extension(), Some(extension) if extension == "wasm")
+}
+
+/// Whether a path refers to a data file that describes a model
+fn is_data(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some(extension) if data::EXTENSIONS.contains(&extension)
+    )
+}
+
+/// Whether a path refers to a script that computes the shape of a model
+fn is_script(path: &Path) -> bool {
+    matches!(path.extension(), Some(extension) if extension == "rhai")
+}
+
 fn package_associated_with_directory<'m>(
     metadata: &'m cargo_metadata::Metadata,
     dir: &Path,
@@ -226,6 +457,85 @@ fn package_associated_with_directory<'m>(
     Err(ambiguous_path_error(metadata, dir))
 }
 
+/// Determine the paths that need to be watched for changes to the model
+///
+/// Follows the dependency graph from the model's package, through all local
+/// packages. Packages from a registry or git repository are skipped, as they
+/// can't change without the manifest or lock file changing too.
+fn watch_paths(
+    metadata: &cargo_metadata::Metadata,
+    pkg: &cargo_metadata::Package,
+) -> Vec<PathBuf> {
+    let mut paths = BTreeSet::new();
+
+    // Changes to the workspace manifest (for example to `[patch]` sections)
+    // can affect the model too.
+    paths.insert(metadata.workspace_root.join("Cargo.toml").into());
+
+    let mut visited = BTreeSet::new();
+    let mut to_visit = vec![pkg.id.clone()];
+
+    while let Some(id) = to_visit.pop() {
+        if !visited.insert(id.clone()) {
+            continue;
+        }
+
+        let pkg = &metadata[&id];
+        if pkg.source.is_some() {
+            continue;
+        }
+
+        let pkg_dir = pkg
+            .manifest_path
+            .parent()
+            .expect("A Cargo.toml always has a parent");
+
+        paths.insert(pkg.manifest_path.clone().into());
+
+        for target in &pkg.targets {
+            let target_dir = target
+                .src_path
+                .parent()
+                .expect("A source file always has a parent");
+
+            // Sources usually live in a directory like `src/`, which we can
+            // watch as a whole. If they live in the package's root directory
+            // instead, as is usual for build scripts, we must not watch that
+            // directory, as it might contain the target directory. Changes to
+            // build artifacts would then trigger rebuilds, forever.
+            if target_dir == pkg_dir {
+                paths.insert(target.src_path.clone().into());
+            } else {
+                paths.insert(target_dir.to_path_buf().into());
+            }
+        }
+
+        let node = metadata.resolve.as_ref().and_then(|resolve| {
+            resolve.nodes.iter().find(|node| node.id == id)
+        });
+        let deps = node.into_iter().flat_map(|node| &node.deps).filter(|dep| {
+            // `dep_kinds` is empty for versions of Cargo that predate it.
+            dep.dep_kinds.is_empty()
+                || dep.dep_kinds.iter().any(|info| {
+                    info.kind != cargo_metadata::DependencyKind::Development
+                })
+        });
+        to_visit.extend(deps.map(|dep| dep.pkg.clone()));
+    }
+
+    // Drop paths that are already covered by a directory that is watched.
+    let covered = |path: &PathBuf| {
+        paths
+            .iter()
+            .any(|other| other != path && path.starts_with(other))
+    };
+    paths
+        .iter()
+        .filter(|path| !covered(path))
+        .cloned()
+        .collect()
+}
+
 fn ambiguous_path_error(
     metadata: &cargo_metadata::Metadata,
     dir: &Path,
@@ -288,6 +598,105 @@ pub enum Error {
         model: String,
     },
 
+    /// Error loading a model that has been compiled to WebAssembly
+    #[error(
+        "Failed to load WebAssembly model\n\
+        - Was the model compiled for the `wasm32-unknown-unknown` target?\n\
+        - Models can't import anything from the host. Does the model depend on \
+        a library that expects to run in a browser, or to have WASI available?"
+    )]
+    LoadingWasm(#[source] wasmi::Error),
+
+    /// A model that has been compiled to WebAssembly lacks an export
+    #[error(
+        "Failed to load `{0}` from WebAssembly model\n\
+        - Did you define a model function using `#[fj::model]`?\n\
+        - Did you enable the `wasm` feature of the `fj` library?"
+    )]
+    LoadingWasmExport(&'static str),
+
+    /// Running a model that has been compiled to WebAssembly failed
+    #[error("Error running WebAssembly model")]
+    RunningWasm(#[source] wasmi::Error),
+
+    /// A model that has been compiled to WebAssembly uses a different format
+    #[error(
+        "WebAssembly model returned its result in format version {}, but the \
+        host expects version {host}\n\
+        - Was the model compiled against a different version of Fornjot?",
+        model.map_or_else(|| String::from("(unknown)"), |v| v.to_string())
+    )]
+    WasmFormat {
+        /// The format version that the host expects
+        host: u32,
+
+        /// The format version that the model used, if it reported one
+        model: Option<u32>,
+    },
+
+    /// A model that has been compiled to WebAssembly ran out of fuel
+    #[error(
+        "WebAssembly model ran out of fuel after about {0} instructions\n\
+        - Does the model loop endlessly?"
+    )]
+    WasmFuel(u64),
+
+    /// The parameters are too large to pass to a WebAssembly model
+    #[error("Parameters are too large for WebAssembly model ({0} bytes)")]
+    WasmParameters(usize),
+
+    /// A model that has been compiled to WebAssembly reported an error
+    #[error("Error evaluating WebAssembly model\n{0}")]
+    Wasm(String),
+
+    /// Error parsing a data file that describes a model
+    #[error("Failed to parse model file")]
+    ParsingData(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// A data file doesn't describe a valid shape
+    #[error(
+        "Model file doesn't describe a valid shape\n\
+        - The shape must be in the serialized form of `fj::Shape`"
+    )]
+    DataShape(#[source] serde_json::Error),
+
+    /// A data file refers to a parameter that it doesn't define
+    #[error(
+        "Model refers to parameter `{0}`, which it doesn't define\n\
+        - Add the parameter and its default value to the `parameters` of the \
+        model file"
+    )]
+    UnknownParameter(String),
+
+    /// The value that was passed for a parameter is invalid
+    #[error("Invalid value for parameter `{name}`: `{value}`")]
+    InvalidParameter {
+        /// The name of the parameter
+        name: String,
+
+        /// The value that was passed
+        value: String,
+
+        /// The error that occurred while parsing the value
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// Error parsing a script that computes the shape of a model
+    #[error("Failed to parse model script")]
+    ParsingScript(#[source] rhai::ParseError),
+
+    /// Running a script that computes the shape of a model failed
+    #[error("Error running model script")]
+    Script(#[source] Box<rhai::EvalAltResult>),
+
+    /// A script didn't result in a shape
+    #[error(
+        "Model script resulted in a value of type `{0}`, instead of a shape\n\
+        - The last expression of the script must be the shape of the model"
+    )]
+    ScriptShape(&'static str),
+
     /// Model failed to compile
     #[error("Error compiling model\n{output}")]
     Compile {
@@ -312,6 +721,43 @@ pub enum Error {
     #[error("Unable to determine the model's geometry")]
     Shape(#[source] fj::models::Error),
 
+    /// Evaluating the model took longer than the configured timeout
+    #[error("Evaluating the model took longer than {0:?}")]
+    Timeout(Duration),
+
+    /// The evaluation was cancelled, as a newer one superseded it
+    #[error("Evaluation was cancelled")]
+    Cancelled,
+
+    /// A previous evaluation can't be stopped, and is still running
+    #[error(
+        "Previous evaluation of the model is still running, and can't be \
+        stopped\n\
+        - The model is evaluated again, once it has finished\n\
+        - Does the model contain an infinite loop? Evaluate it in a separate \
+        process, to be able to stop it."
+    )]
+    StillRunning,
+
+    /// Evaluating the model in a worker process took too long
+    #[error(
+        "Evaluating the model took longer than {0:?}\n\
+        - Does the model contain an infinite loop?"
+    )]
+    WorkerTimeout(Duration),
+
+    /// The worker process exited without reporting a result
+    #[error("Worker process exited without reporting a result ({0})")]
+    WorkerExit(ExitStatus),
+
+    /// The worker process reported an error
+    #[error("Error evaluating model in worker process\n{0}")]
+    Worker(String),
+
+    /// The result of the worker process could not be transferred
+    #[error("Error communicating with worker process")]
+    WorkerCommunication(#[from] serde_json::Error),
+
     /// Error while watching the model code for changes
     #[error("Error watching model for changes")]
     Notify(#[from] notify::Error),
@@ -0,0 +1,253 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Instant,
+};
+
+use fj_interop::profile::{Profile, Span};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Metadata, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+/// Collects the spans of Fornjot's crates into a [`Profile`]
+///
+/// Evaluating and processing a model is instrumented using spans of the
+/// [`tracing`] crate. `Profiler` is a [`Layer`] that records those spans, once
+/// it has been added to the subscriber. Spans of other crates are ignored.
+#[derive(Clone, Default)]
+pub struct Profiler {
+    spans: Arc<Mutex<Vec<(Instant, Span)>>>,
+}
+
+impl Profiler {
+    /// Construct an instance of `Profiler`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take all top-level spans that have been closed since the last call
+    pub fn take(&self) -> Profile {
+        let mut spans = std::mem::take(&mut *self.lock());
+        spans.sort_by_key(|(started, _)| *started);
+
+        Profile {
+            spans: spans.into_iter().map(|(_, span)| span).collect(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Vec<(Instant, Span)>> {
+        // The lock is only poisoned, if recording a span panicked. That can
+        // only leave a span out of the profile, which is fine.
+        self.spans.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<S> Layer<S> for Profiler
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes, id: &Id, ctx: Context<S>) {
+        if !is_fornjot(attrs.metadata()) {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut recording = Recording {
+            started: Instant::now(),
+            span: Span {
+                name: attrs.metadata().name(),
+                fields: Vec::new(),
+                duration: Default::default(),
+                count: 1,
+                children: Vec::new(),
+            },
+            children: Vec::new(),
+        };
+        attrs.record(&mut Fields(&mut recording.span.fields));
+
+        span.extensions_mut().insert(recording);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record, ctx: Context<S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut extensions = span.extensions_mut();
+        if let Some(recording) = extensions.get_mut::<Recording>() {
+            values.record(&mut Fields(&mut recording.span.fields));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(recording) = span.extensions_mut().remove::<Recording>()
+        else {
+            return;
+        };
+        let (started, finished) = recording.finish();
+
+        // Spans of other crates might be in between this span and the one it's
+        // a part of.
+        for ancestor in span.scope().skip(1) {
+            let mut extensions = ancestor.extensions_mut();
+            if let Some(parent) = extensions.get_mut::<Recording>() {
+                add(&mut parent.children, started, finished);
+                return;
+            }
+        }
+
+        add(&mut self.lock(), started, finished);
+    }
+}
+
+/// A span that hasn't been closed yet
+struct Recording {
+    started: Instant,
+    span: Span,
+    children: Vec<(Instant, Span)>,
+}
+
+impl Recording {
+    fn finish(mut self) -> (Instant, Span) {
+        self.span.duration = self.started.elapsed();
+
+        self.children.sort_by_key(|(started, _)| *started);
+        self.span.children =
+            self.children.into_iter().map(|(_, span)| span).collect();
+
+        (self.started, self.span)
+    }
+}
+
+/// Add a span to a list of spans
+///
+/// Spans without children are merged with a span with the same name and fields
+/// that is already in the list. Otherwise steps that are taken very often, like
+/// validating an object, would drown out everything else.
+fn add(spans: &mut Vec<(Instant, Span)>, started: Instant, span: Span) {
+    if span.children.is_empty() {
+        let same = spans.iter_mut().find(|(_, existing)| {
+            existing.children.is_empty()
+                && existing.name == span.name
+                && existing.fields == span.fields
+        });
+
+        if let Some((_, existing)) = same {
+            existing.duration += span.duration;
+            existing.count += span.count;
+            return;
+        }
+    }
+
+    spans.push((started, span));
+}
+
+fn is_fornjot(metadata: &Metadata) -> bool {
+    metadata.target().starts_with("fj_")
+}
+
+struct Fields<'r>(&'r mut Vec<(&'static str, String)>);
+
+impl Fields<'_> {
+    fn set(&mut self, field: &Field, value: String) {
+        match self.0.iter_mut().find(|(name, _)| *name == field.name()) {
+            Some((_, existing)) => *existing = value,
+            None => self.0.push((field.name(), value)),
+        }
+    }
+}
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.set(field, format!("{value:?}"));
+    }
+}
+
+/// Convert a [`Profile`] into JSON
+///
+/// Durations are given in milliseconds.
+pub fn profile_to_json(profile: &Profile) -> String {
+    fn span_to_json(span: &Span) -> serde_json::Value {
+        let fields = span
+            .fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone().into()))
+            .collect::<serde_json::Map<_, _>>();
+
+        serde_json::json!({
+            "name": span.name,
+            "fields": fields,
+            "duration_ms": span.duration.as_secs_f64() * 1000.,
+            "count": span.count,
+            "children": span.children.iter().map(span_to_json).collect::<Vec<_>>(),
+        })
+    }
+
+    let profile = serde_json::json!({
+        "duration_ms": profile.duration().as_secs_f64() * 1000.,
+        "spans": profile.spans.iter().map(span_to_json).collect::<Vec<_>>(),
+    });
+
+    format!("{profile:#}")
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::info_span;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::Profiler;
+
+    #[test]
+    fn record_tree_of_spans() {
+        let profiler = Profiler::new();
+        let subscriber = tracing_subscriber::registry().with(profiler.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _process = info_span!("process").entered();
+
+            for i in 0..3 {
+                let span =
+                    info_span!("compute", i, objects = tracing::field::Empty);
+                span.record("objects", i * 2);
+                span.in_scope(|| {
+                    info_span!("validate").in_scope(|| {});
+                    info_span!("validate").in_scope(|| {});
+                });
+            }
+        });
+
+        let profile = profiler.take();
+        assert_eq!(profile.spans.len(), 1);
+
+        let process = &profile.spans[0];
+        assert_eq!(process.name, "process");
+        assert_eq!(process.children.len(), 3);
+
+        for (i, compute) in process.children.iter().enumerate() {
+            assert_eq!(compute.field("i"), Some(i.to_string().as_str()));
+            assert_eq!(
+                compute.field("objects"),
+                Some((i * 2).to_string().as_str())
+            );
+
+            // Both validations are merged into one span.
+            assert_eq!(compute.children.len(), 1);
+            assert_eq!(compute.children[0].count, 2);
+        }
+
+        assert!(profiler.take().spans.is_empty());
+    }
+}
@@ -0,0 +1,424 @@
+use std::{fs, path::Path, sync::atomic::Ordering, time::Instant};
+
+use fj::syntax::{
+    Annotate as _, CircularPattern as _, Difference as _, Group as _,
+    Intersection as _, LinearPattern as _, Offset as _, Reference as _,
+    Sketch as _, Sweep as _, Transform as _, Union as _,
+};
+use rhai::{
+    module_resolvers::DummyModuleResolver, Array, Dynamic, Engine,
+    EvalAltResult, Scope, INT,
+};
+use tracing::{debug, info, info_span};
+
+use crate::{
+    control::{Control, Stage},
+    Error, Parameters,
+};
+
+/// Load a model that is written as a script, and compute its shape
+///
+/// The script is written in [Rhai], and runs in an embedded interpreter. Its
+/// last expression must be the shape of the model:
+///
+/// ``` rhai
+/// let radius = param("radius", 1.0);
+///
+/// circle(radius).with_color([255, 0, 0, 255]).sweep([0, 0, 2])
+/// ```
+///
+/// The shape is built from the same constructors and operations as in Rust
+/// (see [`fj::syntax`]). `param` returns the value that was passed for a
+/// parameter, or the default value otherwise. The passed value is converted to
+/// the type of the default value.
+///
+/// [Rhai]: https://rhai.rs/
+pub(crate) fn load_shape(
+    path: &Path,
+    parameters: &Parameters,
+    control: &Control,
+) -> Result<fj::Shape, Error> {
+    control.check()?;
+    control.report(Stage::Loading);
+    let loading = info_span!("load_model").entered();
+
+    let engine = engine(parameters, control);
+
+    let source = fs::read_to_string(path)?;
+    let script = engine.compile(source).map_err(Error::ParsingScript)?;
+
+    drop(loading);
+    control.check()?;
+    control.report(Stage::Evaluating);
+    let _span = info_span!("compute_shape").entered();
+
+    let result = engine
+        .eval_ast_with_scope::<Dynamic>(&mut Scope::new(), &script)
+        .map_err(|err| {
+            // If the script was stopped, report why, instead of just the fact
+            // that it was.
+            control.check().err().unwrap_or(Error::Script(err))
+        })?;
+
+    if result.is::<fj::Shape2d>() {
+        return Ok(fj::Shape::Shape2d(result.cast()));
+    }
+
+    let type_name = result.type_name();
+    result.try_cast().ok_or(Error::ScriptShape(type_name))
+}
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Create the interpreter that runs model scripts
+fn engine(parameters: &Parameters, control: &Control) -> Engine {
+    let mut engine = Engine::new();
+
+    // The host only watches the script itself for changes, so it can't pick
+    // up changes in modules that the script imports.
+    engine.set_module_resolver(DummyModuleResolver::new());
+
+    engine.on_print(|text| info!("{text}"));
+    engine.on_debug(|text, _, position| debug!("{text} ({position})"));
+
+    let cancelled = control.cancellation();
+    let deadline = control.deadline();
+    engine.on_progress(move |_| {
+        let stop = cancelled.load(Ordering::Relaxed)
+            || matches!(deadline, Some(deadline) if Instant::now() > deadline);
+        stop.then_some(Dynamic::UNIT)
+    });
+
+    let parameters = parameters.0.clone();
+    engine.register_fn("param", move |name: &str, default: Dynamic| {
+        match parameters.get(name) {
+            Some(value) => parameter(name, value, default),
+            None => Ok(default),
+        }
+    });
+
+    engine
+        .register_type_with_name::<fj::Shape>("Shape")
+        .register_type_with_name::<fj::Shape2d>("Shape2d")
+        .register_type_with_name::<fj::Angle>("Angle")
+        .register_type_with_name::<fj::Annotation>("Annotation")
+        .register_type_with_name::<fj::Reference>("Reference");
+
+    register_constructors(&mut engine);
+    register_2d_operations(&mut engine);
+    register_operations(&mut engine, |shape: fj::Shape| shape);
+    register_operations(&mut engine, fj::Shape::Shape2d);
+
+    engine
+}
+
+fn register_constructors(engine: &mut Engine) {
+    engine
+        .register_fn("sketch", |points: Array| -> ScriptResult<fj::Shape2d> {
+            let points = points
+                .into_iter()
+                .map(|point| vector(array(point)?))
+                .collect::<ScriptResult<Vec<[f64; 2]>>>()?;
+            Ok(points.sketch().into())
+        })
+        .register_fn("circle", |radius: Dynamic| -> ScriptResult<fj::Shape2d> {
+            let circle = fj::Circle::from_radius(number(radius)?);
+            Ok(fj::Sketch::from_circle(circle).into())
+        })
+        .register_fn("rad", |rad: Dynamic| -> ScriptResult<_> {
+            Ok(fj::Angle::from_rad(number(rad)?))
+        })
+        .register_fn("deg", |deg: Dynamic| -> ScriptResult<_> {
+            Ok(fj::Angle::from_deg(number(deg)?))
+        })
+        .register_fn("rev", |rev: Dynamic| -> ScriptResult<_> {
+            Ok(fj::Angle::from_rev(number(rev)?))
+        })
+        .register_fn("gon", |gon: Dynamic| -> ScriptResult<_> {
+            Ok(fj::Angle::from_gon(number(gon)?))
+        })
+        .register_fn(
+            "dimension",
+            |name: &str,
+             from: Array,
+             to: Array|
+             -> ScriptResult<fj::Annotation> {
+                Ok(fj::Dimension::new(name, vector(from)?, vector(to)?).into())
+            },
+        )
+        .register_fn(
+            "label",
+            |text: &str, position: Array| -> ScriptResult<fj::Annotation> {
+                Ok(fj::Label::new(text, vector(position)?).into())
+            },
+        )
+        .register_fn(
+            "reference_point",
+            |name: &str, position: Array| -> ScriptResult<fj::Annotation> {
+                Ok(fj::ReferencePoint::new(name, vector(position)?).into())
+            },
+        )
+        .register_fn("reference", |path: &str| fj::Reference::from_path(path))
+        .register_fn(
+            "with_color",
+            |reference: fj::Reference,
+             rgba: Array|
+             -> ScriptResult<fj::Reference> {
+                Ok(reference.with_color(color(rgba)?))
+            },
+        )
+        .register_fn("with_export", fj::Reference::with_export);
+}
+
+/// Register the operations that only apply to 2D shapes
+fn register_2d_operations(engine: &mut Engine) {
+    engine
+        .register_fn(
+            "with_color",
+            |shape: fj::Shape2d, rgba: Array| -> ScriptResult<fj::Shape2d> {
+                match shape {
+                    fj::Shape2d::Sketch(sketch) => {
+                        Ok(sketch.with_color(color(rgba)?).into())
+                    }
+                    _ => Err("Only sketches have their own color".into()),
+                }
+            },
+        )
+        .register_fn("difference", |a: fj::Shape2d, b: fj::Shape2d| {
+            fj::Shape2d::from(a.difference(&b))
+        })
+        .register_fn("intersection", |a: fj::Shape2d, b: fj::Shape2d| {
+            fj::Shape2d::from(a.intersection(&b))
+        })
+        .register_fn("union", |a: fj::Shape2d, b: fj::Shape2d| {
+            fj::Shape2d::from(a.union(&b))
+        })
+        .register_fn(
+            "offset",
+            |shape: fj::Shape2d,
+             distance: Dynamic|
+             -> ScriptResult<fj::Shape2d> {
+                Ok(shape.offset(number(distance)?).into())
+            },
+        )
+        .register_fn(
+            "sweep",
+            |shape: fj::Shape2d, path: Array| -> ScriptResult<fj::Shape> {
+                Ok(shape.sweep(vector(path)?).into())
+            },
+        );
+}
+
+/// Register the operations that apply to 2D and 3D shapes alike
+fn register_operations<T>(engine: &mut Engine, into_shape: fn(T) -> fj::Shape)
+where
+    T: Clone + Send + Sync + 'static,
+{
+    engine
+        .register_fn("group", move |a: T, b: fj::Shape| {
+            fj::Shape::from(into_shape(a).group(&b))
+        })
+        .register_fn("group", move |a: T, b: fj::Shape2d| {
+            fj::Shape::from(into_shape(a).group(&fj::Shape::Shape2d(b)))
+        })
+        .register_fn(
+            "translate",
+            move |shape: T, offset: Array| -> ScriptResult<_> {
+                let shape = into_shape(shape);
+                Ok(fj::Shape::from(shape.translate(vector(offset)?)))
+            },
+        )
+        .register_fn(
+            "rotate",
+            move |shape: T, axis: Array, angle: fj::Angle| -> ScriptResult<_> {
+                let shape = into_shape(shape);
+                Ok(fj::Shape::from(shape.rotate(vector(axis)?, angle)))
+            },
+        )
+        .register_fn(
+            "scale",
+            move |shape: T, factors: Array| -> ScriptResult<_> {
+                let shape = into_shape(shape);
+                Ok(fj::Shape::from(shape.scale(vector(factors)?)))
+            },
+        )
+        .register_fn(
+            "mirror",
+            move |shape: T, normal: Array| -> ScriptResult<_> {
+                let shape = into_shape(shape);
+                Ok(fj::Shape::from(shape.mirror(vector(normal)?)))
+            },
+        )
+        .register_fn(
+            "linear_pattern",
+            move |shape: T, offset: Array, count: INT| -> ScriptResult<_> {
+                let pattern = into_shape(shape)
+                    .linear_pattern(vector(offset)?, instances(count)?);
+                Ok(fj::Shape::from(pattern))
+            },
+        )
+        .register_fn(
+            "circular_pattern",
+            move |shape: T, axis: Array, count: INT| -> ScriptResult<_> {
+                let pattern = into_shape(shape)
+                    .circular_pattern(vector(axis)?, instances(count)?);
+                Ok(fj::Shape::from(pattern))
+            },
+        )
+        .register_fn("annotate", move |shape: T, annotation: fj::Annotation| {
+            fj::Shape::from(into_shape(shape).annotate(annotation))
+        })
+        .register_fn(
+            "with_reference",
+            move |shape: T, reference: fj::Reference| {
+                fj::Shape::from(into_shape(shape).with_reference(reference))
+            },
+        );
+}
+
+/// Convert the value that was passed for a parameter to the type of its
+/// default value
+fn parameter(
+    name: &str,
+    value: &str,
+    default: Dynamic,
+) -> ScriptResult<Dynamic> {
+    let parsed = if default.is::<INT>() {
+        value.parse::<INT>().ok().map(Dynamic::from)
+    } else if default.is_float() {
+        value.parse::<f64>().ok().map(Dynamic::from)
+    } else if default.is_bool() {
+        value.parse::<bool>().ok().map(Dynamic::from)
+    } else if default.is_string() {
+        Some(value.into())
+    } else {
+        return Err(format!(
+            "Default value of parameter `{name}` must be a number, a boolean, \
+            or a string, not `{}`",
+            default.type_name()
+        )
+        .into());
+    };
+
+    parsed.ok_or_else(|| {
+        format!(
+            "Invalid value for parameter `{name}`: `{value}` (expected `{}`)",
+            default.type_name()
+        )
+        .into()
+    })
+}
+
+fn number(value: Dynamic) -> ScriptResult<f64> {
+    match value.as_float() {
+        Ok(value) => Ok(value),
+        Err(_) => match value.as_int() {
+            Ok(value) => Ok(value as f64),
+            Err(type_name) => {
+                Err(format!("Expected a number, not `{type_name}`").into())
+            }
+        },
+    }
+}
+
+fn array(value: Dynamic) -> ScriptResult<Array> {
+    let type_name = value.type_name();
+    value
+        .try_cast()
+        .ok_or_else(|| format!("Expected an array, not `{type_name}`").into())
+}
+
+fn vector<const D: usize>(values: Array) -> ScriptResult<[f64; D]> {
+    let len = values.len();
+    let values = values
+        .into_iter()
+        .map(number)
+        .collect::<ScriptResult<Vec<_>>>()?;
+
+    values
+        .try_into()
+        .map_err(|_| format!("Expected {D} numbers, not {len}").into())
+}
+
+fn color(values: Array) -> ScriptResult<[u8; 4]> {
+    let len = values.len();
+    let values = values
+        .into_iter()
+        .map(|value| {
+            let component = value.as_int()?;
+            u8::try_from(component).map_err(|_| "integer outside of 0-255")
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("Expected a color, as RGBA values ({err})"))?;
+
+    values.try_into().map_err(|_| {
+        format!("Expected a color, as 4 RGBA values, not {len}").into()
+    })
+}
+
+fn instances(count: INT) -> ScriptResult<u32> {
+    u32::try_from(count)
+        .map_err(|_| format!("Invalid number of instances: {count}").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs, process, sync::atomic::Ordering, thread, time::Duration,
+    };
+
+    use crate::{control::Control, Error, Parameters};
+
+    #[test]
+    fn evaluate_script() {
+        let path = env::temp_dir()
+            .join(format!("fj-host-script-{}.rhai", process::id()));
+        fs::write(
+            &path,
+            r#"
+                let radius = param("radius", 1.0);
+                circle(radius).with_color([255, 0, 0, 255]).sweep([0, 0, 2])
+            "#,
+        )
+        .unwrap();
+
+        let load = |parameters: &Parameters| {
+            super::load_shape(&path, parameters, &Control::default())
+        };
+        let cylinder = |radius: f64| -> fj::Shape {
+            let circle =
+                fj::Sketch::from_circle(fj::Circle::from_radius(radius))
+                    .with_color([255, 0, 0, 255]);
+            fj::Sweep::from_path(circle.into(), [0., 0., 2.]).into()
+        };
+
+        let default = load(&Parameters::empty());
+        let overridden = load(Parameters::empty().insert("radius", 2));
+        let invalid = load(Parameters::empty().insert("radius", "large"));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(default.unwrap(), cylinder(1.));
+        assert_eq!(overridden.unwrap(), cylinder(2.));
+        assert!(matches!(invalid, Err(Error::Script(_))));
+    }
+
+    #[test]
+    fn cancel_script() {
+        let path = env::temp_dir()
+            .join(format!("fj-host-script-loop-{}.rhai", process::id()));
+        fs::write(&path, "loop {}").unwrap();
+
+        // Cancel the script while it runs, as it never finishes otherwise.
+        let control = Control::default();
+        let cancellation = control.cancellation();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            cancellation.store(true, Ordering::Relaxed);
+        });
+
+        let result = super::load_shape(&path, &Parameters::empty(), &control);
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(result, Err(Error::Cancelled)));
+    }
+}
@@ -0,0 +1,328 @@
+use std::{fs::File, io::BufReader, path::Path};
+
+use fj::abi::wasm;
+use serde::Deserialize;
+use tracing::{debug, info_span};
+use wasmi::{
+    core::TrapCode, errors::MemoryError, AsContext, Config, Engine, Instance,
+    Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+    WasmParams, WasmResults,
+};
+
+use crate::{
+    control::{Control, Stage},
+    Error, Parameters,
+};
+
+/// The fuel that a model gets for its first evaluation
+///
+/// That's roughly the number of instructions it can execute. Models only
+/// assemble the description of their shape, which usually takes a fraction of
+/// that.
+const INITIAL_FUEL: u64 = 1 << 24;
+
+/// The most fuel that a model gets for a single evaluation
+///
+/// The evaluation can only be stopped between runs, so this limits how long it
+/// takes until a timeout or cancellation takes effect. A model that needs more
+/// fuel than this most likely never finishes.
+const MAX_FUEL: u64 = 1 << 30;
+
+/// The most memory that a model can use, in bytes
+const MAX_MEMORY: usize = 1 << 30;
+
+/// Load a model that is compiled to WebAssembly and compute its shape
+///
+/// The model runs in an embedded WebAssembly runtime. It can't access anything
+/// outside of its own memory, as the host doesn't provide any imports to it.
+/// See [`fj::abi::wasm`] for how the host and the model communicate.
+///
+/// The model runs on a limited amount of fuel, so it can be stopped, if it
+/// takes too long or is cancelled. If it runs out of [`MAX_FUEL`], it fails.
+/// Its memory is limited to [`MAX_MEMORY`].
+pub(crate) fn load_shape(
+    wasm_path: &Path,
+    parameters: &Parameters,
+    control: &Control,
+) -> Result<fj::Shape, Error> {
+    control.check()?;
+    control.report(Stage::Loading);
+    let loading = info_span!("load_model").entered();
+
+    let mut config = Config::default();
+    config.consume_fuel(true);
+
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, BufReader::new(File::open(wasm_path)?))
+        .map_err(Error::LoadingWasm)?;
+
+    drop(loading);
+    control.check()?;
+    control.report(Stage::Evaluating);
+    let _span = info_span!("compute_shape").entered();
+
+    let parameters = serde_json::to_vec(&parameters.0)?;
+
+    // The runtime can't resume a model that ran out of fuel. But a model can't
+    // observe anything outside of its own memory, so evaluating it again from
+    // scratch leads to the same result. Until then, each time it runs out, the
+    // evaluation is stopped, if it should be, or started over with twice the
+    // fuel, up to `MAX_FUEL`.
+    let mut fuel = INITIAL_FUEL;
+    let result = loop {
+        match evaluate(&engine, &module, &parameters, fuel) {
+            Err(err) if is_out_of_fuel(&err) => {
+                if fuel >= MAX_FUEL {
+                    return Err(Error::WasmFuel(MAX_FUEL));
+                }
+
+                control.check()?;
+                fuel = (fuel * 2).min(MAX_FUEL);
+            }
+            result => break result?,
+        }
+    };
+
+    check_format(&result)?;
+
+    let evaluation: wasm::Evaluation = serde_json::from_slice(&result)?;
+    evaluation.result.map_err(Error::Wasm)
+}
+
+/// Instantiate the model, and evaluate it using the provided amount of fuel
+fn evaluate(
+    engine: &Engine,
+    module: &Module,
+    parameters: &[u8],
+    fuel: u64,
+) -> Result<Vec<u8>, Error> {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(MAX_MEMORY)
+        .trap_on_grow_failure(true)
+        .build();
+
+    let mut store = Store::new(engine, limits);
+    store.limiter(|limits| limits);
+    store
+        .add_fuel(fuel)
+        .expect("Fuel consumption has been enabled");
+
+    let instance = Linker::<StoreLimits>::new(engine)
+        .instantiate(&mut store, module)
+        .and_then(|instance| instance.start(&mut store))
+        .map_err(Error::LoadingWasm)?;
+
+    let exports = Exports::new(instance, &store)?;
+
+    exports
+        .evaluate(&mut store, parameters)
+        .map_err(|err| match err {
+            Error::RunningWasm(err) if !is_out_of_fuel_trap(&err) => {
+                // If the model panicked, the panic message is much more useful
+                // than the trap that stopped it.
+                match exports.last_panic(&mut store) {
+                    Some(message) => Error::Wasm(message),
+                    None => Error::RunningWasm(err),
+                }
+            }
+            err => err,
+        })
+}
+
+/// Whether the model was stopped, because it ran out of fuel
+fn is_out_of_fuel(err: &Error) -> bool {
+    match err {
+        Error::LoadingWasm(err) | Error::RunningWasm(err) => {
+            is_out_of_fuel_trap(err)
+        }
+        _ => false,
+    }
+}
+
+fn is_out_of_fuel_trap(err: &wasmi::Error) -> bool {
+    matches!(
+        err,
+        wasmi::Error::Trap(trap)
+            if matches!(trap.trap_code(), Some(TrapCode::OutOfFuel))
+    )
+}
+
+/// The exports of a model that is compiled to WebAssembly
+struct Exports {
+    memory: Memory,
+    alloc: TypedFunc<u32, u32>,
+    free: TypedFunc<(u32, u32), ()>,
+    evaluate: TypedFunc<(u32, u32), u64>,
+    last_panic: TypedFunc<(), u64>,
+}
+
+impl Exports {
+    fn new(
+        instance: Instance,
+        store: &Store<StoreLimits>,
+    ) -> Result<Self, Error> {
+        fn func<Params, Results>(
+            instance: Instance,
+            store: &Store<StoreLimits>,
+            name: &'static str,
+        ) -> Result<TypedFunc<Params, Results>, Error>
+        where
+            Params: WasmParams,
+            Results: WasmResults,
+        {
+            instance
+                .get_typed_func(store, name)
+                .map_err(|_| Error::LoadingWasmExport(name))
+        }
+
+        Ok(Self {
+            memory: instance
+                .get_memory(store, "memory")
+                .ok_or(Error::LoadingWasmExport("memory"))?,
+            alloc: func(instance, store, wasm::ALLOC_FUNCTION_NAME)?,
+            free: func(instance, store, wasm::FREE_FUNCTION_NAME)?,
+            evaluate: func(instance, store, wasm::EVALUATE_FUNCTION_NAME)?,
+            last_panic: func(instance, store, wasm::PANIC_FUNCTION_NAME)?,
+        })
+    }
+
+    fn evaluate(
+        &self,
+        store: &mut Store<StoreLimits>,
+        parameters: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let len = u32::try_from(parameters.len())
+            .map_err(|_| Error::WasmParameters(parameters.len()))?;
+
+        let mut evaluate = || {
+            let ptr = self.alloc.call(&mut *store, len)?;
+            self.memory.write(&mut *store, ptr as usize, parameters)?;
+
+            let result = self.evaluate.call(&mut *store, (ptr, len))?;
+            self.take(&mut *store, result)
+        };
+
+        evaluate().map_err(Error::RunningWasm)
+    }
+
+    fn last_panic(&self, store: &mut Store<StoreLimits>) -> Option<String> {
+        let message = self.last_panic.call(&mut *store, ()).ok()?;
+        if message == 0 {
+            return None;
+        }
+
+        let message = self.take(store, message).ok()?;
+        Some(String::from_utf8_lossy(&message).into_owned())
+    }
+
+    /// Read a buffer that the model returned, then free it
+    fn take(
+        &self,
+        store: &mut Store<StoreLimits>,
+        packed: u64,
+    ) -> Result<Vec<u8>, wasmi::Error> {
+        let ptr = (packed >> 32) as u32;
+        let len = packed as u32;
+
+        // The length comes from the model. Make sure that it's valid, before
+        // allocating a buffer that large.
+        let memory_size = self.memory.data(store.as_context()).len() as u64;
+        if u64::from(ptr) + u64::from(len) > memory_size {
+            return Err(MemoryError::OutOfBoundsAccess.into());
+        }
+
+        let mut buffer = vec![0; len as usize];
+        self.memory
+            .read(store.as_context(), ptr as usize, &mut buffer)?;
+        self.free.call(store, (ptr, len))?;
+
+        Ok(buffer)
+    }
+}
+
+/// Check the format of the model's result, before trusting the rest of it
+fn check_format(result: &[u8]) -> Result<(), Error> {
+    #[derive(Deserialize)]
+    struct Format {
+        format: Option<u32>,
+    }
+
+    let host = wasm::FORMAT_VERSION;
+    let model = serde_json::from_slice::<Format>(result)?.format;
+
+    debug!("Comparing format versions (host: {host}, model: {model:?})");
+    if model != Some(host) {
+        return Err(Error::WasmFormat { host, model });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process, time::Duration};
+
+    use crate::{control::Control, Error, Parameters};
+
+    #[test]
+    fn module_without_exports() {
+        // The smallest valid module: Just the magic number and the version.
+        let path = env::temp_dir()
+            .join(format!("fj-host-empty-{}.wasm", process::id()));
+        fs::write(&path, b"\0asm\x01\0\0\0").unwrap();
+
+        let result =
+            super::load_shape(&path, &Parameters::empty(), &Control::default());
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(result, Err(Error::LoadingWasmExport("memory"))));
+    }
+
+    #[test]
+    fn time_out_in_infinite_loop() {
+        // A module with all of the exports, whose `fj_model_evaluate` never
+        // returns.
+        #[rustfmt::skip]
+        let module: &[u8] = &[
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+            // Types
+            0x01, 0x15, 0x04,
+            0x60, 0x01, 0x7f, 0x01, 0x7f,
+            0x60, 0x02, 0x7f, 0x7f, 0x00,
+            0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7e,
+            0x60, 0x00, 0x01, 0x7e,
+            // Functions
+            0x03, 0x05, 0x04, 0x00, 0x01, 0x02, 0x03,
+            // Memory
+            0x05, 0x03, 0x01, 0x00, 0x01,
+            // Exports
+            0x07, 0x50, 0x05,
+            0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00,
+            0x0e, b'f', b'j', b'_', b'm', b'o', b'd', b'e', b'l', b'_',
+            b'a', b'l', b'l', b'o', b'c', 0x00, 0x00,
+            0x0d, b'f', b'j', b'_', b'm', b'o', b'd', b'e', b'l', b'_',
+            b'f', b'r', b'e', b'e', 0x00, 0x01,
+            0x11, b'f', b'j', b'_', b'm', b'o', b'd', b'e', b'l', b'_',
+            b'e', b'v', b'a', b'l', b'u', b'a', b't', b'e', 0x00, 0x02,
+            0x0e, b'f', b'j', b'_', b'm', b'o', b'd', b'e', b'l', b'_',
+            b'p', b'a', b'n', b'i', b'c', 0x00, 0x03,
+            // Code
+            0x0a, 0x18, 0x04,
+            0x04, 0x00, 0x41, 0x00, 0x0b,
+            0x02, 0x00, 0x0b,
+            0x09, 0x00, 0x03, 0x40, 0x0c, 0x00, 0x0b, 0x42, 0x00, 0x0b,
+            0x04, 0x00, 0x42, 0x00, 0x0b,
+        ];
+
+        let path = env::temp_dir()
+            .join(format!("fj-host-infinite-loop-{}.wasm", process::id()));
+        fs::write(&path, module).unwrap();
+
+        let control =
+            Control::default().with_timeout(Some(Duration::from_millis(10)));
+        let result = super::load_shape(&path, &Parameters::empty(), &control);
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(result, Err(Error::Timeout(_))));
+    }
+}
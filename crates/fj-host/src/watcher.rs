@@ -1,9 +1,23 @@
-use std::{collections::HashSet, ffi::OsStr, path::Path, thread};
+use std::{
+    collections::BTreeSet,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
 
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use notify::Watcher as _;
 
 use crate::{evaluator::TriggerEvaluation, Error, Evaluator};
 
+/// How long to wait for further changes, before triggering an evaluation
+///
+/// Saving a file often results in a burst of events, as editors write
+/// temporary files, rename them, and so on. Waiting until things have calmed
+/// down results in a single evaluation per burst.
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(100);
+
 /// Watches a model for changes, reloading it continually
 pub struct Watcher {
     _watcher: Box<dyn notify::Watcher>,
@@ -11,14 +25,20 @@ pub struct Watcher {
 
 impl Watcher {
     /// Watch the provided model for changes
+    ///
+    /// Directories in `watch_paths` are watched recursively. Files are watched
+    /// by watching their parent directory, as editors often replace files
+    /// instead of modifying them. See [`Model::watch_paths`].
+    ///
+    /// [`Model::watch_paths`]: crate::Model::watch_paths
     pub fn watch_model(
-        watch_path: impl AsRef<Path>,
+        watch_paths: impl IntoIterator<Item = impl AsRef<Path>>,
         evaluator: &Evaluator,
     ) -> Result<Self, Error> {
-        let watch_path = watch_path.as_ref();
+        let filter = WatchFilter::new(watch_paths);
+        let watches = filter.watches();
 
-        let watch_tx = evaluator.trigger();
-        let watch_tx_2 = evaluator.trigger();
+        let (change_tx, change_rx) = crossbeam_channel::unbounded();
 
         let mut watcher = notify::recommended_watcher(
             move |event: notify::Result<notify::Event>| {
@@ -26,65 +46,196 @@ impl Watcher {
                 // this might happen, so no idea if it needs to be handled.
                 let event = event.expect("Error handling watch event");
 
-                // Various acceptable ModifyKind kinds. Varies across platforms
-                // (e.g. MacOs vs. Windows10)
-                if let notify::EventKind::Modify(
-                    notify::event::ModifyKind::Any
-                    | notify::event::ModifyKind::Data(
-                        notify::event::DataChange::Any
-                        | notify::event::DataChange::Content,
-                    ),
-                ) = event.kind
-                {
-                    let file_ext = event
-                        .paths
-                        .get(0)
-                        .expect("File path missing in watch event")
-                        .extension();
-
-                    let black_list = HashSet::from([
-                        OsStr::new("swp"),
-                        OsStr::new("tmp"),
-                        OsStr::new("swx"),
-                    ]);
-
-                    if let Some(ext) = file_ext {
-                        if black_list.contains(ext) {
-                            return;
-                        }
-                    }
-
-                    // This will panic, if the other end is disconnected, which
-                    // is probably the result of a panic on that thread, or the
-                    // application is being shut down.
-                    //
-                    // Either way, not much we can do about it here.
-                    watch_tx
-                        .send(TriggerEvaluation)
-                        .expect("Channel is disconnected");
+                if filter.is_relevant(&event) {
+                    // This only fails, if the debouncing thread has stopped,
+                    // because the evaluator is gone. Nothing left to do then.
+                    let _ = change_tx.send(());
                 }
             },
         )?;
 
-        watcher.watch(watch_path, notify::RecursiveMode::Recursive)?;
+        for (path, mode) in watches {
+            watcher.watch(&path, mode)?;
+        }
 
         // To prevent a race condition between the initial load and the start of
-        // watching, we'll trigger the initial load here, after having started
+        // watching, the thread triggers the initial load, after we've started
         // watching.
         //
-        // This happens in a separate thread, because the channel is bounded and
-        // has no buffer.
-        //
-        // Will panic, if the receiving end has panicked. Not much we can do
-        // about that, if it happened.
-        thread::spawn(move || {
-            watch_tx_2
-                .send(TriggerEvaluation)
-                .expect("Channel is disconnected");
-        });
+        // The thread then waits for each burst of changes to end, before it
+        // triggers the next evaluation. See `debounce`.
+        let trigger_tx = evaluator.trigger();
+        thread::spawn(move || debounce(change_rx, trigger_tx));
 
         Ok(Self {
             _watcher: Box::new(watcher),
         })
     }
 }
+
+/// Decides which paths to watch, and which events are relevant
+struct WatchFilter {
+    dirs: Vec<PathBuf>,
+    files: BTreeSet<PathBuf>,
+}
+
+impl WatchFilter {
+    fn new(watch_paths: impl IntoIterator<Item = impl AsRef<Path>>) -> Self {
+        let mut dirs = Vec::new();
+        let mut files = BTreeSet::new();
+
+        for path in watch_paths {
+            let path = path.as_ref().to_path_buf();
+
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                files.insert(path);
+            }
+        }
+
+        Self { dirs, files }
+    }
+
+    /// The paths to pass to the `notify` watcher
+    fn watches(&self) -> Vec<(PathBuf, notify::RecursiveMode)> {
+        let mut watches = self
+            .dirs
+            .iter()
+            .map(|dir| (dir.clone(), notify::RecursiveMode::Recursive))
+            .collect::<Vec<_>>();
+
+        let parents = self
+            .files
+            .iter()
+            .filter_map(|file| file.parent())
+            .filter(|parent| !self.is_in_watched_dir(parent))
+            .collect::<BTreeSet<_>>();
+        watches.extend(parents.into_iter().map(|parent| {
+            (parent.to_path_buf(), notify::RecursiveMode::NonRecursive)
+        }));
+
+        watches
+    }
+
+    fn is_relevant(&self, event: &notify::Event) -> bool {
+        use notify::{
+            event::{DataChange, ModifyKind},
+            EventKind,
+        };
+
+        // Various acceptable kinds of events. Varies across platforms (e.g.
+        // MacOs vs. Windows10) and editors, some of which save files by
+        // creating a new file and renaming it.
+        let kind_is_relevant = matches!(
+            event.kind,
+            EventKind::Create(_)
+                | EventKind::Remove(_)
+                | EventKind::Modify(
+                    ModifyKind::Any
+                        | ModifyKind::Name(_)
+                        | ModifyKind::Data(
+                            DataChange::Any | DataChange::Content
+                        )
+                )
+        );
+
+        kind_is_relevant && event.paths.iter().any(|path| self.is_watched(path))
+    }
+
+    fn is_watched(&self, path: &Path) -> bool {
+        let black_list =
+            [OsStr::new("swp"), OsStr::new("tmp"), OsStr::new("swx")];
+
+        if let Some(ext) = path.extension() {
+            if black_list.contains(&ext) {
+                return false;
+            }
+        }
+
+        self.files.contains(path) || self.is_in_watched_dir(path)
+    }
+
+    fn is_in_watched_dir(&self, path: &Path) -> bool {
+        self.dirs.iter().any(|dir| path.starts_with(dir))
+    }
+}
+
+/// Trigger an evaluation for each burst of changes
+fn debounce(changes: Receiver<()>, trigger_tx: Sender<TriggerEvaluation>) {
+    // Trigger the initial load.
+    if trigger_tx.send(TriggerEvaluation).is_err() {
+        return;
+    }
+
+    while changes.recv().is_ok() {
+        loop {
+            match changes.recv_timeout(DEBOUNCE_DURATION) {
+                Ok(()) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        // If the evaluator is gone, the model is no longer being watched, and
+        // we're done.
+        if trigger_tx.send(TriggerEvaluation).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use notify::{
+        event::{CreateKind, DataChange, ModifyKind, RenameMode},
+        Event, EventKind,
+    };
+
+    use super::WatchFilter;
+
+    #[test]
+    fn relevant_events() {
+        let dir = std::env::temp_dir();
+        let manifest = PathBuf::from("/model/Cargo.toml");
+        let filter = WatchFilter::new([dir.clone(), manifest.clone()]);
+
+        let event = |kind, path: PathBuf| Event::new(kind).add_path(path);
+
+        let modify = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+        let create = EventKind::Create(CreateKind::File);
+        let rename = EventKind::Modify(ModifyKind::Name(RenameMode::To));
+
+        assert!(filter.is_relevant(&event(modify.clone(), dir.join("lib.rs"))));
+        assert!(filter.is_relevant(&event(create, dir.join("a/b.rs"))));
+        assert!(filter.is_relevant(&event(rename, manifest)));
+
+        assert!(
+            !filter.is_relevant(&event(modify.clone(), dir.join("lib.rs.swp")))
+        );
+        assert!(!filter.is_relevant(&event(modify, "/model/x.rs".into())));
+        assert!(!filter.is_relevant(&event(
+            EventKind::Access(notify::event::AccessKind::Any),
+            dir.join("lib.rs"),
+        )));
+    }
+
+    #[test]
+    fn watches() {
+        let dir = std::env::temp_dir();
+        let filter = WatchFilter::new([
+            dir.clone(),
+            dir.join("build.rs"),
+            PathBuf::from("/model/Cargo.toml"),
+        ]);
+
+        let watches = filter
+            .watches()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        assert_eq!(watches, [dir, PathBuf::from("/model")]);
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    env, error, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::info_span;
+
+use crate::{
+    control::{self, Control, Stage},
+    model::load_shape,
+    Error, Parameters,
+};
+
+/// Tells a process to run as a worker, and which model library to load
+const LIB_PATH_VAR: &str = "FJ_HOST_WORKER_LIB";
+
+/// The parameters of the model, serialized as JSON
+const PARAMETERS_VAR: &str = "FJ_HOST_WORKER_PARAMETERS";
+
+/// The file that the worker writes its result to
+const RESULT_PATH_VAR: &str = "FJ_HOST_WORKER_RESULT";
+
+/// Evaluates models in a separate process
+///
+/// Loading a model runs its code in the process that loads it. A model that
+/// corrupts memory or never finishes takes that process down with it. And
+/// since models are loaded again after every change, their libraries pile up
+/// in memory.
+///
+/// A worker process loads the model instead, and sends its shape back. If
+/// evaluating the model takes longer than the timeout, the worker is killed.
+///
+/// Workers run the program that is configured here. That program must call
+/// [`run_if_worker`] at the start of its `main` function.
+#[derive(Clone, Debug)]
+pub struct Worker {
+    program: PathBuf,
+    timeout: Duration,
+}
+
+impl Worker {
+    /// The default time that evaluating a model may take
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// Run workers using the given program
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Run workers using the executable of the current process
+    pub fn current_exe() -> Result<Self, Error> {
+        Ok(Self::new(env::current_exe()?))
+    }
+
+    /// Kill workers that take longer than `timeout` to evaluate the model
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Evaluate a compiled model in a new worker process
+    pub(crate) fn evaluate(
+        &self,
+        lib_path: &Path,
+        parameters: &Parameters,
+        control: &Control,
+    ) -> Result<fj::Shape, Error> {
+        let result_path = result_path();
+
+        // The worker loads the model too, so there's no separate stage for
+        // that, as far as the host is concerned.
+        control.check()?;
+        control.report(Stage::Evaluating);
+        let _span = info_span!("compute_shape", isolated = true).entered();
+
+        let mut child = Command::new(&self.program)
+            .env(LIB_PATH_VAR, lib_path)
+            .env(PARAMETERS_VAR, serde_json::to_string(&parameters.0)?)
+            .env(RESULT_PATH_VAR, &result_path)
+            .spawn()?;
+
+        let started = Instant::now();
+        let status = control::wait(&mut child, || {
+            if started.elapsed() > self.timeout {
+                return Err(Error::WorkerTimeout(self.timeout));
+            }
+            control.check()
+        });
+        let status = match status {
+            Ok(status) => status,
+            Err(err) => {
+                let _ = fs::remove_file(&result_path);
+                return Err(err);
+            }
+        };
+
+        let result = fs::read(&result_path);
+        let _ = fs::remove_file(&result_path);
+
+        // A worker that crashed might have left a partial result behind, so
+        // only trust the result of a worker that exited normally.
+        let result = match result {
+            Ok(result) if status.success() => result,
+            _ => return Err(Error::WorkerExit(status)),
+        };
+
+        match serde_json::from_slice(&result)? {
+            WorkerResult::Shape(shape) => Ok(shape),
+            WorkerResult::Error(message) => Err(Error::Worker(message)),
+        }
+    }
+}
+
+/// Run as a worker, if the current process was started as one
+///
+/// A worker evaluates the model, reports the result to the process that
+/// started it, then exits. This function doesn't return in that case. If the
+/// current process wasn't started as a worker, it returns right away.
+///
+/// See [`Worker`].
+pub fn run_if_worker() {
+    let Some(lib_path) = env::var_os(LIB_PATH_VAR) else {
+        return;
+    };
+
+    let result = match evaluate(Path::new(&lib_path)) {
+        Ok(shape) => WorkerResult::Shape(shape),
+        Err(err) => WorkerResult::Error(error_message(&err)),
+    };
+
+    match report(&result) {
+        Ok(()) => process::exit(0),
+        Err(err) => {
+            eprintln!("Worker failed to report result: {err}");
+            process::exit(1);
+        }
+    }
+}
+
+fn report(result: &WorkerResult) -> Result<(), Error> {
+    let result_path = env::var_os(RESULT_PATH_VAR).unwrap_or_default();
+    fs::write(result_path, serde_json::to_vec(result)?)?;
+
+    Ok(())
+}
+
+fn evaluate(lib_path: &Path) -> Result<fj::Shape, Error> {
+    let parameters = env::var(PARAMETERS_VAR).unwrap_or_default();
+    let parameters = serde_json::from_str(&parameters)?;
+
+    load_shape(lib_path, &Parameters(parameters), &Control::default())
+}
+
+/// The result of evaluating a model, as reported by the worker
+#[derive(Deserialize, Serialize)]
+enum WorkerResult {
+    Shape(fj::Shape),
+    Error(String),
+}
+
+/// Create a unique path for the result of a worker
+fn result_path() -> PathBuf {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+    env::temp_dir().join(format!("fj-host-worker-{}-{id}.json", process::id()))
+}
+
+/// Render an error, including all of its sources
+///
+/// The error can't be sent to the host as it is, so this is the next best
+/// thing.
+fn error_message(err: &Error) -> String {
+    let mut message = err.to_string();
+
+    let mut source = error::Error::source(err);
+    while let Some(err) = source {
+        message.push_str(&format!("\n\nCaused by:\n    {err}"));
+        source = err.source();
+    }
+
+    message
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::{control::Control, Error, Parameters};
+
+    use super::Worker;
+
+    #[test]
+    fn worker_without_result() {
+        // `true` exits successfully, without ever acting as a worker.
+        let worker = Worker::new("true");
+        let result = worker.evaluate(
+            "model.so".as_ref(),
+            &Parameters::empty(),
+            &Control::default(),
+        );
+
+        assert!(matches!(result, Err(Error::WorkerExit(_))));
+    }
+}
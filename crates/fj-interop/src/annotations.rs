@@ -0,0 +1,89 @@
+//! Annotations of a processed shape
+
+use fj_math::{Point, Scalar, Transform};
+
+/// An annotation that a model attached to a shape
+///
+/// Unlike the annotations that the model defines, these are given in the
+/// coordinates of the whole model, with all transformations applied.
+#[derive(Clone, Debug)]
+pub enum Annotation {
+    /// A named dimension, measured between two points
+    Dimension {
+        /// The name of the dimension
+        name: String,
+
+        /// The point the dimension is measured from
+        from: Point<3>,
+
+        /// The point the dimension is measured to
+        to: Point<3>,
+    },
+
+    /// A text label, attached to a point
+    Label {
+        /// The text of the label
+        text: String,
+
+        /// The point the label is attached to
+        position: Point<3>,
+    },
+
+    /// A named reference point
+    ReferencePoint {
+        /// The name of the reference point
+        name: String,
+
+        /// The position of the reference point
+        position: Point<3>,
+    },
+}
+
+impl Annotation {
+    /// Apply a transform to the annotation
+    pub fn transform(&self, transform: &Transform) -> Self {
+        match self {
+            Self::Dimension { name, from, to } => Self::Dimension {
+                name: name.clone(),
+                from: transform.transform_point(from),
+                to: transform.transform_point(to),
+            },
+            Self::Label { text, position } => Self::Label {
+                text: text.clone(),
+                position: transform.transform_point(position),
+            },
+            Self::ReferencePoint { name, position } => Self::ReferencePoint {
+                name: name.clone(),
+                position: transform.transform_point(position),
+            },
+        }
+    }
+
+    /// The value of a dimension, `None` for other annotations
+    pub fn value(&self) -> Option<Scalar> {
+        match self {
+            Self::Dimension { from, to, .. } => Some(from.distance_to(to)),
+            Self::Label { .. } | Self::ReferencePoint { .. } => None,
+        }
+    }
+
+    /// The point that the text of the annotation should be displayed at
+    pub fn anchor(&self) -> Point<3> {
+        match self {
+            Self::Dimension { from, to, .. } => *from + (*to - *from) * 0.5,
+            Self::Label { position, .. }
+            | Self::ReferencePoint { position, .. } => *position,
+        }
+    }
+
+    /// The text of the annotation, as it should be displayed
+    pub fn text(&self) -> String {
+        match self {
+            Self::Dimension { name, from, to } => {
+                format!("{name}: {:.3}", from.distance_to(to).into_f64())
+            }
+            Self::Label { text, .. } => text.clone(),
+            Self::ReferencePoint { name, .. } => name.clone(),
+        }
+    }
+}
@@ -27,6 +27,11 @@ impl DebugInfo {
     pub fn clear(&mut self) {
         self.triangle_edge_checks.clear();
     }
+
+    /// Add all information from another instance to this one
+    pub fn extend(&mut self, other: DebugInfo) {
+        self.triangle_edge_checks.extend(other.triangle_edge_checks);
+    }
 }
 
 /// Record of a check to determine if a triangle edge is within a face
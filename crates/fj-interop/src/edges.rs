@@ -0,0 +1,113 @@
+//! Edges of a processed shape
+
+use fj_math::{Point, Scalar, Segment, Vector};
+
+/// An edge of a processed shape
+///
+/// Unlike the triangle mesh, this preserves the exact geometry of the edge, if
+/// it is available.
+#[derive(Clone, Debug)]
+pub struct Edge {
+    /// The exact geometry of the edge, if available
+    pub geometry: Option<EdgeGeometry>,
+
+    /// The points that approximate the edge, from its start to its end
+    pub points: Vec<Point<3>>,
+}
+
+impl Edge {
+    /// Compute the length of the edge
+    ///
+    /// Uses the exact geometry, if available. Falls back to the length of the
+    /// approximation otherwise.
+    pub fn length(&self) -> Scalar {
+        match self.geometry {
+            Some(EdgeGeometry::Segment(segment)) => {
+                let [a, b] = segment.points();
+                a.distance_to(&b)
+            }
+            Some(EdgeGeometry::Arc { radius, angle, .. }) => radius * angle,
+            None => self
+                .points
+                .windows(2)
+                .map(|points| points[0].distance_to(&points[1]))
+                .fold(Scalar::ZERO, |length, part| length + part),
+        }
+    }
+
+    /// Find the point on the edge's approximation that is closest to `point`
+    ///
+    /// Returns `None`, if the edge has no points.
+    pub fn closest_point(&self, point: &Point<3>) -> Option<Point<3>> {
+        let closest_on_line = |[a, b]: [Point<3>; 2]| {
+            let direction = b - a;
+            let length_squared = direction.dot(&direction);
+
+            if length_squared == Scalar::ZERO {
+                return a;
+            }
+
+            let t = ((*point - a).dot(&direction) / length_squared)
+                .max(Scalar::ZERO)
+                .min(Scalar::ONE);
+            a + direction * t
+        };
+
+        if let [single] = self.points.as_slice() {
+            return Some(*single);
+        }
+
+        self.points
+            .windows(2)
+            .map(|points| closest_on_line([points[0], points[1]]))
+            .min_by_key(|closest| closest.distance_to(point))
+    }
+}
+
+/// The exact geometry of an [`Edge`]
+#[derive(Clone, Copy, Debug)]
+pub enum EdgeGeometry {
+    /// A straight edge
+    Segment(Segment<3>),
+
+    /// An edge that is part of a circle
+    ///
+    /// The edge starts at the first of its points, and runs counterclockwise
+    /// around `normal`.
+    Arc {
+        /// The center of the circle
+        center: Point<3>,
+
+        /// The radius of the circle
+        radius: Scalar,
+
+        /// The normal of the plane that the circle lies in
+        normal: Vector<3>,
+
+        /// The angle that the edge covers, in radians
+        angle: Scalar,
+    },
+}
+
+impl EdgeGeometry {
+    /// Reverse the direction of the edge
+    ///
+    /// The start of an arc is not part of its geometry, so this only reverses
+    /// its direction. Reversing the points of the edge is up to the caller.
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Segment(segment) => Self::Segment(segment.reverse()),
+            Self::Arc {
+                center,
+                radius,
+                normal,
+                angle,
+            } => Self::Arc {
+                center,
+                radius,
+                normal: -normal,
+                angle,
+            },
+        }
+    }
+}
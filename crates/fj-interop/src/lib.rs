@@ -14,7 +14,10 @@
 
 #![warn(missing_docs)]
 
+pub mod annotations;
 pub mod debug;
+pub mod edges;
 pub mod ext;
 pub mod mesh;
 pub mod processed_shape;
+pub mod profile;
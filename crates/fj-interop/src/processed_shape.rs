@@ -1,8 +1,10 @@
 //! A processed shape
 
-use fj_math::{Aabb, Point};
+use fj_math::{Aabb, Point, Scalar};
 
-use crate::{debug::DebugInfo, mesh::Mesh};
+use crate::{
+    annotations::Annotation, debug::DebugInfo, edges::Edge, mesh::Mesh,
+};
 
 /// A processed shape
 #[derive(Clone, Debug)]
@@ -13,6 +15,78 @@ pub struct ProcessedShape {
     /// The triangle mesh that approximates the original shape
     pub mesh: Mesh<Point<3>>,
 
+    /// Statistics about how well the triangle mesh approximates the shape
+    pub mesh_stats: MeshStats,
+
+    /// The edges of the shape, with their exact geometry where available
+    pub edges: Vec<Edge>,
+
+    /// The annotations that the model attached to the shape
+    pub annotations: Vec<Annotation>,
+
+    /// The reference bodies that the model placed next to the shape
+    ///
+    /// Reference bodies are not part of the shape, and are not included in
+    /// [`ProcessedShape::aabb`] or [`ProcessedShape::mesh`].
+    pub references: Vec<Reference>,
+
     /// The debug info generated while processing the shape
     pub debug_info: DebugInfo,
 }
+
+/// A reference body, loaded from a mesh file
+#[derive(Clone, Debug)]
+pub struct Reference {
+    /// The triangle mesh of the reference body, in the coordinates of the shape
+    ///
+    /// The triangles have the color of the reference body.
+    pub mesh: Mesh<Point<3>>,
+
+    /// Whether the reference body is included in exports
+    pub is_exported: bool,
+}
+
+/// Statistics about the triangle mesh of a processed shape
+#[derive(Clone, Debug, Default)]
+pub struct MeshStats {
+    /// The statistics of each face of the shape
+    pub faces: Vec<FaceStats>,
+}
+
+impl MeshStats {
+    /// The total number of triangles
+    pub fn triangles(&self) -> usize {
+        self.faces.iter().map(|face| face.triangles).sum()
+    }
+
+    /// The maximum distance between the triangle mesh and the shape
+    pub fn max_deviation(&self) -> Scalar {
+        self.faces
+            .iter()
+            .map(|face| face.max_deviation)
+            .max()
+            .unwrap_or(Scalar::ZERO)
+    }
+
+    /// The length of the longest edge of the triangle mesh
+    pub fn max_edge_length(&self) -> Scalar {
+        self.faces
+            .iter()
+            .map(|face| face.max_edge_length)
+            .max()
+            .unwrap_or(Scalar::ZERO)
+    }
+}
+
+/// Statistics about the triangles that approximate one face of a shape
+#[derive(Clone, Copy, Debug)]
+pub struct FaceStats {
+    /// The number of triangles
+    pub triangles: usize,
+
+    /// The maximum distance between the triangles and the face
+    pub max_deviation: Scalar,
+
+    /// The length of the longest edge of the triangles
+    pub max_edge_length: Scalar,
+}
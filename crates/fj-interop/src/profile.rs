@@ -0,0 +1,68 @@
+//! Timing information about the steps of evaluating and processing a model
+
+use std::time::Duration;
+
+/// A report on how long each step of evaluating and processing a model took
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    /// The top-level steps, in the order they were started
+    pub spans: Vec<Span>,
+}
+
+impl Profile {
+    /// Construct an empty instance of `Profile`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The total duration of the top-level steps
+    pub fn duration(&self) -> Duration {
+        self.spans.iter().map(|span| span.duration).sum()
+    }
+}
+
+/// A step of evaluating or processing a model, and the steps it consists of
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Span {
+    /// The name of the step, like `compute_brep`
+    pub name: &'static str,
+
+    /// Details about the step, like the kind of shape or the number of objects
+    pub fields: Vec<(&'static str, String)>,
+
+    /// How long the step took
+    ///
+    /// If the step was taken multiple times (see [`Span::count`]), this is the
+    /// sum of all of them.
+    pub duration: Duration,
+
+    /// How often the step was taken
+    ///
+    /// Steps without any children, that were taken multiple times with the
+    /// same details, are merged into a single span.
+    pub count: usize,
+
+    /// The steps that this step consists of, in the order they were started
+    pub children: Vec<Span>,
+}
+
+impl Span {
+    /// Access the value of a field, if the span has it
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Iterate over this span and all of its descendants, depth-first
+    pub fn iter(&self) -> impl Iterator<Item = &Span> {
+        let mut stack = vec![self];
+
+        std::iter::from_fn(move || {
+            let span = stack.pop()?;
+            stack.extend(span.children.iter().rev());
+            Some(span)
+        })
+    }
+}
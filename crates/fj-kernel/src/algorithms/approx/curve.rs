@@ -23,6 +23,9 @@ impl Approx for (&Handle<Curve>, RangeOnPath) {
     type Approximation = CurveApprox;
     type Cache = CurveCache;
 
+    /// # Panics
+    ///
+    /// Panics, if the curve is not supported. See [`check_supported`].
     fn approx_with_cache(
         self,
         tolerance: impl Into<Tolerance>,
@@ -51,24 +54,56 @@ impl Approx for (&Handle<Curve>, RangeOnPath) {
     }
 }
 
+/// Check that a curve can be approximated
+///
+/// Approximating a circle or an ellipse on a curved surface is not supported
+/// yet, and panics. Callers that can't rule out that case should check their
+/// curves first.
+pub fn check_supported(curve: &Curve) -> Result<(), UnsupportedCurve> {
+    match (curve.path(), curve.surface().geometry().u) {
+        (
+            SurfacePath::Circle(_) | SurfacePath::Ellipse(_),
+            GlobalPath::Circle(_) | GlobalPath::Ellipse(_),
+        ) => Err(UnsupportedCurve),
+        _ => Ok(()),
+    }
+}
+
+/// A curve that can't be approximated
+///
+/// See [`check_supported`].
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Approximating a circle or ellipse on a curved surface is not supported yet"
+)]
+pub struct UnsupportedCurve;
+
 fn approx_global_curve(
     curve: &Curve,
     range: RangeOnPath,
     tolerance: impl Into<Tolerance>,
 ) -> GlobalCurveApprox {
-    // There are different cases of varying complexity. Circles are the hard
-    // part here, as they need to be approximated, while lines don't need to be.
+    // There are different cases of varying complexity. Circles and ellipses
+    // are the hard part here, as they need to be approximated, while lines
+    // don't need to be.
     //
     // This will probably all be unified eventually, as `SurfacePath` and
     // `GlobalPath` grow APIs that are better suited to implementing this code
     // in a more abstract way.
     let points = match (curve.path(), curve.surface().geometry().u) {
-        (SurfacePath::Circle(_), GlobalPath::Circle(_)) => {
+        (
+            SurfacePath::Circle(_) | SurfacePath::Ellipse(_),
+            GlobalPath::Circle(_) | GlobalPath::Ellipse(_),
+        ) => {
             todo!(
-                "Approximating a circle on a curved surface not supported yet."
+                "Approximating a circle or ellipse on a curved surface not \
+                supported yet."
             )
         }
-        (SurfacePath::Circle(_), GlobalPath::Line(_)) => {
+        (
+            SurfacePath::Circle(_) | SurfacePath::Ellipse(_),
+            GlobalPath::Line(_),
+        ) => {
             (curve.path(), range)
                 .approx_with_cache(tolerance, &mut ())
                 .into_iter()
@@ -30,7 +30,7 @@
 
 use std::iter;
 
-use fj_math::{Circle, Point, Scalar, Sign};
+use fj_math::{Circle, Ellipse, Point, Scalar, Sign};
 
 use crate::geometry::path::{GlobalPath, SurfacePath};
 
@@ -51,6 +51,9 @@ impl Approx for (SurfacePath, RangeOnPath) {
             SurfacePath::Circle(circle) => {
                 approx_circle(&circle, range, tolerance.into())
             }
+            SurfacePath::Ellipse(ellipse) => {
+                approx_ellipse(&ellipse, range, tolerance.into())
+            }
             SurfacePath::Line(_) => vec![],
         }
     }
@@ -71,6 +74,9 @@ impl Approx for (GlobalPath, RangeOnPath) {
             GlobalPath::Circle(circle) => {
                 approx_circle(&circle, range, tolerance.into())
             }
+            GlobalPath::Ellipse(ellipse) => {
+                approx_ellipse(&ellipse, range, tolerance.into())
+            }
             GlobalPath::Line(_) => vec![],
         }
     }
@@ -115,6 +121,28 @@ fn approx_circle<const D: usize>(
     points
 }
 
+/// Approximate an ellipse
+///
+/// `tolerance` specifies how much the approximation is allowed to deviate
+/// from the ellipse.
+fn approx_ellipse<const D: usize>(
+    ellipse: &Ellipse<D>,
+    range: impl Into<RangeOnPath>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<D>)> {
+    let range = range.into();
+
+    let params = PathApproxParams::for_ellipse(ellipse, tolerance);
+    let mut points = Vec::new();
+
+    for point_curve in params.points(range) {
+        let point_global = ellipse.point_from_ellipse_coords(point_curve);
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
 struct PathApproxParams {
     increment: Scalar,
 }
@@ -124,14 +152,45 @@ impl PathApproxParams {
         circle: &Circle<D>,
         tolerance: impl Into<Tolerance>,
     ) -> Self {
-        let radius = circle.a().magnitude();
+        Self::for_radius(circle.a().magnitude(), tolerance)
+    }
+
+    pub fn for_ellipse<const D: usize>(
+        ellipse: &Ellipse<D>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        // An ellipse is a circle, stretched by at most its major radius. The
+        // same is true for the deviation of the approximation from the curve,
+        // so the increment that is good enough for a circle of that radius is
+        // also good enough for the ellipse.
+        Self::for_radius(ellipse.major_radius(), tolerance)
+    }
+
+    fn for_radius(radius: Scalar, tolerance: impl Into<Tolerance>) -> Self {
+        let tolerance = tolerance.into();
 
-        let num_vertices_to_approx_full_circle = Scalar::max(
-            Scalar::PI
-                / (Scalar::ONE - (tolerance.into().inner() / radius)).acos(),
-            3.,
-        )
-        .ceil();
+        let mut num_vertices_to_approx_full_circle =
+            Scalar::PI / (Scalar::ONE - (tolerance.inner() / radius)).acos();
+
+        // Each segment of the approximation spans `increment` radians, which
+        // is also the angle between neighboring segments.
+        if let Some(angle) = tolerance.angle() {
+            num_vertices_to_approx_full_circle =
+                num_vertices_to_approx_full_circle.max(Scalar::TAU / angle);
+        }
+
+        // The length of each segment is `2 * radius * sin(increment / 2)`.
+        if let Some(length) = tolerance.max_edge_length() {
+            let diameter = radius * 2.;
+            if length < diameter {
+                num_vertices_to_approx_full_circle =
+                    num_vertices_to_approx_full_circle
+                        .max(Scalar::PI / (length / diameter).asin());
+            }
+        }
+
+        let num_vertices_to_approx_full_circle =
+            Scalar::max(num_vertices_to_approx_full_circle, 3.).ceil();
 
         let increment = Scalar::TAU / num_vertices_to_approx_full_circle;
 
@@ -185,7 +244,7 @@ impl PathApproxParams {
 mod tests {
     use std::f64::consts::TAU;
 
-    use fj_math::{Circle, Point, Scalar};
+    use fj_math::{Circle, Ellipse, Point, Scalar};
 
     use crate::algorithms::approx::{path::RangeOnPath, Tolerance};
 
@@ -210,6 +269,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn increment_for_circle_with_limits() {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+
+        // The deviation alone would require 7 vertices.
+        let tolerance = Tolerance::from_scalar(0.1).unwrap();
+
+        let with_angle = tolerance.with_angle(TAU / 12.).unwrap();
+        assert_eq!(
+            PathApproxParams::for_circle(&circle, with_angle).increment(),
+            Scalar::TAU / 12.,
+        );
+
+        // A chord of length 0.5 spans about 29 degrees of a circle with radius
+        // 1, so 13 of them are required.
+        let with_length = tolerance.with_max_edge_length(0.5).unwrap();
+        assert_eq!(
+            PathApproxParams::for_circle(&circle, with_length).increment(),
+            Scalar::TAU / 13.,
+        );
+    }
+
+    #[test]
+    fn increment_for_ellipse() {
+        let tolerance = 0.1;
+
+        let ellipse = Ellipse::new([0., 0.], [1., 0.], [0., 2.]);
+        let circle = Circle::from_center_and_radius([0., 0.], 2.);
+
+        assert_eq!(
+            PathApproxParams::for_ellipse(&ellipse, tolerance).increment(),
+            PathApproxParams::for_circle(&circle, tolerance).increment(),
+        );
+    }
+
     #[test]
     fn points_for_circle() {
         // At the chosen values for radius and tolerance (see below), the
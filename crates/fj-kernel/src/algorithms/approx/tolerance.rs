@@ -12,6 +12,11 @@ use fj_math::Scalar;
 /// The `Tolerance` type enforces that the tolerance value is always larger than
 /// zero, which is an attribute that the approximation code relies on.
 ///
+/// In addition to the deviation, a tolerance can limit the angle between
+/// neighboring segments of an approximated curve, and the length of the edges
+/// of the resulting triangle mesh. See [`Tolerance::with_angle`] and
+/// [`Tolerance::with_max_edge_length`].
+///
 /// # Failing [`From`]/[`Into`] implementation
 ///
 /// The [`From`]/[`Into`] implementations of tolerance are fallible, which goes
@@ -22,7 +27,11 @@ use fj_math::Scalar;
 /// documentation doesn't provide any actual reasoning for this requirement, I'm
 /// feeling free to just ignore it.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct Tolerance(Scalar);
+pub struct Tolerance {
+    deviation: Scalar,
+    angle: Option<Scalar>,
+    max_edge_length: Option<Scalar>,
+}
 
 impl Tolerance {
     /// Construct a `Tolerance` from a [`Scalar`]
@@ -37,12 +46,63 @@ impl Tolerance {
             return Err(InvalidTolerance(scalar));
         }
 
-        Ok(Self(scalar))
+        Ok(Self {
+            deviation: scalar,
+            angle: None,
+            max_edge_length: None,
+        })
     }
 
-    /// Return the [`Scalar`] that defines the tolerance
+    /// Limit the angle between neighboring segments of approximated curves
+    ///
+    /// The angle is given in radians. On curved faces, this is also the angle
+    /// between the normals of neighboring triangles.
+    ///
+    /// Returns an error, if the passed angle is not larger than zero.
+    pub fn with_angle(
+        mut self,
+        angle: impl Into<Scalar>,
+    ) -> Result<Self, InvalidTolerance> {
+        let angle = angle.into();
+
+        if angle <= Scalar::ZERO {
+            return Err(InvalidTolerance(angle));
+        }
+
+        self.angle = Some(angle);
+        Ok(self)
+    }
+
+    /// Limit the length of the edges of the triangle mesh
+    ///
+    /// Returns an error, if the passed length is not larger than zero.
+    pub fn with_max_edge_length(
+        mut self,
+        length: impl Into<Scalar>,
+    ) -> Result<Self, InvalidTolerance> {
+        let length = length.into();
+
+        if length <= Scalar::ZERO {
+            return Err(InvalidTolerance(length));
+        }
+
+        self.max_edge_length = Some(length);
+        Ok(self)
+    }
+
+    /// Return the [`Scalar`] that defines the maximum deviation
     pub fn inner(&self) -> Scalar {
-        self.0
+        self.deviation
+    }
+
+    /// Return the maximum angle between neighboring segments, if limited
+    pub fn angle(&self) -> Option<Scalar> {
+        self.angle
+    }
+
+    /// Return the maximum length of mesh edges, if limited
+    pub fn max_edge_length(&self) -> Option<Scalar> {
+        self.max_edge_length
     }
 }
 
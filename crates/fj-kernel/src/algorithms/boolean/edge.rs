@@ -0,0 +1,438 @@
+use fj_math::{Circle, Ellipse, Line, Point, Scalar, Segment, Vector};
+
+use crate::algorithms::intersect::{
+    CircleCircleIntersection, LineEllipseIntersection, LineSegmentIntersection,
+};
+
+/// An edge on the boundary of a 2-dimensional shape, in surface coordinates
+///
+/// This is a lightweight representation of a [`HalfEdge`], which the boolean
+/// operations use to split edges and classify the resulting pieces, before
+/// any new objects are built.
+///
+/// Each edge spans a range of parameters. For line segments, that range is
+/// always `[0, 1]`. For arcs, it is a range of circle coordinates.
+///
+/// [`HalfEdge`]: crate::objects::HalfEdge
+#[derive(Clone, Copy, Debug)]
+pub enum BoundaryEdge {
+    /// A line segment
+    Segment {
+        /// The points that bound the line segment
+        points: [Point<2>; 2],
+    },
+
+    /// An arc of a circle, possibly the full circle
+    Arc {
+        /// The circle that the arc is on
+        circle: Circle<2>,
+
+        /// The range of circle coordinates that the arc spans
+        range: [Scalar; 2],
+    },
+}
+
+impl BoundaryEdge {
+    /// Access the range of parameters that the edge spans
+    pub fn range(&self) -> [Scalar; 2] {
+        match self {
+            Self::Segment { .. } => [Scalar::ZERO, Scalar::ONE],
+            Self::Arc { range, .. } => *range,
+        }
+    }
+
+    /// Convert a parameter on the edge into a point
+    pub fn point_from_param(&self, t: Scalar) -> Point<2> {
+        match self {
+            Self::Segment { points: [a, b] } => *a + (*b - *a) * t,
+            Self::Arc { circle, .. } => circle.point_from_circle_coords([t]),
+        }
+    }
+
+    /// Access the points that bound the edge
+    pub fn points(&self) -> [Point<2>; 2] {
+        self.range().map(|t| self.point_from_param(t))
+    }
+
+    /// Compute the point in the middle of the edge
+    pub fn midpoint(&self) -> Point<2> {
+        let [a, b] = self.range();
+        self.point_from_param((a + b) / 2.)
+    }
+
+    /// Determine whether the edge is a full circle
+    pub fn is_full_circle(&self) -> bool {
+        match self {
+            Self::Segment { .. } => false,
+            Self::Arc { range, .. } => {
+                (range[1] - range[0]).abs() >= Scalar::TAU
+            }
+        }
+    }
+
+    /// Compute the direction of the edge at its start and end points
+    ///
+    /// The returned vectors are normalized.
+    pub fn tangents(&self) -> [Vector<2>; 2] {
+        match self {
+            Self::Segment { points: [a, b] } => {
+                let direction = (*b - *a).normalize();
+                [direction, direction]
+            }
+            Self::Arc { circle, range } => {
+                let direction = if range[0] < range[1] {
+                    Scalar::ONE
+                } else {
+                    -Scalar::ONE
+                };
+
+                range.map(|t| {
+                    let (sin, cos) = t.sin_cos();
+                    let tangent = -circle.a() * sin + circle.b() * cos;
+                    tangent.normalize() * direction
+                })
+            }
+        }
+    }
+
+    /// Create a new instance that is reversed
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Segment { points: [a, b] } => {
+                Self::Segment { points: [b, a] }
+            }
+            Self::Arc {
+                circle,
+                range: [a, b],
+            } => Self::Arc {
+                circle,
+                range: [b, a],
+            },
+        }
+    }
+
+    /// Adjust the end of the edge, so it ends exactly at the given point
+    ///
+    /// The point must be very close to the current end of the edge. This is
+    /// used to compensate for numerical inaccuracies, where arcs meet.
+    pub fn with_end_at(self, point: Point<2>) -> Self {
+        match self {
+            Self::Segment { points: [a, _] } => {
+                Self::Segment { points: [a, point] }
+            }
+            Self::Arc {
+                circle,
+                range: [start, end],
+            } => {
+                let [_, end_point] = self.points();
+
+                let delta = circle_coord(&circle, point)
+                    - circle_coord(&circle, end_point);
+                let delta = delta - Scalar::TAU * (delta / Scalar::TAU).round();
+
+                Self::Arc {
+                    circle,
+                    range: [start, end + delta],
+                }
+            }
+        }
+    }
+
+    /// Split the edge at the given parameters
+    ///
+    /// Parameters that are not strictly within the edge's range (taking
+    /// `epsilon` into account) are ignored, as are parameters that are too
+    /// close to each other.
+    pub fn split(
+        &self,
+        params: impl IntoIterator<Item = Scalar>,
+        epsilon: Scalar,
+    ) -> Vec<Self> {
+        let [start, end] = self.range();
+        let tolerance = self.param_tolerance(epsilon);
+
+        // Sort the parameters in the direction of the edge, so they can be
+        // processed from the start of the edge to its end.
+        let direction = if start < end {
+            Scalar::ONE
+        } else {
+            -Scalar::ONE
+        };
+        let mut params = params
+            .into_iter()
+            .map(|t| (t - start) * direction)
+            .filter(|&t| t > tolerance && t < (end - start).abs() - tolerance)
+            .collect::<Vec<_>>();
+        params.sort();
+        params.dedup_by(|a, b| *a - *b < tolerance);
+
+        let mut boundaries = vec![start];
+        boundaries.extend(params.into_iter().map(|t| start + t * direction));
+        boundaries.push(end);
+
+        boundaries
+            .windows(2)
+            .map(|range| match self {
+                Self::Segment { .. } => Self::Segment {
+                    points: [range[0], range[1]]
+                        .map(|t| self.point_from_param(t)),
+                },
+                Self::Arc { circle, .. } => Self::Arc {
+                    circle: *circle,
+                    range: [range[0], range[1]],
+                },
+            })
+            .collect()
+    }
+
+    /// Convert a point into a parameter on the edge, if it is on the edge
+    ///
+    /// Points are considered to be on the edge, if their distance to it is
+    /// less than `epsilon`.
+    pub fn point_to_param(
+        &self,
+        point: Point<2>,
+        epsilon: Scalar,
+    ) -> Option<Scalar> {
+        let t = match self {
+            Self::Segment { points: [a, b] } => {
+                let ab = *b - *a;
+                (point - *a).dot(&ab) / ab.dot(&ab)
+            }
+            Self::Arc { circle, range } => {
+                // The circle coordinate is in the range `[-PI, PI]`, but the
+                // range of the arc could be anywhere. Find the representation
+                // of the coordinate that is closest to the arc's range.
+                let t = circle_coord(circle, point);
+                let mid = (range[0] + range[1]) / 2.;
+                t + Scalar::TAU * ((mid - t) / Scalar::TAU).round()
+            }
+        };
+
+        let [a, b] = self.range();
+        let [min, max] = if a < b { [a, b] } else { [b, a] };
+        let tolerance = self.param_tolerance(epsilon);
+
+        if t < min - tolerance || t > max + tolerance {
+            return None;
+        }
+        if self.point_from_param(t).distance_to(&point) > epsilon {
+            return None;
+        }
+
+        Some(t)
+    }
+
+    /// Compute the parameters on this edge, where it intersects `other`
+    ///
+    /// This includes the parameters where the end points of `other` touch this
+    /// edge, which also covers the case of edges that partially overlap.
+    pub fn intersection_params(
+        &self,
+        other: &Self,
+        epsilon: Scalar,
+    ) -> Vec<Scalar> {
+        let mut points = Vec::new();
+
+        match (self, other) {
+            (Self::Segment { points: a }, Self::Segment { points: b }) => {
+                // If the segments are coincident, the end points of the other
+                // edge are the relevant intersection points. Those are covered
+                // below.
+                let line = Line::from_points(*a);
+                if let Some(LineSegmentIntersection::Point { point_on_line }) =
+                    LineSegmentIntersection::compute(
+                        &line,
+                        &Segment::from_points(*b),
+                    )
+                {
+                    points.push(line.point_from_line_coords(point_on_line));
+                }
+            }
+            (Self::Segment { points: segment }, Self::Arc { circle, .. })
+            | (Self::Arc { circle, .. }, Self::Segment { points: segment }) => {
+                let line = Line::from_points(*segment);
+                let points_on_line = match LineEllipseIntersection::compute(
+                    &line,
+                    &Ellipse::from(*circle),
+                ) {
+                    Some(LineEllipseIntersection::Tangent {
+                        point_on_line,
+                    }) => {
+                        vec![point_on_line]
+                    }
+                    Some(LineEllipseIntersection::Crossing {
+                        points_on_line,
+                    }) => points_on_line.to_vec(),
+                    None => Vec::new(),
+                };
+                points.extend(
+                    points_on_line
+                        .into_iter()
+                        .map(|point| line.point_from_line_coords(point)),
+                );
+            }
+            (Self::Arc { circle: a, .. }, Self::Arc { circle: b, .. }) => {
+                // As with segments, coincident circles are covered by the end
+                // points of the other edge.
+                match CircleCircleIntersection::compute(a, b) {
+                    Some(CircleCircleIntersection::Tangent { point }) => {
+                        points.push(point);
+                    }
+                    Some(CircleCircleIntersection::Crossing {
+                        points: crossing,
+                    }) => points.extend(crossing),
+                    Some(CircleCircleIntersection::Coincident) | None => {}
+                }
+            }
+        }
+
+        points
+            .into_iter()
+            .chain(other.points())
+            .filter(|point| other.point_to_param(*point, epsilon).is_some())
+            .filter_map(|point| self.point_to_param(point, epsilon))
+            .collect()
+    }
+
+    /// Compute the angle that the edge sweeps, as seen from `point`
+    ///
+    /// Summing up these angles for all edges of a closed boundary results in
+    /// the winding number of the boundary around the point, times `TAU`.
+    pub fn winding_angle(&self, point: Point<2>) -> Scalar {
+        let [a, b] = self.points();
+
+        // The angle swept by the straight line between the end points.
+        let chord_angle = {
+            let a = a - point;
+            let b = b - point;
+            Scalar::atan2(a.cross2d(&b), a.dot(&b))
+        };
+
+        let (circle, range) = match self {
+            Self::Segment { .. } => return chord_angle,
+            Self::Arc { circle, range } => (circle, range),
+        };
+
+        // The arc, followed by the chord in the reverse direction, forms a
+        // closed boundary. If the point is within that boundary, the arc
+        // sweeps a full turn more (or less) than the chord.
+        let is_inside_circle =
+            (point - circle.center()).magnitude() < circle.radius();
+        let is_on_arc_side_of_chord = if self.is_full_circle() {
+            // There is no chord, so there's only one side.
+            true
+        } else {
+            let chord = b - a;
+            let side_of_point = chord.cross2d(&(point - a));
+            let side_of_arc = chord.cross2d(&(self.midpoint() - a));
+            side_of_point.sign() == side_of_arc.sign()
+        };
+
+        if is_inside_circle && is_on_arc_side_of_chord {
+            let orientation =
+                circle.a().cross2d(&circle.b()) * (range[1] - range[0]);
+            if orientation > Scalar::ZERO {
+                return chord_angle + Scalar::TAU;
+            } else {
+                return chord_angle - Scalar::TAU;
+            }
+        }
+
+        chord_angle
+    }
+
+    /// Compute the edge's contribution to twice the signed area of a boundary
+    pub fn double_area(&self) -> Scalar {
+        let [a, b] = self.points();
+
+        match self {
+            Self::Segment { .. } => a.coords.cross2d(&b.coords),
+            Self::Arc { circle, range } => {
+                circle.center().coords.cross2d(&(b - a))
+                    + circle.a().cross2d(&circle.b()) * (range[1] - range[0])
+            }
+        }
+    }
+
+    /// Merge the edge with the edge that follows it, if they are on the same
+    /// line or circle
+    ///
+    /// Returns `None`, if the edges can't be merged.
+    pub fn merge(&self, next: &Self, epsilon: Scalar) -> Option<Self> {
+        match (self, next) {
+            (
+                Self::Segment { points: [a, b] },
+                Self::Segment { points: [_, c] },
+            ) => {
+                let ab = *b - *a;
+                let bc = *c - *b;
+
+                let is_collinear =
+                    ab.cross2d(&bc).abs() < epsilon * (*c - *a).magnitude();
+                let is_same_direction = ab.dot(&bc) > Scalar::ZERO;
+
+                (is_collinear && is_same_direction)
+                    .then_some(Self::Segment { points: [*a, *c] })
+            }
+            (
+                Self::Arc {
+                    circle: a,
+                    range: [start, end],
+                },
+                Self::Arc {
+                    circle: b,
+                    range: [next_start, next_end],
+                },
+            ) => {
+                let is_same_circle = a.center().distance_to(&b.center())
+                    < epsilon
+                    && (a.a() - b.a()).magnitude() < epsilon
+                    && (a.b() - b.b()).magnitude() < epsilon;
+                let range = [*start, *end + (*next_end - *next_start)];
+                let is_same_direction =
+                    (*end - *start).sign() == (*next_end - *next_start).sign();
+                let span = (range[1] - range[0]).abs();
+
+                // Merging parts of a circle into a full circle is fine, but
+                // going any further would make no sense.
+                (is_same_circle
+                    && is_same_direction
+                    && span <= Scalar::TAU + self.param_tolerance(epsilon))
+                .then_some(Self::Arc { circle: *a, range })
+            }
+            _ => None,
+        }
+    }
+
+    /// Determine whether the edge is geometrically identical to another
+    pub fn is_identical_to(&self, other: &Self, epsilon: Scalar) -> bool {
+        // Comparing the end points and the midpoint is not enough. A full
+        // circle has the same end points and midpoint as its reverse.
+        [0., 0.25, 0.5, 0.75, 1.].into_iter().all(|fraction| {
+            let [a, b] = self.range();
+            let [c, d] = other.range();
+
+            let p = self.point_from_param(a + (b - a) * fraction);
+            let q = other.point_from_param(c + (d - c) * fraction);
+
+            p.distance_to(&q) < epsilon
+        })
+    }
+
+    fn param_tolerance(&self, epsilon: Scalar) -> Scalar {
+        match self {
+            Self::Segment { points: [a, b] } => epsilon / a.distance_to(b),
+            Self::Arc { circle, .. } => epsilon / circle.radius(),
+        }
+    }
+}
+
+/// Compute the circle coordinate of a point, in the range `[-PI, PI]`
+///
+/// Unlike [`Circle::point_to_circle_coords`], this takes the orientation of the
+/// circle into account.
+pub fn circle_coord(circle: &Circle<2>, point: Point<2>) -> Scalar {
+    let v = point - circle.center();
+    Scalar::atan2(v.dot(&circle.b()), v.dot(&circle.a()))
+}
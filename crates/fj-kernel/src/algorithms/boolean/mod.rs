@@ -0,0 +1,113 @@
+//! Boolean operations on 2-dimensional shapes
+//!
+//! See [`Boolean2d`].
+
+pub(super) mod edge;
+pub(super) mod region;
+
+mod sketch;
+
+use fj_interop::mesh::Color;
+
+use crate::{objects::Objects, services::Service};
+
+/// A boolean operation between two 2-dimensional shapes
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum BooleanOp {
+    /// The union of both shapes
+    Union,
+
+    /// The intersection of both shapes
+    Intersection,
+
+    /// The difference between both shapes
+    ///
+    /// The second shape is subtracted from the first one.
+    Difference,
+}
+
+/// Compute boolean operations between 2-dimensional shapes
+///
+/// The boundaries of both shapes are split wherever they intersect, the
+/// resulting pieces are classified as being inside or outside of the other
+/// shape, and the pieces that bound the result are joined into new cycles.
+///
+/// # Implementation Note
+///
+/// Both shapes must be defined in the same surface, and their edges must be
+/// lines or circles. Otherwise, a [`Boolean2dError`] is returned.
+///
+/// Since the boundaries of the result are rebuilt from scratch, the orientation
+/// and color of the input faces are not preserved: exterior cycles of the
+/// result always have counter-clockwise winding, interior cycles clockwise
+/// winding, and all faces of the result have the color that is passed to the
+/// operation.
+pub trait Boolean2d: Sized {
+    /// The shape that results from a boolean operation
+    type Output;
+
+    /// Compute the boolean operation between `self` and `other`
+    fn boolean(
+        self,
+        other: Self,
+        op: BooleanOp,
+        color: Color,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self::Output, Boolean2dError>;
+
+    /// Compute the union of `self` and `other`
+    fn union(
+        self,
+        other: Self,
+        color: Color,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self::Output, Boolean2dError> {
+        self.boolean(other, BooleanOp::Union, color, objects)
+    }
+
+    /// Compute the intersection of `self` and `other`
+    fn intersection(
+        self,
+        other: Self,
+        color: Color,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self::Output, Boolean2dError> {
+        self.boolean(other, BooleanOp::Intersection, color, objects)
+    }
+
+    /// Compute the difference between `self` and `other`
+    fn difference(
+        self,
+        other: Self,
+        color: Color,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self::Output, Boolean2dError> {
+        self.boolean(other, BooleanOp::Difference, color, objects)
+    }
+}
+
+/// Error computing a boolean operation between 2-dimensional shapes
+///
+/// See [`Boolean2d`].
+#[derive(Debug, thiserror::Error)]
+pub enum Boolean2dError {
+    /// The faces of the shapes are defined in different surfaces
+    #[error("Faces in different surfaces are not supported")]
+    DifferentSurfaces,
+
+    /// An edge of the shapes is defined by an ellipse
+    #[error("Sketches with ellipses are not supported yet")]
+    Ellipse,
+
+    /// The edges of the result don't form closed cycles
+    ///
+    /// This can only happen due to numerical problems.
+    #[error("Boundary of the result is not closed")]
+    OpenCycle,
+
+    /// An interior cycle of the result is not contained in any exterior cycle
+    ///
+    /// This can only happen due to numerical problems.
+    #[error("Hole in the result is not within any face")]
+    OrphanInterior,
+}
@@ -0,0 +1,488 @@
+use std::collections::BTreeMap;
+
+use fj_interop::{ext::ArrayExt, mesh::Color};
+use fj_math::{Point, Scalar, Winding};
+
+use crate::{
+    builder::HalfEdgeBuilder,
+    geometry::path::SurfacePath,
+    insert::Insert,
+    objects::{Cycle, Face, Objects, Sketch, Surface, SurfaceVertex},
+    partial::{
+        Partial, PartialCycle, PartialFace, PartialHalfEdge, PartialObject,
+        PartialSurfaceVertex,
+    },
+    services::Service,
+    storage::Handle,
+};
+
+use super::{edge::BoundaryEdge, Boolean2dError, BooleanOp};
+
+/// An area in a surface, represented by its boundary
+///
+/// The edges are not in any particular order, but they are oriented such that
+/// the area is always to their left.
+pub struct Region {
+    edges: Vec<BoundaryEdge>,
+}
+
+impl Region {
+    /// Create a region from the edges that bound it
+    pub fn new(edges: Vec<BoundaryEdge>) -> Self {
+        Self { edges }
+    }
+
+    /// Create a region from the faces of a sketch
+    pub fn from_sketch(sketch: &Sketch) -> Result<Self, Boolean2dError> {
+        let cycles = boundary_cycles(sketch)?;
+        Ok(Self::new(cycles.into_iter().flatten().collect()))
+    }
+
+    /// Compute the boolean operation between `self` and `other`
+    pub fn boolean(
+        &self,
+        other: &Self,
+        op: BooleanOp,
+        epsilon: Scalar,
+    ) -> Self {
+        let a_pieces = self.split_at(other, epsilon);
+        let b_pieces = other.split_at(self, epsilon);
+
+        let mut selected = Vec::new();
+
+        for piece in &a_pieces {
+            let keep = matches!(
+                (op, classify(piece, other, &b_pieces, epsilon)),
+                (BooleanOp::Union, Location::Outside)
+                    | (BooleanOp::Union, Location::SameBoundary)
+                    | (BooleanOp::Intersection, Location::Inside)
+                    | (BooleanOp::Intersection, Location::SameBoundary)
+                    | (BooleanOp::Difference, Location::Outside)
+                    | (BooleanOp::Difference, Location::OppositeBoundary)
+            );
+
+            if keep {
+                selected.push(*piece);
+            }
+        }
+
+        for piece in &b_pieces {
+            // Pieces of the boundary that both shapes share, have already
+            // been taken care of above.
+            let piece = match (op, classify(piece, self, &a_pieces, epsilon)) {
+                (BooleanOp::Union, Location::Outside)
+                | (BooleanOp::Intersection, Location::Inside) => *piece,
+                (BooleanOp::Difference, Location::Inside) => piece.reverse(),
+                _ => continue,
+            };
+
+            selected.push(piece);
+        }
+
+        Self::new(selected)
+    }
+
+    /// Merge adjacent edges that are on the same line or circle
+    ///
+    /// Boolean operations split edges wherever they meet other edges. This can
+    /// result in more edges than necessary, which this method cleans up.
+    ///
+    /// Returns an error, if the edges don't form closed cycles.
+    pub fn simplify(self, epsilon: Scalar) -> Result<Self, Boolean2dError> {
+        let mut edges = Vec::new();
+
+        for cycle in join_into_cycles(self.edges, epsilon)? {
+            let mut merged: Vec<BoundaryEdge> = Vec::new();
+
+            for edge in cycle {
+                match merged.last().and_then(|last| last.merge(&edge, epsilon))
+                {
+                    Some(edge) => {
+                        *merged.last_mut().expect("Checked above") = edge
+                    }
+                    None => merged.push(edge),
+                }
+            }
+
+            // The cycle is closed, so the last edge might be mergeable with
+            // the first one too.
+            if merged.len() > 1 {
+                let last = merged[merged.len() - 1];
+                if let Some(edge) = last.merge(&merged[0], epsilon) {
+                    merged[0] = edge;
+                    merged.pop();
+                }
+            }
+
+            edges.extend(merged);
+        }
+
+        Ok(Self::new(edges))
+    }
+
+    /// Build a sketch from the region
+    ///
+    /// All faces of the sketch are defined in `surface`, and have the provided
+    /// color.
+    ///
+    /// Returns an error, if the edges don't form closed cycles, or if an
+    /// interior cycle is not contained in any exterior cycle.
+    pub fn build(
+        &self,
+        surface: &Handle<Surface>,
+        color: Color,
+        epsilon: Scalar,
+        objects: &mut Service<Objects>,
+    ) -> Result<Handle<Sketch>, Boolean2dError> {
+        let cycles = join_into_cycles(self.edges.clone(), epsilon)?;
+
+        let faces = group_into_faces(cycles)?
+            .into_iter()
+            .map(|face| build_face(surface, &face, color, objects))
+            .collect::<Vec<_>>();
+
+        Ok(Sketch::builder().with_faces(faces).build(objects))
+    }
+
+    /// Split the boundary wherever it intersects the boundary of `other`
+    fn split_at(&self, other: &Self, epsilon: Scalar) -> Vec<BoundaryEdge> {
+        self.edges
+            .iter()
+            .flat_map(|edge| {
+                let params = other
+                    .edges
+                    .iter()
+                    .flat_map(|other| edge.intersection_params(other, epsilon))
+                    .collect::<Vec<_>>();
+
+                edge.split(params, epsilon)
+            })
+            .collect()
+    }
+
+    /// Determine whether the point is within the region
+    ///
+    /// Must not be called with points that are on the boundary.
+    fn contains(&self, point: Point<2>) -> bool {
+        winding_number(&self.edges, point) != Scalar::ZERO
+    }
+}
+
+/// Access the boundaries of all faces of a sketch, as cycles of edges
+///
+/// The cycles are oriented consistently, such that the area bounded by them is
+/// always to their left: exteriors are counter-clockwise, interiors clockwise.
+pub fn boundary_cycles(
+    sketch: &Sketch,
+) -> Result<Vec<Vec<BoundaryEdge>>, Boolean2dError> {
+    let mut cycles = Vec::new();
+
+    for face in sketch.faces() {
+        // Make sure that the exterior of all faces is counter-clockwise, so
+        // the boundaries of different shapes can be combined consistently.
+        let reverse = face.exterior().winding() == Winding::Cw;
+
+        for cycle in face.all_cycles() {
+            let mut edges = Vec::new();
+
+            for half_edge in cycle.half_edges() {
+                let [a, b] = half_edge.vertices();
+
+                let edge = match half_edge.curve().path() {
+                    SurfacePath::Circle(circle) => BoundaryEdge::Arc {
+                        circle,
+                        range: [a, b].map(|vertex| vertex.position().t),
+                    },
+                    SurfacePath::Ellipse(_) => {
+                        return Err(Boolean2dError::Ellipse)
+                    }
+                    SurfacePath::Line(_) => BoundaryEdge::Segment {
+                        points: [a, b]
+                            .map(|vertex| vertex.surface_form().position()),
+                    },
+                };
+
+                edges.push(edge);
+            }
+
+            if reverse {
+                edges = edges
+                    .into_iter()
+                    .rev()
+                    .map(BoundaryEdge::reverse)
+                    .collect();
+            }
+
+            cycles.push(edges);
+        }
+    }
+
+    Ok(cycles)
+}
+
+/// The location of a piece of boundary, relative to another shape
+enum Location {
+    Inside,
+    Outside,
+
+    /// The piece is shared with the other shape's boundary
+    SameBoundary,
+
+    /// The piece is shared with the other shape's boundary, reversed
+    OppositeBoundary,
+}
+
+fn classify(
+    piece: &BoundaryEdge,
+    other: &Region,
+    other_pieces: &[BoundaryEdge],
+    epsilon: Scalar,
+) -> Location {
+    for other_piece in other_pieces {
+        if piece.is_identical_to(other_piece, epsilon) {
+            return Location::SameBoundary;
+        }
+        if piece.is_identical_to(&other_piece.reverse(), epsilon) {
+            return Location::OppositeBoundary;
+        }
+    }
+
+    if other.contains(piece.midpoint()) {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+fn winding_number(edges: &[BoundaryEdge], point: Point<2>) -> Scalar {
+    let angle = edges
+        .iter()
+        .map(|edge| edge.winding_angle(point))
+        .fold(Scalar::ZERO, |a, b| a + b);
+
+    (angle / Scalar::TAU).round()
+}
+
+fn join_into_cycles(
+    pieces: Vec<BoundaryEdge>,
+    epsilon: Scalar,
+) -> Result<Vec<Vec<BoundaryEdge>>, Boolean2dError> {
+    // Identify the end points of all pieces, so we can figure out which pieces
+    // connect to each other.
+    let mut vertices: Vec<Point<2>> = Vec::new();
+    let mut vertex_id = |point: Point<2>| {
+        if let Some(id) = vertices
+            .iter()
+            .position(|vertex| vertex.distance_to(&point) < epsilon)
+        {
+            return id;
+        }
+
+        vertices.push(point);
+        vertices.len() - 1
+    };
+
+    let mut outgoing: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    let mut ends = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        let [start, end] = piece.points().map(&mut vertex_id);
+        outgoing.entry(start).or_default().push(i);
+        ends.push((start, end));
+    }
+
+    let mut used = vec![false; pieces.len()];
+    let mut cycles = Vec::new();
+
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+
+        let (start, mut end) = ends[first];
+        let mut cycle = vec![pieces[first]];
+
+        while end != start {
+            let next = outgoing.get(&end).and_then(|candidates| {
+                candidates.iter().copied().find(|&i| !used[i])
+            });
+
+            let Some(next) = next else {
+                // The pieces don't form a closed cycle. This can only happen
+                // due to numerical problems. Leaving out the incomplete cycle
+                // would silently change the shape.
+                return Err(Boolean2dError::OpenCycle);
+            };
+
+            used[next] = true;
+            cycle.push(pieces[next]);
+            end = ends[next].1;
+        }
+
+        cycles.push(cycle);
+    }
+
+    Ok(cycles)
+}
+
+/// The cycles that bound a face
+struct FaceCycles {
+    exterior: Vec<BoundaryEdge>,
+    interiors: Vec<Vec<BoundaryEdge>>,
+}
+
+/// Group the cycles into faces, each with an exterior and any interiors
+fn group_into_faces(
+    cycles: Vec<Vec<BoundaryEdge>>,
+) -> Result<Vec<FaceCycles>, Boolean2dError> {
+    let double_area = |cycle: &[BoundaryEdge]| {
+        cycle
+            .iter()
+            .map(BoundaryEdge::double_area)
+            .fold(Scalar::ZERO, |a, b| a + b)
+    };
+
+    let (mut exteriors, interiors): (Vec<_>, Vec<_>) = cycles
+        .into_iter()
+        .partition(|cycle| double_area(cycle) > Scalar::ZERO);
+
+    // If an interior is contained in multiple exteriors, it belongs to the
+    // smallest one.
+    exteriors.sort_by_key(|cycle| double_area(cycle));
+
+    let mut faces = exteriors
+        .into_iter()
+        .map(|exterior| FaceCycles {
+            exterior,
+            interiors: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+
+    for interior in interiors {
+        let point = interior[0].midpoint();
+
+        let face = faces
+            .iter_mut()
+            .find(|face| winding_number(&face.exterior, point) != Scalar::ZERO);
+
+        // An interior that isn't contained in any exterior can only be the
+        // result of numerical problems. There's no face it could be a hole in.
+        let Some(face) = face else {
+            return Err(Boolean2dError::OrphanInterior);
+        };
+        face.interiors.push(interior);
+    }
+
+    Ok(faces)
+}
+
+fn build_face(
+    surface: &Handle<Surface>,
+    cycles: &FaceCycles,
+    color: Color,
+    objects: &mut Service<Objects>,
+) -> Handle<Face> {
+    let face = PartialFace {
+        exterior: build_cycle(surface, &cycles.exterior),
+        interiors: cycles
+            .interiors
+            .iter()
+            .map(|interior| build_cycle(surface, interior))
+            .collect(),
+        color: Some(color),
+    };
+
+    face.build(objects).insert(objects)
+}
+
+fn build_cycle(
+    surface: &Handle<Surface>,
+    pieces: &[BoundaryEdge],
+) -> Partial<Cycle> {
+    let surface = Partial::from_full_entry_point(surface.clone());
+    let num_pieces = pieces.len();
+
+    // Each vertex is the start of one piece, and the end of the previous one.
+    // If either of those is an arc, the position of the vertex is taken from
+    // the arc, to make sure it is exactly on the circle.
+    let surface_vertices = (0..num_pieces)
+        .map(|i| {
+            let previous = &pieces[(i + num_pieces - 1) % num_pieces];
+            let next = &pieces[i];
+
+            let position = match (previous, next) {
+                (_, BoundaryEdge::Arc { .. })
+                | (BoundaryEdge::Segment { .. }, _) => {
+                    let [start, _] = next.points();
+                    start
+                }
+                (BoundaryEdge::Arc { .. }, BoundaryEdge::Segment { .. }) => {
+                    let [_, end] = previous.points();
+                    end
+                }
+            };
+
+            Partial::from_partial(PartialSurfaceVertex {
+                position: Some(position),
+                surface: surface.clone(),
+                ..Default::default()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let half_edges = pieces
+        .iter()
+        .enumerate()
+        .map(|(i, piece)| {
+            let vertices: [Partial<SurfaceVertex>; 2] =
+                [i, (i + 1) % num_pieces].map(|i| surface_vertices[i].clone());
+
+            let mut half_edge = PartialHalfEdge::default();
+            half_edge.curve().write().surface = surface.clone();
+
+            {
+                let global_vertices =
+                    &mut half_edge.global_form.write().vertices;
+
+                for ((vertex, surface_form), global_form) in half_edge
+                    .vertices
+                    .each_mut_ext()
+                    .zip_ext(vertices.clone())
+                    .zip_ext(global_vertices.each_mut_ext())
+                {
+                    *global_form = surface_form.read().global_form.clone();
+                    vertex.write().surface_form = surface_form;
+                }
+            }
+
+            match piece {
+                BoundaryEdge::Segment { .. } => {
+                    half_edge.update_as_line_segment();
+                }
+                BoundaryEdge::Arc { .. } => {
+                    // The end of the arc might be positioned at the start of
+                    // another arc. Adjust the arc, to account for the
+                    // numerical differences that might cause.
+                    let piece = if num_pieces > 1 {
+                        let [_, end] = &vertices;
+                        let end = end
+                            .read()
+                            .position
+                            .expect("Position of vertex was set above");
+                        piece.with_end_at(end)
+                    } else {
+                        *piece
+                    };
+
+                    if let BoundaryEdge::Arc { circle, range } = piece {
+                        half_edge.update_as_arc(circle, range);
+                    }
+                }
+            }
+
+            Partial::from_partial(half_edge)
+        })
+        .collect();
+
+    Partial::from_partial(PartialCycle::new(half_edges))
+}
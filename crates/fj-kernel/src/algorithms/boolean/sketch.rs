@@ -0,0 +1,355 @@
+use fj_interop::mesh::Color;
+
+use crate::{
+    objects::{Objects, Sketch},
+    services::Service,
+    storage::Handle,
+    validate::ValidationConfig,
+};
+
+use super::{region::Region, Boolean2d, Boolean2dError, BooleanOp};
+
+impl Boolean2d for &Sketch {
+    type Output = Handle<Sketch>;
+
+    fn boolean(
+        self,
+        other: Self,
+        op: BooleanOp,
+        color: Color,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self::Output, Boolean2dError> {
+        let mut faces = self.faces().into_iter().chain(other.faces());
+
+        let Some(first) = faces.next() else {
+            // Both sketches are empty, and so is the result.
+            return Ok(Sketch::builder().build(objects));
+        };
+        let surface = first.surface().clone();
+
+        if faces.any(|face| face.surface().id() != surface.id()) {
+            return Err(Boolean2dError::DifferentSurfaces);
+        }
+
+        // Points that are closer than this are considered to be identical.
+        let epsilon = ValidationConfig::default().distinct_min_distance;
+
+        let a = Region::from_sketch(self)?;
+        let b = Region::from_sketch(other)?;
+
+        a.boolean(&b, op, epsilon)
+            .build(&surface, color, epsilon, objects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Circle, Point, Scalar};
+
+    use crate::{
+        algorithms::boolean::{
+            edge::BoundaryEdge, region::Region, Boolean2d, Boolean2dError,
+        },
+        objects::{Sketch, Surface},
+        services::Services,
+        storage::Handle,
+        validate::ValidationConfig,
+    };
+
+    #[test]
+    fn difference_of_contained_polygon() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let a = Sketch::builder()
+            .with_polygon_from_points(
+                surface.clone(),
+                [[0., 0.], [3., 0.], [3., 3.], [0., 3.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+        let b = Sketch::builder()
+            .with_polygon_from_points(
+                surface,
+                [[1., 1.], [2., 1.], [2., 2.], [1., 2.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+
+        let color = Color([0, 255, 0, 255]);
+        let difference =
+            a.difference(&b, color, &mut services.objects).unwrap();
+
+        let faces = difference.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 4);
+        assert_eq!(faces[0].interiors().count(), 1);
+        assert_eq!(faces[0].color(), color);
+    }
+
+    #[test]
+    fn union_of_overlapping_polygons() {
+        let mut services = Services::new();
+
+        let [a, b] = overlapping_squares(&mut services);
+        let union = a
+            .union(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        let faces = union.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 8);
+        assert_eq!(faces[0].interiors().count(), 0);
+    }
+
+    #[test]
+    fn intersection_of_overlapping_polygons() {
+        let mut services = Services::new();
+
+        let [a, b] = overlapping_squares(&mut services);
+        let intersection = a
+            .intersection(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        let faces = intersection.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 4);
+        assert_eq!(faces[0].interiors().count(), 0);
+    }
+
+    #[test]
+    fn difference_of_overlapping_polygons() {
+        let mut services = Services::new();
+
+        let [a, b] = overlapping_squares(&mut services);
+        let difference = a
+            .difference(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        let faces = difference.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 6);
+        assert_eq!(faces[0].interiors().count(), 0);
+    }
+
+    #[test]
+    fn intersection_of_overlapping_triangles() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let [a, b] = [0., 1.].map(|v| {
+            Sketch::builder()
+                .with_polygon_from_points(
+                    surface.clone(),
+                    [[0., v], [2., v], [1., v + 2.]],
+                    &mut services.objects,
+                )
+                .build(&mut services.objects)
+        });
+
+        let intersection = a
+            .intersection(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        let faces = intersection.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 3);
+    }
+
+    #[test]
+    fn union_of_adjacent_polygons() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let a = Sketch::builder()
+            .with_polygon_from_points(
+                surface.clone(),
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+        let b = Sketch::builder()
+            .with_polygon_from_points(
+                surface,
+                [[1., 0.], [2., 0.], [2., 1.], [1., 1.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+
+        let union = a
+            .union(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        let faces = union.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 6);
+    }
+
+    #[test]
+    fn difference_of_concentric_circles() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let a = circle(&surface, [0., 0.], 2., &mut services);
+        let b = circle(&surface, [0., 0.], 1., &mut services);
+
+        let difference = a
+            .difference(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        let faces = difference.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 1);
+        assert_eq!(faces[0].interiors().count(), 1);
+    }
+
+    #[test]
+    fn union_of_overlapping_circles() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let a = circle(&surface, [0., 0.], 1., &mut services);
+        let b = circle(&surface, [1., 0.], 1., &mut services);
+
+        let union = a
+            .union(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        // One arc from `a`, two arcs from `b`. The circle of `b` starts at a
+        // point that is part of the union's boundary, so it is split there.
+        let faces = union.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 3);
+    }
+
+    #[test]
+    fn difference_of_circle_from_polygon() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let a = Sketch::builder()
+            .with_polygon_from_points(
+                surface.clone(),
+                [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+        let b = circle(&surface, [2., 1.], 0.5, &mut services);
+
+        let difference = a
+            .difference(&b, Color::default(), &mut services.objects)
+            .unwrap();
+
+        let faces = difference.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 6);
+        assert_eq!(faces[0].interiors().count(), 0);
+    }
+
+    #[test]
+    fn difference_of_sketches_in_different_surfaces() {
+        let mut services = Services::new();
+
+        let [a, b] = [
+            services.objects.surfaces.xy_plane(),
+            services.objects.surfaces.xz_plane(),
+        ]
+        .map(|surface| {
+            Sketch::builder()
+                .with_polygon_from_points(
+                    surface,
+                    [[0., 0.], [1., 0.], [0., 1.]],
+                    &mut services.objects,
+                )
+                .build(&mut services.objects)
+        });
+
+        let difference =
+            a.difference(&b, Color::default(), &mut services.objects);
+        assert!(matches!(difference, Err(Boolean2dError::DifferentSurfaces)));
+    }
+
+    #[test]
+    fn build_open_cycle() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let sketch = polygon(&[[0., 0.], [1., 0.], [0., 1.]], false).build(
+            &surface,
+            Color::default(),
+            ValidationConfig::default().distinct_min_distance,
+            &mut services.objects,
+        );
+
+        assert!(matches!(sketch, Err(Boolean2dError::OpenCycle)));
+    }
+
+    #[test]
+    fn build_interior_without_exterior() {
+        let mut services = Services::new();
+
+        // The triangle is clockwise, which makes it an interior cycle.
+        let surface = services.objects.surfaces.xy_plane();
+        let sketch = polygon(&[[0., 0.], [0., 1.], [1., 0.]], true).build(
+            &surface,
+            Color::default(),
+            ValidationConfig::default().distinct_min_distance,
+            &mut services.objects,
+        );
+
+        assert!(matches!(sketch, Err(Boolean2dError::OrphanInterior)));
+    }
+
+    fn overlapping_squares(services: &mut Services) -> [Handle<Sketch>; 2] {
+        let surface = services.objects.surfaces.xy_plane();
+
+        [[0., 0.], [1., 1.]].map(|[u, v]| {
+            Sketch::builder()
+                .with_polygon_from_points(
+                    surface.clone(),
+                    [[u, v], [u + 2., v], [u + 2., v + 2.], [u, v + 2.]],
+                    &mut services.objects,
+                )
+                .build(&mut services.objects)
+        })
+    }
+
+    fn polygon(points: &[[f64; 2]], closed: bool) -> Region {
+        let mut edges = points
+            .windows(2)
+            .map(|points| BoundaryEdge::Segment {
+                points: [points[0], points[1]].map(Point::from),
+            })
+            .collect::<Vec<_>>();
+
+        if closed {
+            let [first, last] =
+                [points[0], points[points.len() - 1]].map(Point::from);
+            edges.push(BoundaryEdge::Segment {
+                points: [last, first],
+            });
+        }
+
+        Region::new(edges)
+    }
+
+    fn circle(
+        surface: &Handle<Surface>,
+        center: [f64; 2],
+        radius: f64,
+        services: &mut Services,
+    ) -> Handle<Sketch> {
+        Region::new(vec![BoundaryEdge::Arc {
+            circle: Circle::from_center_and_radius(center, radius),
+            range: [Scalar::ZERO, Scalar::TAU],
+        }])
+        .build(
+            surface,
+            Color::default(),
+            ValidationConfig::default().distinct_min_distance,
+            &mut services.objects,
+        )
+        .unwrap()
+    }
+}
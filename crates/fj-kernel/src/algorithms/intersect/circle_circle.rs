@@ -0,0 +1,140 @@
+use fj_math::{Circle, Point, Scalar, Vector};
+
+/// An intersection between two [`Circle`]s
+#[derive(Debug, Eq, PartialEq)]
+pub enum CircleCircleIntersection {
+    /// The circles touch at a single point
+    Tangent {
+        /// The point where the circles touch
+        point: Point<2>,
+    },
+
+    /// The circles cross each other at two points
+    Crossing {
+        /// The intersection points
+        ///
+        /// Seen from the center of the first circle, the first point is to the
+        /// left of the line that connects the centers, the second to the right.
+        points: [Point<2>; 2],
+    },
+
+    /// The circles are coincident
+    Coincident,
+}
+
+impl CircleCircleIntersection {
+    /// The tolerance for detecting a tangent, relative to the circles' size
+    const TANGENT_TOLERANCE: f64 = 1e-9;
+
+    /// Determine the intersection between two [`Circle`]s
+    pub fn compute(a: &Circle<2>, b: &Circle<2>) -> Option<Self> {
+        let center_to_center = b.center() - a.center();
+        let distance = center_to_center.magnitude();
+
+        let [ra, rb] = [a.radius(), b.radius()];
+        let tolerance = (ra + rb) * Self::TANGENT_TOLERANCE;
+
+        if distance <= tolerance {
+            // The circles are concentric, which means they either don't
+            // intersect at all, or are the same circle.
+            if (ra - rb).abs() <= tolerance {
+                return Some(Self::Coincident);
+            }
+            return None;
+        }
+
+        let is_outside = distance > ra + rb + tolerance;
+        let is_inside = distance < (ra - rb).abs() - tolerance;
+        if is_outside || is_inside {
+            return None;
+        }
+
+        // Distance from the center of `a` to the line through the intersection
+        // points, and from that line to each of the points.
+        let along = (ra * ra - rb * rb + distance * distance) / (distance * 2.);
+        let across = (ra * ra - along * along).max(Scalar::ZERO).sqrt();
+
+        let direction = center_to_center / distance;
+        let base = a.center() + direction * along;
+
+        let is_tangent = (distance - (ra + rb)).abs() <= tolerance
+            || (distance - (ra - rb).abs()).abs() <= tolerance;
+        if is_tangent {
+            return Some(Self::Tangent { point: base });
+        }
+
+        let normal = Vector::from([-direction.v, direction.u]);
+
+        Some(Self::Crossing {
+            points: [base + normal * across, base - normal * across],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Point};
+
+    use super::CircleCircleIntersection;
+
+    #[test]
+    fn compute_crossing() {
+        let a = Circle::from_center_and_radius([0., 0.], 1.);
+        let b = Circle::from_center_and_radius([1., 0.], 1.);
+
+        let Some(CircleCircleIntersection::Crossing { points: [p, q] }) =
+            CircleCircleIntersection::compute(&a, &b)
+        else {
+            panic!("Expected circles to cross");
+        };
+
+        let y = f64::sqrt(3.) / 2.;
+        assert!(p.distance_to(&Point::from([0.5, y])) < 1e-12.into());
+        assert!(q.distance_to(&Point::from([0.5, -y])) < 1e-12.into());
+    }
+
+    #[test]
+    fn compute_tangent() {
+        let a = Circle::from_center_and_radius([0., 0.], 1.);
+
+        let outside = Circle::from_center_and_radius([3., 0.], 2.);
+        assert_eq!(
+            CircleCircleIntersection::compute(&a, &outside),
+            Some(CircleCircleIntersection::Tangent {
+                point: Point::from([1., 0.]),
+            }),
+        );
+
+        let inside = Circle::from_center_and_radius([-1., 0.], 2.);
+        assert_eq!(
+            CircleCircleIntersection::compute(&a, &inside),
+            Some(CircleCircleIntersection::Tangent {
+                point: Point::from([1., 0.]),
+            }),
+        );
+    }
+
+    #[test]
+    fn compute_coincident() {
+        let a = Circle::from_center_and_radius([1., 1.], 1.);
+
+        assert_eq!(
+            CircleCircleIntersection::compute(&a, &a.reverse()),
+            Some(CircleCircleIntersection::Coincident),
+        );
+    }
+
+    #[test]
+    fn compute_no_hit() {
+        let a = Circle::from_center_and_radius([0., 0.], 1.);
+
+        let apart = Circle::from_center_and_radius([3., 0.], 1.);
+        assert_eq!(CircleCircleIntersection::compute(&a, &apart), None);
+
+        let within = Circle::from_center_and_radius([0.5, 0.], 0.25);
+        assert_eq!(CircleCircleIntersection::compute(&a, &within), None);
+
+        let concentric = Circle::from_center_and_radius([0., 0.], 2.);
+        assert_eq!(CircleCircleIntersection::compute(&a, &concentric), None);
+    }
+}
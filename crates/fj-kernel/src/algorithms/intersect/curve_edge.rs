@@ -1,11 +1,11 @@
-use fj_math::{Point, Segment};
+use fj_math::{Ellipse, Line, Point, Scalar, Segment};
 
 use crate::{
     geometry::path::SurfacePath,
     objects::{Curve, HalfEdge},
 };
 
-use super::LineSegmentIntersection;
+use super::{LineEllipseIntersection, LineSegmentIntersection};
 
 /// The intersection between a [`Curve`] and a [`HalfEdge`]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -16,6 +16,15 @@ pub enum CurveEdgeIntersection {
         point_on_curve: Point<1>,
     },
 
+    /// The curve and a curved edge intersect at two points
+    ///
+    /// If the curve touches the edge without crossing it, both points are the
+    /// same.
+    Points {
+        /// The intersection points, in curve coordinates on the curve
+        points_on_curve: [Point<1>; 2],
+    },
+
     /// The edge lies on the curve
     Coincident {
         /// The end points of the edge, in curve coordinates on the curve
@@ -26,34 +35,43 @@ pub enum CurveEdgeIntersection {
 impl CurveEdgeIntersection {
     /// Compute the intersection
     ///
-    /// # Panics
-    ///
-    /// Currently, only intersections between lines and line segments can be
-    /// computed. Panics, if a different type of [`Curve`] or [`HalfEdge`] is
-    /// passed.
-    pub fn compute(curve: &Curve, half_edge: &HalfEdge) -> Option<Self> {
+    /// Currently, only intersections between lines and edges can be computed.
+    /// Returns an error, if a different type of [`Curve`] is passed.
+    pub fn compute(
+        curve: &Curve,
+        half_edge: &HalfEdge,
+    ) -> Result<Option<Self>, CurveEdgeIntersectionError> {
         let curve_as_line = match curve.path() {
             SurfacePath::Line(line) => line,
-            _ => todo!("Curve-edge intersection only supports lines"),
+            _ => return Err(CurveEdgeIntersectionError::CurveNotLine),
         };
 
-        let edge_as_segment = {
-            let edge_curve_as_line = match half_edge.curve().path() {
-                SurfacePath::Line(line) => line,
-                _ => {
-                    todo!("Curve-edge intersection only supports line segments")
-                }
-            };
-
-            let edge_vertices = half_edge.vertices().clone().map(|vertex| {
-                edge_curve_as_line.point_from_line_coords(vertex.position())
-            });
+        let range =
+            half_edge.vertices().clone().map(|vertex| vertex.position());
 
-            Segment::from_points(edge_vertices)
+        let intersection = match half_edge.curve().path() {
+            SurfacePath::Line(line) => {
+                let segment = Segment::from_points(
+                    range.map(|point| line.point_from_line_coords(point)),
+                );
+                Self::compute_with_segment(&curve_as_line, &segment)
+            }
+            SurfacePath::Circle(circle) => {
+                Self::compute_with_arc(&curve_as_line, &circle.into(), range)
+            }
+            SurfacePath::Ellipse(ellipse) => {
+                Self::compute_with_arc(&curve_as_line, &ellipse, range)
+            }
         };
 
-        let intersection =
-            LineSegmentIntersection::compute(&curve_as_line, &edge_as_segment)?;
+        Ok(intersection)
+    }
+
+    fn compute_with_segment(
+        line: &Line<2>,
+        segment: &Segment<2>,
+    ) -> Option<Self> {
+        let intersection = LineSegmentIntersection::compute(line, segment)?;
 
         let intersection = match intersection {
             LineSegmentIntersection::Point { point_on_line } => Self::Point {
@@ -68,19 +86,94 @@ impl CurveEdgeIntersection {
 
         Some(intersection)
     }
+
+    /// Compute the intersection of a line and an arc of `ellipse`
+    ///
+    /// The arc spans `range`, in ellipse coordinates.
+    fn compute_with_arc(
+        line: &Line<2>,
+        ellipse: &Ellipse<2>,
+        range: [Point<1>; 2],
+    ) -> Option<Self> {
+        let on_arc = |point_on_line: Point<1>| {
+            let point = line.point_from_line_coords(point_on_line);
+            let coord = ellipse.point_to_ellipse_coords(point);
+            is_on_arc(coord.t, range).then_some(point_on_line)
+        };
+
+        let intersection =
+            match LineEllipseIntersection::compute(line, ellipse)? {
+                LineEllipseIntersection::Tangent { point_on_line } => {
+                    let point = on_arc(point_on_line)?;
+                    Self::Points {
+                        points_on_curve: [point, point],
+                    }
+                }
+                LineEllipseIntersection::Crossing { points_on_line } => {
+                    match points_on_line.map(on_arc) {
+                        [Some(a), Some(b)] => Self::Points {
+                            points_on_curve: [a, b],
+                        },
+                        [Some(point), None] | [None, Some(point)] => {
+                            Self::Point {
+                                point_on_curve: point,
+                            }
+                        }
+                        [None, None] => return None,
+                    }
+                }
+            };
+
+        Some(intersection)
+    }
+}
+
+/// Error computing a [`CurveEdgeIntersection`]
+#[derive(Debug, thiserror::Error)]
+pub enum CurveEdgeIntersectionError {
+    /// The curve is not a line
+    #[error("Curve-edge intersection only supports lines")]
+    CurveNotLine,
+}
+
+/// Whether the ellipse coordinate `t` is within the arc that spans `range`
+///
+/// `t` must be between `0` and `TAU`. The arc can run in either direction, and
+/// might start or end outside of that range.
+fn is_on_arc(t: Scalar, range: [Point<1>; 2]) -> bool {
+    let [start, end] = range.map(|point| point.t);
+
+    let length = (end - start).abs();
+    if length >= Scalar::TAU {
+        return true;
+    }
+
+    // The distance of `t` from the start of the arc, in the direction of the
+    // arc.
+    let distance = if end >= start { t - start } else { start - t };
+    let distance = distance % Scalar::TAU;
+    let distance = if distance < Scalar::ZERO {
+        distance + Scalar::TAU
+    } else {
+        distance
+    };
+
+    distance <= length
 }
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Point;
+    use fj_math::{Circle, Point, Scalar};
 
     use crate::{
-        builder::{CurveBuilder, HalfEdgeBuilder},
+        builder::{CurveBuilder, HalfEdgeBuilder, SurfaceVertexBuilder},
+        objects::{HalfEdge, Surface},
         partial::{Partial, PartialCurve, PartialHalfEdge, PartialObject},
         services::Services,
+        storage::Handle,
     };
 
-    use super::CurveEdgeIntersection;
+    use super::{CurveEdgeIntersection, CurveEdgeIntersectionError};
 
     #[test]
     fn compute_edge_in_front_of_curve_origin() {
@@ -105,7 +198,8 @@ mod tests {
             half_edge.build(&mut services.objects)
         };
 
-        let intersection = CurveEdgeIntersection::compute(&curve, &half_edge);
+        let intersection =
+            CurveEdgeIntersection::compute(&curve, &half_edge).unwrap();
 
         assert_eq!(
             intersection,
@@ -138,7 +232,8 @@ mod tests {
             half_edge.build(&mut services.objects)
         };
 
-        let intersection = CurveEdgeIntersection::compute(&curve, &half_edge);
+        let intersection =
+            CurveEdgeIntersection::compute(&curve, &half_edge).unwrap();
 
         assert_eq!(
             intersection,
@@ -171,7 +266,8 @@ mod tests {
             half_edge.build(&mut services.objects)
         };
 
-        let intersection = CurveEdgeIntersection::compute(&curve, &half_edge);
+        let intersection =
+            CurveEdgeIntersection::compute(&curve, &half_edge).unwrap();
 
         assert!(intersection.is_none());
     }
@@ -199,7 +295,8 @@ mod tests {
             half_edge.build(&mut services.objects)
         };
 
-        let intersection = CurveEdgeIntersection::compute(&curve, &half_edge);
+        let intersection =
+            CurveEdgeIntersection::compute(&curve, &half_edge).unwrap();
 
         assert_eq!(
             intersection,
@@ -208,4 +305,120 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn compute_with_circle_as_curve() {
+        let mut services = Services::new();
+
+        let surface = Partial::from_full_entry_point(
+            services.objects.surfaces.xy_plane(),
+        );
+        let mut curve = PartialCurve {
+            surface: surface.clone(),
+            ..Default::default()
+        };
+        curve.update_as_circle_from_radius(1.);
+        let curve = curve.build(&mut services.objects);
+        let half_edge = {
+            let mut half_edge = PartialHalfEdge::default();
+            half_edge.update_as_line_segment_from_points(
+                surface,
+                [[0., -2.], [0., 2.]],
+            );
+
+            half_edge.build(&mut services.objects)
+        };
+
+        let intersection = CurveEdgeIntersection::compute(&curve, &half_edge);
+
+        assert!(matches!(
+            intersection,
+            Err(CurveEdgeIntersectionError::CurveNotLine)
+        ));
+    }
+
+    #[test]
+    fn compute_edge_on_circle() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+
+        // A full circle, and the upper half of the same circle.
+        let full_circle = arc(&surface, circle, Scalar::TAU, &mut services);
+        let half_circle = arc(&surface, circle, Scalar::PI, &mut services);
+
+        let line = |v: f64, services: &mut Services| {
+            let mut curve = PartialCurve {
+                surface: Partial::from_full_entry_point(surface.clone()),
+                ..Default::default()
+            };
+            curve.update_as_line_from_points([[0., v], [1., v]]);
+            curve.build(&mut services.objects)
+        };
+        let crossing = |edge: &HalfEdge, v: f64, services: &mut Services| {
+            let intersection =
+                CurveEdgeIntersection::compute(&line(v, services), edge)
+                    .unwrap();
+            intersection.map(|intersection| match intersection {
+                CurveEdgeIntersection::Points { points_on_curve } => {
+                    let mut points = points_on_curve.map(|point| point.t);
+                    points.sort();
+                    points.map(|t| (t * 1e6).round() / 1e6).to_vec()
+                }
+                CurveEdgeIntersection::Point { point_on_curve } => {
+                    vec![(point_on_curve.t * 1e6).round() / 1e6]
+                }
+                CurveEdgeIntersection::Coincident { .. } => {
+                    unreachable!("Line can't be coincident with circle")
+                }
+            })
+        };
+
+        let x = (0.75_f64.sqrt() * 1e6).round() / 1e6;
+
+        assert_eq!(
+            crossing(&full_circle, 0.5, &mut services),
+            Some(vec![Scalar::from(-x), Scalar::from(x)])
+        );
+        assert_eq!(
+            crossing(&full_circle, -0.5, &mut services),
+            Some(vec![Scalar::from(-x), Scalar::from(x)])
+        );
+        assert_eq!(
+            crossing(&half_circle, 0.5, &mut services),
+            Some(vec![Scalar::from(-x), Scalar::from(x)])
+        );
+        assert_eq!(crossing(&half_circle, -0.5, &mut services), None);
+        assert_eq!(
+            crossing(&half_circle, 1., &mut services),
+            Some(vec![Scalar::ZERO, Scalar::ZERO])
+        );
+        assert_eq!(crossing(&half_circle, 1.5, &mut services), None);
+    }
+
+    /// Build an arc of `circle`, that starts at circle coordinate zero
+    fn arc(
+        surface: &Handle<Surface>,
+        circle: Circle<2>,
+        length: Scalar,
+        services: &mut Services,
+    ) -> HalfEdge {
+        let mut half_edge = PartialHalfEdge::default();
+        half_edge.curve().write().surface =
+            Partial::from_full_entry_point(surface.clone());
+        half_edge.update_as_arc(circle, [Scalar::ZERO, length]);
+        for (vertex, t) in
+            half_edge.vertices.iter_mut().zip([Scalar::ZERO, length])
+        {
+            let mut surface_form = vertex.write().surface_form.clone();
+            let mut surface_form = surface_form.write();
+            surface_form.position = Some(circle.point_from_circle_coords([t]));
+            surface_form.surface =
+                Partial::from_full_entry_point(surface.clone());
+            surface_form.infer_global_position();
+        }
+
+        half_edge.build(&mut services.objects)
+    }
 }
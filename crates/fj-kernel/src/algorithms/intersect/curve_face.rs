@@ -5,7 +5,7 @@ use fj_math::Point;
 
 use crate::objects::{Curve, Face};
 
-use super::CurveEdgeIntersection;
+use super::{CurveEdgeIntersection, CurveEdgeIntersectionError};
 
 /// The intersections between a [`Curve`] and a [`Face`], in curve coordinates
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -28,20 +28,28 @@ impl CurveFaceIntersection {
     }
 
     /// Compute the intersections between a [`Curve`] and a [`Face`]
-    pub fn compute(curve: &Curve, face: &Face) -> Self {
+    ///
+    /// Returns an error, if the intersection between the curve and any of the
+    /// face's edges can't be computed. See [`CurveEdgeIntersection::compute`].
+    pub fn compute(
+        curve: &Curve,
+        face: &Face,
+    ) -> Result<Self, CurveEdgeIntersectionError> {
         let half_edges = face.all_cycles().flat_map(|cycle| cycle.half_edges());
 
         let mut intersections = Vec::new();
 
         for half_edge in half_edges {
-            let intersection = CurveEdgeIntersection::compute(curve, half_edge);
+            let intersection =
+                CurveEdgeIntersection::compute(curve, half_edge)?;
 
             if let Some(intersection) = intersection {
                 match intersection {
                     CurveEdgeIntersection::Point { point_on_curve } => {
                         intersections.push(point_on_curve);
                     }
-                    CurveEdgeIntersection::Coincident { points_on_curve } => {
+                    CurveEdgeIntersection::Points { points_on_curve }
+                    | CurveEdgeIntersection::Coincident { points_on_curve } => {
                         intersections.extend(points_on_curve);
                     }
                 }
@@ -58,7 +66,7 @@ impl CurveFaceIntersection {
             .map(|&[start, end]| CurveFaceIntersectionInterval { start, end })
             .collect();
 
-        Self { intervals }
+        Ok(Self { intervals })
     }
 
     /// Merge this intersection list with another
@@ -192,7 +200,10 @@ mod tests {
 
         let expected =
             CurveFaceIntersection::from_intervals([[[1.], [2.]], [[4.], [5.]]]);
-        assert_eq!(CurveFaceIntersection::compute(&curve, &face), expected);
+        assert_eq!(
+            CurveFaceIntersection::compute(&curve, &face).unwrap(),
+            expected
+        );
     }
 
     #[test]
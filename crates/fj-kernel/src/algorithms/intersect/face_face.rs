@@ -7,7 +7,10 @@ use crate::{
     storage::Handle,
 };
 
-use super::{CurveFaceIntersection, SurfaceSurfaceIntersection};
+use super::{
+    CurveEdgeIntersectionError, CurveFaceIntersection,
+    SurfaceSurfaceIntersection,
+};
 
 /// An intersection between two faces
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -28,16 +31,20 @@ pub struct FaceFaceIntersection {
 
 impl FaceFaceIntersection {
     /// Compute the intersections between two faces
+    ///
+    /// Returns an error, if the curves where the surfaces of the faces
+    /// intersect can't be intersected with the faces. See
+    /// [`CurveFaceIntersection::compute`].
     pub fn compute(
         faces: [&Face; 2],
         objects: &mut Service<Objects>,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, CurveEdgeIntersectionError> {
         let surfaces = faces.map(|face| face.surface().clone());
 
         let intersection_curves =
             match SurfaceSurfaceIntersection::compute(surfaces, objects) {
                 Some(intersection) => intersection.intersection_curves,
-                None => return None,
+                None => return Ok(None),
             };
 
         let curve_face_intersections = intersection_curves
@@ -49,17 +56,17 @@ impl FaceFaceIntersection {
 
         let intersection_intervals = {
             let [a, b] = curve_face_intersections;
-            a.merge(&b)
+            a?.merge(&b?)
         };
 
         if intersection_intervals.is_empty() {
-            return None;
+            return Ok(None);
         }
 
-        Some(Self {
+        Ok(Some(Self {
             intersection_curves,
             intersection_intervals,
-        })
+        }))
     }
 }
 
@@ -99,7 +106,8 @@ mod tests {
         });
 
         let intersection =
-            FaceFaceIntersection::compute([&a, &b], &mut services.objects);
+            FaceFaceIntersection::compute([&a, &b], &mut services.objects)
+                .unwrap();
 
         assert!(intersection.is_none());
     }
@@ -126,7 +134,8 @@ mod tests {
         });
 
         let intersection =
-            FaceFaceIntersection::compute([&a, &b], &mut services.objects);
+            FaceFaceIntersection::compute([&a, &b], &mut services.objects)
+                .unwrap();
 
         let expected_curves = surfaces.map(|surface| {
             let mut curve = PartialCurve {
@@ -0,0 +1,145 @@
+use fj_math::{Ellipse, Line, Point, Scalar, Vector};
+
+/// An intersection between a [`Line`] and an [`Ellipse`]
+///
+/// Circles are covered too, by converting them into an [`Ellipse`] first.
+#[derive(Debug, Eq, PartialEq)]
+pub enum LineEllipseIntersection {
+    /// The line touches the ellipse at a single point
+    Tangent {
+        /// The point where the line touches the ellipse, as a coordinate on
+        /// the line
+        point_on_line: Point<1>,
+    },
+
+    /// The line crosses the ellipse at two points
+    Crossing {
+        /// The intersection points, given as coordinates on the line
+        ///
+        /// The points are sorted in the direction of the line.
+        points_on_line: [Point<1>; 2],
+    },
+}
+
+impl LineEllipseIntersection {
+    /// The tolerance for detecting a tangent, relative to the ellipse's size
+    const TANGENT_TOLERANCE: f64 = 1e-9;
+
+    /// Determine the intersection between a [`Line`] and an [`Ellipse`]
+    pub fn compute(line: &Line<2>, ellipse: &Ellipse<2>) -> Option<Self> {
+        // The ellipse is the unit circle, mapped by the linear transform whose
+        // columns are `a` and `b`. Map the line back by the inverse transform,
+        // then intersect it with the unit circle.
+        let to_unit_circle = |vector: Vector<2>| {
+            let det = ellipse.a().cross2d(&ellipse.b());
+
+            Vector::from([
+                vector.cross2d(&ellipse.b()) / det,
+                ellipse.a().cross2d(&vector) / det,
+            ])
+        };
+
+        let origin = to_unit_circle(line.origin() - ellipse.center());
+        let direction = to_unit_circle(line.direction());
+
+        // Solve `|origin + direction * t| = 1` for `t`.
+        let a = direction.dot(&direction);
+        let b = origin.dot(&direction);
+        let c = origin.dot(&origin) - Scalar::ONE;
+
+        let discriminant = b * b - a * c;
+
+        // The distance of the line from the center of the unit circle. Compare
+        // that against the radius with a tolerance, as the discriminant is
+        // rarely exactly zero for a line that touches the ellipse.
+        let distance =
+            (Scalar::ONE - discriminant / a).max(Scalar::ZERO).sqrt();
+
+        if (distance - Scalar::ONE).abs()
+            <= Scalar::from(Self::TANGENT_TOLERANCE)
+        {
+            return Some(Self::Tangent {
+                point_on_line: Point::from([-b / a]),
+            });
+        }
+        if distance > Scalar::ONE {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+
+        Some(Self::Crossing {
+            points_on_line: [(-b - root) / a, (-b + root) / a]
+                .map(|t| Point::from([t])),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Ellipse, Line, Point, Vector};
+
+    use super::LineEllipseIntersection;
+
+    #[test]
+    fn compute_crossing() {
+        let line =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_u());
+        let ellipse = Ellipse::new([1., 0.], [2., 0.], [0., 1.]);
+
+        assert_eq!(
+            LineEllipseIntersection::compute(&line, &ellipse),
+            Some(LineEllipseIntersection::Crossing {
+                points_on_line: [Point::from([-1.]), Point::from([3.])],
+            }),
+        );
+    }
+
+    #[test]
+    fn compute_tangent() {
+        let line = Line::from_origin_and_direction(
+            Point::from([0., 1.]),
+            Vector::unit_u(),
+        );
+        let ellipse =
+            Ellipse::from(Circle::from_center_and_radius(Point::origin(), 1.));
+
+        assert_eq!(
+            LineEllipseIntersection::compute(&line, &ellipse),
+            Some(LineEllipseIntersection::Tangent {
+                point_on_line: Point::from([0.]),
+            }),
+        );
+    }
+
+    #[test]
+    fn compute_near_tangent() {
+        let ellipse =
+            Ellipse::from(Circle::from_center_and_radius(Point::origin(), 1.));
+
+        for v in [1. - 1e-12, 1. + 1e-12] {
+            let line = Line::from_origin_and_direction(
+                Point::from([0., v]),
+                Vector::unit_u(),
+            );
+
+            assert_eq!(
+                LineEllipseIntersection::compute(&line, &ellipse),
+                Some(LineEllipseIntersection::Tangent {
+                    point_on_line: Point::from([0.]),
+                }),
+            );
+        }
+    }
+
+    #[test]
+    fn compute_no_hit() {
+        let line = Line::from_origin_and_direction(
+            Point::from([0., 2.]),
+            Vector::unit_u(),
+        );
+        let ellipse = Ellipse::new([0., 0.], [1., 0.], [1., 1.]);
+
+        assert_eq!(LineEllipseIntersection::compute(&line, &ellipse), None);
+    }
+}
@@ -1,4 +1,4 @@
-use fj_math::{Aabb, Line, Point, Scalar, Segment, Vector};
+use fj_math::{Line, Point, Scalar, Segment, Vector};
 
 /// An intersection between a [`Line`] and a [`Segment`]
 #[derive(Debug, Eq, PartialEq)]
@@ -25,10 +25,8 @@ impl LineSegmentIntersection {
         let [a, b] = segment.points();
 
         // Find vector that is orthogonal to `segment`.
-        let n = {
-            let ab = b - a;
-            Vector::from([ab.v, ab.u])
-        };
+        let ab = b - a;
+        let n = Vector::from([-ab.v, ab.u]);
 
         let n_dot_origin = n.dot(&(b - line.origin()));
         let n_dot_direction = n.dot(&line.direction());
@@ -52,9 +50,12 @@ impl LineSegmentIntersection {
         // line defined by `segment`'s points.
         let t = n_dot_origin / n_dot_direction;
 
-        let point_is_on_segment = Aabb::<2>::from_points(segment.points())
-            .contains(line.point_from_line_coords([t]));
-        if !point_is_on_segment {
+        // Check where that point is on the segment. Unlike checking whether
+        // the segment's bounding box contains the point, this doesn't depend
+        // on the point exactly matching a coordinate of an axis-aligned
+        // segment.
+        let s = (line.point_from_line_coords([t]) - a).dot(&ab) / ab.dot(&ab);
+        if s < Scalar::ZERO || s > Scalar::ONE {
             return None;
         }
 
@@ -86,6 +87,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compute_one_hit_diagonal() {
+        let line =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_u());
+
+        assert_eq!(
+            LineSegmentIntersection::compute(
+                &line,
+                &Segment::from_points([[1., -1.], [3., 1.]]),
+            ),
+            Some(LineSegmentIntersection::Point {
+                point_on_line: Point::from([2.])
+            }),
+        );
+    }
+
     #[test]
     fn compute_coincident() {
         let line =
@@ -5,18 +5,22 @@ pub mod ray_edge;
 pub mod ray_face;
 pub mod ray_segment;
 
+mod circle_circle;
 mod curve_edge;
 mod curve_face;
 mod face_face;
+mod line_ellipse;
 mod line_segment;
 mod surface_surface;
 
 use fj_math::{Point, Vector};
 
 pub use self::{
-    curve_edge::CurveEdgeIntersection,
+    circle_circle::CircleCircleIntersection,
+    curve_edge::{CurveEdgeIntersection, CurveEdgeIntersectionError},
     curve_face::{CurveFaceIntersection, CurveFaceIntersectionInterval},
     face_face::FaceFaceIntersection,
+    line_ellipse::LineEllipseIntersection,
     line_segment::LineSegmentIntersection,
     surface_surface::SurfaceSurfaceIntersection,
 };
@@ -19,8 +19,11 @@ impl Intersect for (&HorizontalRayToTheRight<2>, &Handle<HalfEdge>) {
 
         let line = match edge.curve().path() {
             SurfacePath::Line(line) => line,
-            SurfacePath::Circle(_) => {
-                todo!("Casting rays against circles is not supported yet")
+            SurfacePath::Circle(_) | SurfacePath::Ellipse(_) => {
+                todo!(
+                    "Casting rays against circles or ellipses is not supported \
+                    yet"
+                )
             }
         };
 
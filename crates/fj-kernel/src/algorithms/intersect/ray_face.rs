@@ -18,8 +18,9 @@ impl Intersect for (&HorizontalRayToTheRight<3>, &Handle<Face>) {
         let (ray, face) = self;
 
         let plane = match face.surface().geometry().u {
-            GlobalPath::Circle(_) => todo!(
-                "Casting a ray against a swept circle is not supported yet"
+            GlobalPath::Circle(_) | GlobalPath::Ellipse(_) => todo!(
+                "Casting a ray against a swept circle or ellipse is not \
+                supported yet"
             ),
             GlobalPath::Line(line) => Plane::from_parametric(
                 line.origin(),
@@ -4,7 +4,9 @@
 //! on their respective purpose.
 
 pub mod approx;
+pub mod boolean;
 pub mod intersect;
+pub mod offset;
 pub mod reverse;
 pub mod sweep;
 pub mod transform;
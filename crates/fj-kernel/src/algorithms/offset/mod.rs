@@ -0,0 +1,56 @@
+//! Offsetting of 2-dimensional shapes
+//!
+//! See [`Offset2d`].
+
+mod sketch;
+
+use fj_math::Scalar;
+
+use crate::{objects::Objects, services::Service};
+
+use super::boolean::Boolean2dError;
+
+/// How the corners of an offset shape are joined
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Join {
+    /// Corners are rounded, with the offset distance as the radius
+    Round,
+
+    /// Corners are sharp, extending the adjacent edges until they meet
+    ///
+    /// Corners that are very sharp would extend too far. If the tip of the
+    /// corner is further than [`MITER_LIMIT`] times the offset distance from
+    /// the original corner, the corner is cut off instead.
+    Miter,
+}
+
+/// The limit for the length of [`Join::Miter`] corners
+///
+/// The limit is relative to the offset distance.
+pub const MITER_LIMIT: f64 = 4.;
+
+/// Offset 2-dimensional shapes
+///
+/// A positive distance grows the shape, a negative distance shrinks it. Parts
+/// of the shape that are thinner than twice the distance disappear, when it's
+/// shrunk.
+///
+/// # Implementation Note
+///
+/// Offsetting rebuilds the boundaries of the shape, using the same approach as
+/// [`Boolean2d`]. The same limitations apply, and are reported using the same
+/// error.
+///
+/// [`Boolean2d`]: super::boolean::Boolean2d
+pub trait Offset2d {
+    /// The shape that results from offsetting
+    type Output;
+
+    /// Offset the shape by the given distance
+    fn offset(
+        self,
+        distance: impl Into<Scalar>,
+        join: Join,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self::Output, Boolean2dError>;
+}
@@ -0,0 +1,519 @@
+use fj_math::{Circle, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::boolean::{
+        edge::{circle_coord, BoundaryEdge},
+        region::{boundary_cycles, Region},
+        Boolean2dError, BooleanOp,
+    },
+    objects::{Objects, Sketch},
+    services::Service,
+    storage::Handle,
+    validate::ValidationConfig,
+};
+
+use super::{Join, Offset2d, MITER_LIMIT};
+
+impl Offset2d for &Sketch {
+    type Output = Handle<Sketch>;
+
+    fn offset(
+        self,
+        distance: impl Into<Scalar>,
+        join: Join,
+        objects: &mut Service<Objects>,
+    ) -> Result<Self::Output, Boolean2dError> {
+        let distance = distance.into();
+
+        let mut faces = self.faces().into_iter();
+
+        let Some(first) = faces.next() else {
+            // The sketch is empty, and so is the result.
+            return Ok(Sketch::builder().build(objects));
+        };
+        let surface = first.surface().clone();
+        let color = first.color();
+
+        if faces.any(|face| face.surface().id() != surface.id()) {
+            return Err(Boolean2dError::DifferentSurfaces);
+        }
+
+        // Points that are closer than this are considered to be identical.
+        let epsilon = ValidationConfig::default().distinct_min_distance;
+
+        // The stroke is the area within `distance` of the boundary, on the side
+        // that the boundary is offset towards. Depending on the direction, it
+        // is added to or removed from the shape.
+        let mut stroke = Region::new(Vec::new());
+        if distance.abs() > epsilon {
+            for cycle in boundary_cycles(self)? {
+                for part in stroke_cycle(&cycle, distance, join, epsilon) {
+                    stroke = stroke.boolean(&part, BooleanOp::Union, epsilon);
+                }
+            }
+        }
+
+        let op = if distance > Scalar::ZERO {
+            BooleanOp::Union
+        } else {
+            BooleanOp::Difference
+        };
+
+        Region::from_sketch(self)?
+            .boolean(&stroke, op, epsilon)
+            .simplify(epsilon)?
+            .build(&surface, color, epsilon, objects)
+    }
+}
+
+/// Compute the parts that make up the stroke of a cycle
+///
+/// For each edge, this is the area between the edge and its offset. For each
+/// corner where the offset edges don't meet, it's the area that fills the gap
+/// between them.
+fn stroke_cycle(
+    cycle: &[BoundaryEdge],
+    distance: Scalar,
+    join: Join,
+    epsilon: Scalar,
+) -> Vec<Region> {
+    let offsets = cycle
+        .iter()
+        .map(|edge| offset_edge(edge, distance, epsilon))
+        .collect::<Vec<_>>();
+
+    let mut parts = Vec::new();
+
+    for (i, edge) in cycle.iter().enumerate() {
+        parts.push(band(edge, offsets[i].as_ref()));
+
+        let next = (i + 1) % cycle.len();
+
+        let [_, vertex] = edge.points();
+        let [_, tangent_before] = edge.tangents();
+        let [tangent_after, _] = cycle[next].tangents();
+
+        let before = match &offsets[i] {
+            Some(offset) => offset.points()[1],
+            None => vertex + offset_normal(tangent_before) * distance,
+        };
+        let after = match &offsets[next] {
+            Some(offset) => offset.points()[0],
+            None => vertex + offset_normal(tangent_after) * distance,
+        };
+
+        if let Some(corner) =
+            corner(vertex, [before, after], distance, join, epsilon)
+        {
+            parts.push(corner);
+        }
+    }
+
+    parts
+}
+
+/// Offset an edge
+///
+/// Returns `None`, if the edge is an arc that collapses into the center of its
+/// circle.
+fn offset_edge(
+    edge: &BoundaryEdge,
+    distance: Scalar,
+    epsilon: Scalar,
+) -> Option<BoundaryEdge> {
+    match edge {
+        BoundaryEdge::Segment { points } => {
+            let [tangent, _] = edge.tangents();
+            let offset = offset_normal(tangent) * distance;
+
+            Some(BoundaryEdge::Segment {
+                points: points.map(|point| point + offset),
+            })
+        }
+        BoundaryEdge::Arc { circle, range } => {
+            // The offset is towards the right of the edge. For a
+            // counter-clockwise arc, that's away from the center.
+            let is_ccw = circle.a().cross2d(&circle.b())
+                * (range[1] - range[0])
+                > Scalar::ZERO;
+            let radius = if is_ccw {
+                circle.radius() + distance
+            } else {
+                circle.radius() - distance
+            };
+
+            if radius < epsilon {
+                return None;
+            }
+
+            // Construct `b` by rotating `a`, to make sure both have precisely
+            // the same length, as required by `Circle`.
+            let a = circle.a() * (radius / circle.radius());
+            let b = if circle.a().cross2d(&circle.b()) > Scalar::ZERO {
+                Vector::from([-a.v, a.u])
+            } else {
+                Vector::from([a.v, -a.u])
+            };
+
+            Some(BoundaryEdge::Arc {
+                circle: Circle::new(circle.center(), a, b),
+                range: *range,
+            })
+        }
+    }
+}
+
+/// Compute the area between an edge and its offset
+fn band(edge: &BoundaryEdge, offset: Option<&BoundaryEdge>) -> Region {
+    let [a, b] = edge.points();
+    let is_full_circle = edge.is_full_circle();
+
+    let mut edges = vec![*edge];
+
+    match (edge, offset) {
+        (_, Some(offset)) => {
+            let [c, d] = offset.points();
+
+            if !is_full_circle {
+                edges.push(segment(b, d));
+            }
+            edges.push(offset.reverse());
+            if !is_full_circle {
+                edges.push(segment(c, a));
+            }
+        }
+        (BoundaryEdge::Arc { circle, .. }, None) => {
+            // The offset collapsed into the center of the circle. If the arc
+            // is a full circle, the edge itself already bounds the band.
+            if !is_full_circle {
+                edges.push(segment(b, circle.center()));
+                edges.push(segment(circle.center(), a));
+            }
+        }
+        (BoundaryEdge::Segment { .. }, None) => {
+            unreachable!("Offset of a line segment always exists")
+        }
+    }
+
+    oriented_region(edges)
+}
+
+/// Compute the area that fills the gap between two offset edges at a corner
+///
+/// Returns `None`, if there is no gap to fill.
+fn corner(
+    vertex: Point<2>,
+    [before, after]: [Point<2>; 2],
+    distance: Scalar,
+    join: Join,
+    epsilon: Scalar,
+) -> Option<Region> {
+    if before.distance_to(&after) < epsilon {
+        return None;
+    }
+
+    let a = before - vertex;
+    let b = after - vertex;
+
+    // If the offset edges turn towards each other, they overlap, and the
+    // bands of both edges already cover the corner.
+    let turn = a.cross2d(&b);
+    if turn * distance < Scalar::ZERO {
+        return None;
+    }
+
+    let edges = match join {
+        Join::Round => {
+            let circle = {
+                let b = if distance > Scalar::ZERO {
+                    Vector::from([-a.v, a.u])
+                } else {
+                    Vector::from([a.v, -a.u])
+                };
+
+                Circle::new(vertex, a, b)
+            };
+
+            let mut angle = circle_coord(&circle, after);
+            if angle < Scalar::ZERO {
+                angle += Scalar::TAU;
+            }
+
+            vec![
+                segment(vertex, before),
+                BoundaryEdge::Arc {
+                    circle,
+                    range: [Scalar::ZERO, angle],
+                },
+                segment(after, vertex),
+            ]
+        }
+        Join::Miter => {
+            if turn.abs() < epsilon * distance.abs() {
+                // The edges reverse direction at this corner. There is no
+                // sensible miter, and cutting it off leaves no area.
+                return None;
+            }
+
+            // The tip of the miter is where the offset edges would meet, if
+            // extended.
+            let tip = vertex
+                + (a + b) / (Scalar::ONE + a.dot(&b) / (distance * distance));
+
+            if (tip - vertex).magnitude() > distance.abs() * MITER_LIMIT {
+                vec![
+                    segment(vertex, before),
+                    segment(before, after),
+                    segment(after, vertex),
+                ]
+            } else {
+                vec![
+                    segment(vertex, before),
+                    segment(before, tip),
+                    segment(tip, after),
+                    segment(after, vertex),
+                ]
+            }
+        }
+    };
+
+    Some(oriented_region(edges))
+}
+
+/// Compute the normal that points to the right of the given tangent
+fn offset_normal(tangent: Vector<2>) -> Vector<2> {
+    Vector::from([tangent.v, -tangent.u])
+}
+
+fn segment(a: Point<2>, b: Point<2>) -> BoundaryEdge {
+    BoundaryEdge::Segment { points: [a, b] }
+}
+
+/// Create a region from a single closed cycle, regardless of its orientation
+fn oriented_region(edges: Vec<BoundaryEdge>) -> Region {
+    let double_area = edges
+        .iter()
+        .map(BoundaryEdge::double_area)
+        .fold(Scalar::ZERO, |a, b| a + b);
+
+    if double_area < Scalar::ZERO {
+        Region::new(
+            edges.into_iter().rev().map(BoundaryEdge::reverse).collect(),
+        )
+    } else {
+        Region::new(edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Circle, Scalar};
+
+    use crate::{
+        algorithms::{
+            boolean::{edge::BoundaryEdge, region::Region},
+            offset::{Join, Offset2d},
+        },
+        objects::Sketch,
+        services::Services,
+        storage::Handle,
+        validate::ValidationConfig,
+    };
+
+    #[test]
+    fn grow_square_with_round_join() {
+        let mut services = Services::new();
+
+        let square = square(&mut services);
+        let offset = square
+            .offset(0.5, Join::Round, &mut services.objects)
+            .unwrap();
+
+        let faces = offset.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 8);
+        assert_eq!(faces[0].interiors().count(), 0);
+    }
+
+    #[test]
+    fn grow_square_with_miter_join() {
+        let mut services = Services::new();
+
+        let square = square(&mut services);
+        let offset = square
+            .offset(0.5, Join::Miter, &mut services.objects)
+            .unwrap();
+
+        let faces = offset.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 4);
+    }
+
+    #[test]
+    fn grow_triangle_with_miter_join() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let triangle = Sketch::builder()
+            .with_polygon_from_points(
+                surface,
+                [[0., 0.], [2., 0.], [1., 2.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+
+        let offset = triangle
+            .offset(0.5, Join::Miter, &mut services.objects)
+            .unwrap();
+
+        let faces = offset.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 3);
+    }
+
+    #[test]
+    fn shrink_square() {
+        let mut services = Services::new();
+
+        let square = square(&mut services);
+
+        for join in [Join::Round, Join::Miter] {
+            let offset =
+                square.offset(-0.5, join, &mut services.objects).unwrap();
+
+            let faces = offset.faces().into_iter().collect::<Vec<_>>();
+            assert_eq!(faces.len(), 1);
+            assert_eq!(faces[0].exterior().half_edges().count(), 4);
+        }
+    }
+
+    #[test]
+    fn shrink_square_until_it_disappears() {
+        let mut services = Services::new();
+
+        let square = square(&mut services);
+        let offset = square
+            .offset(-1.5, Join::Round, &mut services.objects)
+            .unwrap();
+
+        assert_eq!(offset.faces().into_iter().count(), 0);
+    }
+
+    #[test]
+    fn grow_concave_polygon() {
+        let mut services = Services::new();
+
+        // An L-shape with five convex corners and one concave corner.
+        let surface = services.objects.surfaces.xy_plane();
+        let l_shape = Sketch::builder()
+            .with_polygon_from_points(
+                surface,
+                [[0., 0.], [2., 0.], [2., 1.], [1., 1.], [1., 2.], [0., 2.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+
+        let offset = l_shape
+            .offset(0.25, Join::Round, &mut services.objects)
+            .unwrap();
+
+        let faces = offset.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 11);
+    }
+
+    #[test]
+    fn grow_polygon_with_hole() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let outer = Sketch::builder()
+            .with_polygon_from_points(
+                surface.clone(),
+                [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+        let inner = Sketch::builder()
+            .with_polygon_from_points(
+                surface,
+                [[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects);
+        let frame = {
+            use crate::algorithms::boolean::Boolean2d;
+            outer
+                .difference(&inner, Color::default(), &mut services.objects)
+                .unwrap()
+        };
+
+        // Growing the frame shrinks the hole, which has no convex corners from
+        // the perspective of the hole's boundary.
+        let offset = frame
+            .offset(0.5, Join::Round, &mut services.objects)
+            .unwrap();
+
+        let faces = offset.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].exterior().half_edges().count(), 8);
+        assert_eq!(faces[0].interiors().count(), 1);
+        let interior = faces[0].interiors().next().unwrap();
+        assert_eq!(interior.half_edges().count(), 4);
+
+        // Grow it more, and the hole disappears.
+        let offset = frame
+            .offset(1.5, Join::Round, &mut services.objects)
+            .unwrap();
+
+        let faces = offset.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 0);
+    }
+
+    #[test]
+    fn offset_circle() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let circle = Region::new(vec![BoundaryEdge::Arc {
+            circle: Circle::from_center_and_radius([0., 0.], 1.),
+            range: [Scalar::ZERO, Scalar::TAU],
+        }])
+        .build(
+            &surface,
+            Color::default(),
+            ValidationConfig::default().distinct_min_distance,
+            &mut services.objects,
+        )
+        .unwrap();
+
+        for distance in [0.5, -0.5] {
+            let offset = circle
+                .offset(distance, Join::Round, &mut services.objects)
+                .unwrap();
+
+            let faces = offset.faces().into_iter().collect::<Vec<_>>();
+            assert_eq!(faces.len(), 1);
+            assert_eq!(faces[0].exterior().half_edges().count(), 1);
+        }
+
+        let offset = circle
+            .offset(-1.5, Join::Round, &mut services.objects)
+            .unwrap();
+        assert_eq!(offset.faces().into_iter().count(), 0);
+    }
+
+    fn square(services: &mut Services) -> Handle<Sketch> {
+        let surface = services.objects.surfaces.xy_plane();
+
+        Sketch::builder()
+            .with_polygon_from_points(
+                surface,
+                [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects)
+    }
+}
@@ -1,4 +1,4 @@
-use fj_math::{Circle, Line, Vector};
+use fj_math::{Circle, Ellipse, Line, Vector};
 
 use crate::{
     builder::SurfaceBuilder,
@@ -10,7 +10,7 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for Handle<Curve> {
     type Swept = Handle<Surface>;
@@ -20,9 +20,9 @@ impl Sweep for Handle<Curve> {
         path: impl Into<Vector<3>>,
         _: &mut SweepCache,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept {
+    ) -> Result<Self::Swept, SweepError> {
         match self.surface().geometry().u {
-            GlobalPath::Circle(_) => {
+            GlobalPath::Circle(_) | GlobalPath::Ellipse(_) => {
                 // Sweeping a `Curve` creates a `Surface`. The u-axis of that
                 // `Surface` is a `GlobalPath`, which we are computing below.
                 // That computation might or might not work with an arbitrary
@@ -34,10 +34,7 @@ impl Sweep for Handle<Curve> {
                 // flat surface). But is the surface we're sweeping from is
                 // curved, there's simply no way to represent the curve of the
                 // resulting bottom edge.
-                todo!(
-                    "Sweeping a curve that is defined on a curved surface is \
-                    not supported yet."
-                )
+                return Err(SweepError::CurvedSurface);
             }
             GlobalPath::Line(_) => {
                 // We're sweeping from a curve on a flat surface, which is
@@ -64,6 +61,24 @@ impl Sweep for Handle<Curve> {
 
                 GlobalPath::Circle(circle)
             }
+            SurfacePath::Ellipse(ellipse) => {
+                let center = self
+                    .surface()
+                    .geometry()
+                    .point_from_surface_coords(ellipse.center());
+                let a = self
+                    .surface()
+                    .geometry()
+                    .vector_from_surface_coords(ellipse.a());
+                let b = self
+                    .surface()
+                    .geometry()
+                    .vector_from_surface_coords(ellipse.b());
+
+                let ellipse = Ellipse::new(center, a, b);
+
+                GlobalPath::Ellipse(ellipse)
+            }
             SurfacePath::Line(line) => {
                 let origin = self
                     .surface()
@@ -80,8 +95,43 @@ impl Sweep for Handle<Curve> {
             }
         };
 
-        PartialSurface::from_axes(u, path)
+        Ok(PartialSurface::from_axes(u, path)
             .build(objects)
-            .insert(objects)
+            .insert(objects))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::sweep::{Sweep, SweepError},
+        builder::{CurveBuilder, SurfaceBuilder},
+        geometry::path::GlobalPath,
+        insert::Insert,
+        partial::{Partial, PartialCurve, PartialObject, PartialSurface},
+        services::Services,
+    };
+
+    #[test]
+    fn sweep_curve_on_curved_surface() {
+        let mut services = Services::new();
+
+        let surface = PartialSurface::from_axes(
+            GlobalPath::circle_from_radius(1.),
+            [0., 0., 1.],
+        )
+        .build(&mut services.objects)
+        .insert(&mut services.objects);
+        let mut curve = PartialCurve {
+            surface: Partial::from_full_entry_point(surface),
+            ..Default::default()
+        };
+        curve.update_as_line_from_points([[0., 0.], [1., 0.]]);
+        let curve = curve
+            .build(&mut services.objects)
+            .insert(&mut services.objects);
+
+        let swept = curve.sweep([1., 0., 0.], &mut services.objects);
+        assert!(matches!(swept, Err(SweepError::CurvedSurface)));
     }
 }
@@ -15,7 +15,7 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for (Handle<HalfEdge>, Color) {
     type Swept = Handle<Face>;
@@ -25,12 +25,14 @@ impl Sweep for (Handle<HalfEdge>, Color) {
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept {
+    ) -> Result<Self::Swept, SweepError> {
         let (edge, color) = self;
         let path = path.into();
 
-        let surface =
-            edge.curve().clone().sweep_with_cache(path, cache, objects);
+        let surface = edge
+            .curve()
+            .clone()
+            .sweep_with_cache(path, cache, objects)?;
 
         // We can't use the edge we're sweeping from as the bottom edge, as that
         // is not defined in the right surface. Let's create a new bottom edge,
@@ -88,9 +90,12 @@ impl Sweep for (Handle<HalfEdge>, Color) {
             HalfEdge::new(vertices, edge.global_form().clone()).insert(objects)
         };
 
-        let side_edges = bottom_edge.vertices().clone().map(|vertex| {
-            (vertex, surface.clone()).sweep_with_cache(path, cache, objects)
-        });
+        let side_edges = {
+            let [a, b] = bottom_edge.vertices().clone().map(|vertex| {
+                (vertex, surface.clone()).sweep_with_cache(path, cache, objects)
+            });
+            [a?, b?]
+        };
 
         let top_edge = {
             let bottom_vertices = bottom_edge.vertices();
@@ -179,7 +184,7 @@ impl Sweep for (Handle<HalfEdge>, Color) {
             color: Some(color),
             ..Default::default()
         };
-        face.build(objects).insert(objects)
+        Ok(face.build(objects).insert(objects))
     }
 }
 
@@ -217,7 +222,8 @@ mod tests {
         };
 
         let face = (half_edge, Color::default())
-            .sweep([0., 0., 1.], &mut services.objects);
+            .sweep([0., 0., 1.], &mut services.objects)
+            .unwrap();
 
         let expected_face = {
             let surface = Partial::from_full_entry_point(
@@ -8,7 +8,7 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for Handle<Face> {
     type Swept = Handle<Shell>;
@@ -18,17 +18,16 @@ impl Sweep for Handle<Face> {
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept {
+    ) -> Result<Self::Swept, SweepError> {
         let path = path.into();
 
         let mut faces = Vec::new();
 
         let is_negative_sweep = {
             let u = match self.surface().geometry().u {
-                GlobalPath::Circle(_) => todo!(
-                    "Sweeping from faces defined in round surfaces is not \
-                    supported"
-                ),
+                GlobalPath::Circle(_) | GlobalPath::Ellipse(_) => {
+                    return Err(SweepError::CurvedSurface)
+                }
                 GlobalPath::Line(line) => line.direction(),
             };
             let v = self.surface().geometry().v;
@@ -68,13 +67,13 @@ impl Sweep for Handle<Face> {
                 };
 
                 let face = (half_edge, self.color())
-                    .sweep_with_cache(path, cache, objects);
+                    .sweep_with_cache(path, cache, objects)?;
 
                 faces.push(face);
             }
         }
 
-        Shell::builder().with_faces(faces).build(objects)
+        Ok(Shell::builder().with_faces(faces).build(objects))
     }
 }
 
@@ -110,7 +109,8 @@ mod tests {
                 &mut services.objects,
             )
             .build(&mut services.objects)
-            .sweep(UP, &mut services.objects);
+            .sweep(UP, &mut services.objects)
+            .unwrap();
 
         let bottom = PartialFace::default()
             .with_exterior_polygon_from_points(surface.clone(), TRIANGLE)
@@ -143,7 +143,9 @@ mod tests {
                     .build(&mut services.objects)
                     .insert(&mut services.objects)
             };
-            (half_edge, Color::default()).sweep(UP, &mut services.objects)
+            (half_edge, Color::default())
+                .sweep(UP, &mut services.objects)
+                .unwrap()
         });
 
         assert!(side_faces
@@ -163,7 +165,8 @@ mod tests {
                 &mut services.objects,
             )
             .build(&mut services.objects)
-            .sweep(DOWN, &mut services.objects);
+            .sweep(DOWN, &mut services.objects)
+            .unwrap();
 
         let bottom = PartialFace::default()
             .with_exterior_polygon_from_points(
@@ -197,7 +200,9 @@ mod tests {
                     .insert(&mut services.objects)
                     .reverse(&mut services.objects)
             };
-            (half_edge, Color::default()).sweep(DOWN, &mut services.objects)
+            (half_edge, Color::default())
+                .sweep(DOWN, &mut services.objects)
+                .unwrap()
         });
 
         assert!(side_faces
@@ -26,7 +26,7 @@ pub trait Sweep: Sized {
         self,
         path: impl Into<Vector<3>>,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept {
+    ) -> Result<Self::Swept, SweepError> {
         let mut cache = SweepCache::default();
         self.sweep_with_cache(path, &mut cache, objects)
     }
@@ -37,7 +37,17 @@ pub trait Sweep: Sized {
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept;
+    ) -> Result<Self::Swept, SweepError>;
+}
+
+/// Error sweeping an object
+///
+/// See [`Sweep`].
+#[derive(Debug, thiserror::Error)]
+pub enum SweepError {
+    /// The object is defined in a curved surface
+    #[error("Sweeping from curved surfaces is not supported yet")]
+    CurvedSurface,
 }
 
 /// A cache used for sweeping
@@ -6,7 +6,7 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for Handle<Sketch> {
     type Swept = Handle<Solid>;
@@ -16,15 +16,15 @@ impl Sweep for Handle<Sketch> {
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept {
+    ) -> Result<Self::Swept, SweepError> {
         let path = path.into();
 
         let mut shells = Vec::new();
         for face in self.faces().clone() {
-            let shell = face.sweep_with_cache(path, cache, objects);
+            let shell = face.sweep_with_cache(path, cache, objects)?;
             shells.push(shell);
         }
 
-        Solid::builder().with_shells(shells).build(objects)
+        Ok(Solid::builder().with_shells(shells).build(objects))
     }
 }
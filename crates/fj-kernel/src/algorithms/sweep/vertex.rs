@@ -11,7 +11,7 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{Sweep, SweepCache, SweepError};
 
 impl Sweep for (Handle<Vertex>, Handle<Surface>) {
     type Swept = Handle<HalfEdge>;
@@ -21,7 +21,7 @@ impl Sweep for (Handle<Vertex>, Handle<Surface>) {
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept {
+    ) -> Result<Self::Swept, SweepError> {
         let (vertex, surface) = self;
         let path = path.into();
 
@@ -63,7 +63,7 @@ impl Sweep for (Handle<Vertex>, Handle<Surface>) {
         let (edge_global, vertices_global) = vertex
             .global_form()
             .clone()
-            .sweep_with_cache(path, cache, objects);
+            .sweep_with_cache(path, cache, objects)?;
 
         // Next, let's compute the surface coordinates of the two vertices of
         // the output `Edge`, as we're going to need these for the rest of this
@@ -120,7 +120,7 @@ impl Sweep for (Handle<Vertex>, Handle<Surface>) {
 
         // And finally, creating the output `Edge` is just a matter of
         // assembling the pieces we've already created.
-        HalfEdge::new(vertices, edge_global).insert(objects)
+        Ok(HalfEdge::new(vertices, edge_global).insert(objects))
     }
 }
 
@@ -132,7 +132,7 @@ impl Sweep for Handle<GlobalVertex> {
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         objects: &mut Service<Objects>,
-    ) -> Self::Swept {
+    ) -> Result<Self::Swept, SweepError> {
         let curve = GlobalCurve.insert(objects);
 
         let a = self.clone();
@@ -151,7 +151,7 @@ impl Sweep for Handle<GlobalVertex> {
         // The vertices of the returned `GlobalEdge` are in normalized order,
         // which means the order can't be relied upon by the caller. Return the
         // ordered vertices in addition.
-        (global_edge, vertices)
+        Ok((global_edge, vertices))
     }
 }
 
@@ -195,7 +195,8 @@ mod tests {
         .insert(&mut services.objects);
 
         let half_edge = (vertex, surface.clone())
-            .sweep([0., 0., 1.], &mut services.objects);
+            .sweep([0., 0., 1.], &mut services.objects)
+            .unwrap();
 
         let expected_half_edge = {
             let mut half_edge = PartialHalfEdge::default();
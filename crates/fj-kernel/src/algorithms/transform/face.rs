@@ -1,6 +1,7 @@
-use fj_math::Transform;
+use fj_math::{Scalar, Transform};
 
 use crate::{
+    algorithms::reverse::Reverse,
     objects::{Face, FaceSet, Objects},
     services::Service,
 };
@@ -17,13 +18,28 @@ impl TransformObject for Face {
         // Color does not need to be transformed.
         let color = self.color();
 
-        let exterior = self
+        let mut exterior = self
             .exterior()
             .clone()
             .transform_with_cache(transform, objects, cache);
-        let interiors = self.interiors().cloned().map(|interior| {
-            interior.transform_with_cache(transform, objects, cache)
-        });
+        let mut interiors = self
+            .interiors()
+            .cloned()
+            .map(|interior| {
+                interior.transform_with_cache(transform, objects, cache)
+            })
+            .collect::<Vec<_>>();
+
+        // A mirroring transform turns the face inside out: Its cycles keep
+        // their winding in surface coordinates, but the surface now faces the
+        // other way. Reversing the cycles makes the face point outward again.
+        if transform.determinant() < Scalar::ZERO {
+            exterior = exterior.reverse(objects);
+            interiors = interiors
+                .into_iter()
+                .map(|interior| interior.reverse(objects))
+                .collect();
+        }
 
         Self::new(exterior, interiors, color)
     }
@@ -45,3 +61,80 @@ impl TransformObject for FaceSet {
         faces
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Transform, Vector};
+
+    use crate::{
+        algorithms::sweep::Sweep,
+        geometry::path::GlobalPath,
+        objects::{Face, Handedness, Sketch, Solid},
+        services::Services,
+        storage::Handle,
+    };
+
+    use super::TransformObject;
+
+    #[test]
+    fn mirror_swept_sketch() {
+        let mut services = Services::new();
+
+        let surface = services.objects.surfaces.xy_plane();
+        let solid = Sketch::builder()
+            .with_polygon_from_points(
+                surface,
+                [[0., 0.], [1., 0.], [0., 1.]],
+                &mut services.objects,
+            )
+            .build(&mut services.objects)
+            .sweep([0., 0., 1.], &mut services.objects)
+            .unwrap();
+        assert_faces_point_outward(&solid);
+
+        let mirrored = solid
+            .transform(&Transform::mirror([1., 0., 0.]), &mut services.objects);
+        assert_faces_point_outward(&mirrored);
+    }
+
+    fn assert_faces_point_outward(solid: &Solid) {
+        let faces = solid
+            .shells()
+            .flat_map(|shell| shell.faces().into_iter().cloned())
+            .collect::<Vec<_>>();
+
+        let centers = faces.iter().map(center).collect::<Vec<_>>();
+        let center_of_solid = mean(&centers);
+
+        for (face, center) in faces.iter().zip(centers) {
+            let surface = face.surface().geometry();
+            let GlobalPath::Line(u) = surface.u else {
+                panic!("Expected only plane faces");
+            };
+
+            let normal = match face.coord_handedness() {
+                Handedness::RightHanded => u.direction().cross(&surface.v),
+                Handedness::LeftHanded => surface.v.cross(&u.direction()),
+            };
+
+            assert!((center - center_of_solid).dot(&normal) > Scalar::ZERO);
+        }
+    }
+
+    fn center(face: &Handle<Face>) -> Point<3> {
+        let points = face
+            .exterior()
+            .half_edges()
+            .map(|half_edge| half_edge.back().global_form().position())
+            .collect::<Vec<_>>();
+
+        mean(&points)
+    }
+
+    fn mean(points: &[Point<3>]) -> Point<3> {
+        let sum = points
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, point| sum + point.coords);
+        Point::origin() + sum / points.len() as f64
+    }
+}
@@ -2,12 +2,17 @@
 
 mod delaunay;
 mod polygon;
+mod refine;
 
 use fj_interop::mesh::Mesh;
 use fj_math::Point;
+use rayon::prelude::*;
+use tracing::warn;
 
 use self::polygon::Polygon;
 
+pub use self::refine::{refine, TooManyTriangles, MAX_TRIANGLES};
+
 use super::approx::{face::FaceApprox, Approx, Tolerance};
 
 /// Triangulate a shape
@@ -34,10 +39,27 @@ where
     fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>) {
         let (approx, tolerance) = self;
 
-        let approx = approx.approx(tolerance);
+        let approx = approx.approx(tolerance).into_iter().collect::<Vec<_>>();
+
+        // The faces are independent of each other, so they are triangulated in
+        // parallel.
+        let mut faces = approx
+            .into_par_iter()
+            .map(|approx| approx.triangulate())
+            .collect::<Vec<_>>();
+
+        // The faces are refined together, to keep their shared edges intact.
+        // If that would create too many triangles, they are left as they are.
+        if let Some(max_edge_length) = tolerance.max_edge_length() {
+            if let Err(err) = refine(&mut faces, max_edge_length) {
+                warn!("Not refining faces: {err}");
+            }
+        }
 
-        for approx in approx {
-            approx.triangulate_into_mesh(mesh);
+        for face in faces {
+            for triangle in face.triangles() {
+                mesh.push_triangle(triangle.inner, triangle.color);
+            }
         }
     }
 }
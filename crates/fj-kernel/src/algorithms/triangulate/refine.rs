@@ -0,0 +1,247 @@
+use std::collections::{BTreeMap, BinaryHeap};
+
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::{Point, Scalar, Triangle};
+
+/// Split triangles, until none of their edges is longer than `max_edge_length`
+///
+/// The meshes are refined together, so edges that are shared between them are
+/// split in the same way, and no gaps open up between the meshes. The longest
+/// edge is always split first, at its midpoint, which keeps the triangles from
+/// becoming too thin.
+///
+/// Refining doesn't change the geometry of the meshes, only how many triangles
+/// they consist of.
+///
+/// Returns an error, if the refined meshes would consist of more than
+/// [`MAX_TRIANGLES`] triangles, as would be the case for a `max_edge_length`
+/// that is tiny compared to the size of the meshes. The meshes are left
+/// unchanged in that case.
+pub fn refine(
+    meshes: &mut [Mesh<Point<3>>],
+    max_edge_length: Scalar,
+) -> Result<(), TooManyTriangles> {
+    refine_with_budget(meshes, max_edge_length, MAX_TRIANGLES)
+}
+
+/// The maximum number of triangles that [`refine`] creates
+pub const MAX_TRIANGLES: usize = 2_000_000;
+
+fn refine_with_budget(
+    meshes: &mut [Mesh<Point<3>>],
+    max_edge_length: Scalar,
+    max_triangles: usize,
+) -> Result<(), TooManyTriangles> {
+    let mut refinement = Refinement::default();
+
+    for (i, mesh) in meshes.iter().enumerate() {
+        for triangle in mesh.triangles() {
+            let vertices = triangle
+                .inner
+                .points()
+                .map(|point| refinement.vertex(point));
+            refinement.triangles.push((vertices, i, triangle.color));
+        }
+    }
+
+    for (i, (vertices, _, _)) in refinement.triangles.iter().enumerate() {
+        for edge in edges(*vertices) {
+            refinement.edges.entry(key(edge)).or_default().push(i);
+        }
+    }
+
+    let mut queue = refinement
+        .edges
+        .keys()
+        .map(|&edge| (refinement.length(edge), edge))
+        .collect::<BinaryHeap<_>>();
+
+    while let Some((length, edge)) = queue.pop() {
+        if length <= max_edge_length {
+            break;
+        }
+
+        // The edge might have been split already, since it was queued.
+        if !refinement.edges.contains_key(&edge) {
+            continue;
+        }
+
+        for edge in refinement.split(edge) {
+            queue.push((refinement.length(edge), edge));
+        }
+
+        if refinement.triangles.len() > max_triangles {
+            return Err(TooManyTriangles(max_triangles));
+        }
+    }
+
+    for mesh in meshes.iter_mut() {
+        *mesh = Mesh::new();
+    }
+    for (vertices, i, color) in refinement.triangles {
+        let points = vertices.map(|vertex| refinement.points[vertex]);
+
+        if let Ok(triangle) = Triangle::from_points(points) {
+            meshes[i].push_triangle(triangle, color);
+        }
+    }
+
+    Ok(())
+}
+
+/// Error refining meshes
+///
+/// See [`refine`].
+#[derive(Debug, thiserror::Error)]
+#[error("Refining the mesh would create more than {0} triangles")]
+pub struct TooManyTriangles(pub usize);
+
+type Edge = [usize; 2];
+
+#[derive(Default)]
+struct Refinement {
+    points: Vec<Point<3>>,
+    indices: BTreeMap<Point<3>, usize>,
+
+    /// The triangles, with the index of their mesh and their color
+    triangles: Vec<([usize; 3], usize, Color)>,
+
+    /// The triangles that each edge belongs to
+    edges: BTreeMap<Edge, Vec<usize>>,
+}
+
+impl Refinement {
+    fn vertex(&mut self, point: Point<3>) -> usize {
+        *self.indices.entry(point).or_insert_with(|| {
+            self.points.push(point);
+            self.points.len() - 1
+        })
+    }
+
+    fn length(&self, [a, b]: Edge) -> Scalar {
+        self.points[a].distance_to(&self.points[b])
+    }
+
+    /// Split an edge at its midpoint, and return the new edges
+    fn split(&mut self, edge: Edge) -> Vec<Edge> {
+        let [a, b] = edge.map(|vertex| self.points[vertex]);
+        let midpoint = self.vertex(a + (b - a) / 2.);
+
+        let mut new_edges = vec![[edge[0], midpoint], [midpoint, edge[1]]];
+
+        let triangles = self.edges.remove(&edge).unwrap_or_default();
+        for i in triangles {
+            let (vertices, mesh, color) = self.triangles[i];
+
+            // Rotate the triangle, so the split edge goes from `a` to `b`,
+            // keeping the winding.
+            let rotation = (0..3)
+                .find(|&j| key([vertices[j], vertices[(j + 1) % 3]]) == edge)
+                .expect("Triangle doesn't contain its edge");
+            let [a, b, c] = [0, 1, 2].map(|j| vertices[(rotation + j) % 3]);
+
+            let j = self.triangles.len();
+            self.triangles[i].0 = [a, midpoint, c];
+            self.triangles.push(([midpoint, b, c], mesh, color));
+
+            let edges = &mut self.edges;
+            edges.entry(key([a, midpoint])).or_default().push(i);
+            edges.entry(key([midpoint, b])).or_default().push(j);
+            edges.entry(key([midpoint, c])).or_default().extend([i, j]);
+            for triangle in edges.entry(key([b, c])).or_default() {
+                if *triangle == i {
+                    *triangle = j;
+                }
+            }
+
+            new_edges.push(key([midpoint, c]));
+        }
+
+        new_edges.iter().map(|&edge| key(edge)).collect()
+    }
+}
+
+fn edges([a, b, c]: [usize; 3]) -> [Edge; 3] {
+    [[a, b], [b, c], [c, a]]
+}
+
+fn key([a, b]: Edge) -> Edge {
+    if a < b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use super::{refine, refine_with_budget, TooManyTriangles};
+
+    #[test]
+    fn refine_shared_edges() {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [4., 0., 0.], [4., 1., 0.], [0., 1., 0.]]
+                .map(Point::from);
+
+        // Two meshes that share the diagonal of a rectangle.
+        let mut meshes = [Mesh::new(), Mesh::new()];
+        meshes[0].push_triangle([a, b, c], Color::default());
+        meshes[1].push_triangle([a, c, d], Color::default());
+
+        let max_edge_length = Scalar::from(1.);
+        refine(&mut meshes, max_edge_length).unwrap();
+
+        let mut area = Scalar::ZERO;
+        for mesh in &meshes {
+            for triangle in mesh.triangles() {
+                let [p, q, r] = triangle.inner.points();
+
+                for [p, q] in [[p, q], [q, r], [r, p]] {
+                    assert!(p.distance_to(&q) <= max_edge_length);
+                }
+
+                // All triangles still face the same way.
+                let normal = (q - p).cross(&(r - p));
+                assert!(normal.z > Scalar::ZERO);
+                area += normal.magnitude() / 2.;
+            }
+        }
+        assert!((area - 4.).abs() < Scalar::from(1e-12));
+
+        // Every point on the shared diagonal is a vertex of both meshes.
+        let vertices = meshes
+            .iter()
+            .map(|mesh| {
+                let mut vertices = mesh
+                    .vertices()
+                    .filter(|point| point.y * 4. == point.x)
+                    .collect::<Vec<_>>();
+                vertices.sort();
+                vertices
+            })
+            .collect::<Vec<_>>();
+        assert!(vertices[0].len() > 2);
+        assert_eq!(vertices[0], vertices[1]);
+    }
+
+    #[test]
+    fn refine_too_many_triangles() {
+        let [a, b, c] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from);
+
+        let mut meshes = [Mesh::new()];
+        meshes[0].push_triangle([a, b, c], Color::default());
+
+        let result = refine_with_budget(&mut meshes, Scalar::from(1e-3), 1000);
+
+        assert!(matches!(result, Err(TooManyTriangles(1000))));
+        let triangles = meshes[0]
+            .triangles()
+            .map(|triangle| triangle.inner.points())
+            .collect::<Vec<_>>();
+        assert_eq!(triangles, vec![[a, b, c]]);
+    }
+}
@@ -1,7 +1,8 @@
 use fj_interop::ext::ArrayExt;
-use fj_math::{Point, Scalar};
+use fj_math::{Circle, Point, Scalar};
 
 use crate::{
+    geometry::path::SurfacePath,
     objects::Surface,
     partial::{Partial, PartialGlobalEdge, PartialHalfEdge},
 };
@@ -13,6 +14,18 @@ pub trait HalfEdgeBuilder {
     /// Update partial half-edge to be a circle, from the given radius
     fn update_as_circle_from_radius(&mut self, radius: impl Into<Scalar>);
 
+    /// Update partial half-edge to be an arc on the given circle
+    ///
+    /// The arc spans the given range of circle coordinates. The surface
+    /// positions of the half-edge's vertices are not updated, as they might be
+    /// shared with neighboring half-edges. If they are not available, they are
+    /// inferred from the circle when the half-edge is built.
+    fn update_as_arc(
+        &mut self,
+        circle: Circle<2>,
+        range: [impl Into<Scalar>; 2],
+    );
+
     /// Update partial half-edge to be a line segment, from the given points
     fn update_as_line_segment_from_points(
         &mut self,
@@ -57,6 +70,23 @@ impl HalfEdgeBuilder for PartialHalfEdge {
             [global_vertex.clone(), global_vertex];
     }
 
+    fn update_as_arc(
+        &mut self,
+        circle: Circle<2>,
+        range: [impl Into<Scalar>; 2],
+    ) {
+        let mut curve = self.curve();
+        curve.write().path = Some(SurfacePath::Circle(circle));
+
+        for (vertex, coord) in self.vertices.each_mut_ext().zip_ext(range) {
+            let mut vertex = vertex.write();
+            vertex.position = Some(Point::from([coord.into()]));
+            vertex.curve = curve.clone();
+        }
+
+        self.global_form.write().curve = curve.read().global_form.clone();
+    }
+
     fn update_as_line_segment_from_points(
         &mut self,
         surface: Partial<Surface>,
@@ -22,7 +22,7 @@
 //! [`Surface`]: crate::objects::Surface
 //! [#1021]: https://github.com/hannobraun/Fornjot/issues/1021
 
-use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+use fj_math::{Circle, Ellipse, Line, Point, Scalar, Transform, Vector};
 
 /// A path through surface (2D) space
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -30,6 +30,9 @@ pub enum SurfacePath {
     /// A circle
     Circle(Circle<2>),
 
+    /// An ellipse
+    Ellipse(Ellipse<2>),
+
     /// A line
     Line(Line<2>),
 }
@@ -54,6 +57,7 @@ impl SurfacePath {
     ) -> Point<2> {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
         }
     }
@@ -65,6 +69,9 @@ pub enum GlobalPath {
     /// A circle
     Circle(Circle<3>),
 
+    /// An ellipse
+    Ellipse(Ellipse<3>),
+
     /// A line
     Line(Line<3>),
 }
@@ -110,6 +117,7 @@ impl GlobalPath {
     pub fn origin(&self) -> Point<3> {
         match self {
             Self::Circle(circle) => circle.center() + circle.a(),
+            Self::Ellipse(ellipse) => ellipse.center() + ellipse.a(),
             Self::Line(line) => line.origin(),
         }
     }
@@ -121,6 +129,7 @@ impl GlobalPath {
     ) -> Point<3> {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
         }
     }
@@ -132,17 +141,34 @@ impl GlobalPath {
     ) -> Vector<3> {
         match self {
             Self::Circle(circle) => circle.vector_from_circle_coords(vector),
+            Self::Ellipse(ellipse) => {
+                ellipse.vector_from_ellipse_coords(vector)
+            }
             Self::Line(line) => line.vector_from_line_coords(vector),
         }
     }
 
     /// Transform the path
+    ///
+    /// A circle becomes an ellipse, if the transform doesn't preserve its
+    /// shape, as is the case for non-uniform scaling.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the path is a circle or an ellipse, and the transform is
+    /// singular. See [`Transform::transform_ellipse`].
     #[must_use]
     pub fn transform(self, transform: &Transform) -> Self {
         match self {
-            Self::Circle(curve) => {
+            Self::Circle(curve) if transform.is_conformal() => {
                 Self::Circle(transform.transform_circle(&curve))
             }
+            Self::Circle(curve) => Self::Ellipse(
+                transform.transform_ellipse(&Ellipse::from(curve)),
+            ),
+            Self::Ellipse(curve) => {
+                Self::Ellipse(transform.transform_ellipse(&curve))
+            }
             Self::Line(curve) => Self::Line(transform.transform_line(&curve)),
         }
     }
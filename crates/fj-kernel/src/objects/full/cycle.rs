@@ -1,6 +1,6 @@
 use std::slice;
 
-use fj_interop::ext::SliceExt;
+use fj_interop::ext::ArrayExt;
 use fj_math::{Scalar, Winding};
 
 use crate::{
@@ -58,31 +58,55 @@ impl Cycle {
     /// two possible windings, depending on the direction you look at the
     /// surface that the cycle is defined on from.
     pub fn winding(&self) -> Winding {
-        // The cycle could be made up of one or two circles. If that is the
-        // case, the winding of the cycle is determined by the winding of the
-        // first circle.
-        if self.half_edges.len() < 3 {
-            let first = self
-                .half_edges()
-                .next()
-                .expect("Invalid cycle: expected at least one half-edge");
-
-            let [a, b] = first.vertices();
-            let edge_direction_positive = a.position() < b.position();
-
-            let circle = match first.curve().path() {
-                SurfacePath::Circle(circle) => circle,
-                SurfacePath::Line(_) => unreachable!(
-                    "Invalid cycle: less than 3 edges, but not all are circles"
-                ),
-            };
-            let cross_positive = circle.a().cross2d(&circle.b()) > Scalar::ZERO;
-
-            if edge_direction_positive == cross_positive {
+        // The cycle could be made up of circles, or contain arcs of circles
+        // or ellipses. If that is the case, we can't treat it as a polygon, and
+        // need to take the exact shape of the arcs into account.
+        let contains_arcs = self.half_edges().any(|half_edge| {
+            matches!(
+                half_edge.curve().path(),
+                SurfacePath::Circle(_) | SurfacePath::Ellipse(_)
+            )
+        });
+        if contains_arcs {
+            // Compute twice the signed area enclosed by the cycle. For an arc
+            // on a circle with center `c`, going from coordinate `t0` to `t1`,
+            // the contribution to that is `c x (p1 - p0) + (a x b) * (t1 - t0)`,
+            // where `p0`/`p1` are the arc's end points, and `a`/`b` are the
+            // vectors that define the circle. The same is true for ellipses.
+            let mut sum = Scalar::ZERO;
+
+            for half_edge in self.half_edges() {
+                let [a, b] = half_edge
+                    .vertices()
+                    .each_ref_ext()
+                    .map(|vertex| vertex.surface_form().position());
+
+                let [t0, t1] = half_edge
+                    .vertices()
+                    .each_ref_ext()
+                    .map(|vertex| vertex.position().t);
+
+                sum += match half_edge.curve().path() {
+                    SurfacePath::Circle(circle) => {
+                        circle.center().coords.cross2d(&(b - a))
+                            + circle.a().cross2d(&circle.b()) * (t1 - t0)
+                    }
+                    SurfacePath::Ellipse(ellipse) => {
+                        ellipse.center().coords.cross2d(&(b - a))
+                            + ellipse.a().cross2d(&ellipse.b()) * (t1 - t0)
+                    }
+                    SurfacePath::Line(_) => a.coords.cross2d(&b.coords),
+                };
+            }
+
+            if sum > Scalar::ZERO {
                 return Winding::Ccw;
-            } else {
+            }
+            if sum < Scalar::ZERO {
                 return Winding::Cw;
             }
+
+            unreachable!("Encountered invalid cycle: {self:#?}");
         }
 
         // Now that we got the special case out of the way, we can treat the
@@ -91,11 +115,11 @@ impl Cycle {
 
         let mut sum = Scalar::ZERO;
 
-        for [a, b] in self.half_edges.as_slice().array_windows_ext() {
-            let [a, b] = [a, b].map(|half_edge| {
-                let [vertex, _] = half_edge.vertices();
-                vertex.surface_form().position()
-            });
+        for half_edge in self.half_edges() {
+            let [a, b] = half_edge
+                .vertices()
+                .each_ref_ext()
+                .map(|vertex| vertex.surface_form().position());
 
             sum += (b.u - a.u) * (b.v + a.v);
         }
@@ -115,3 +139,39 @@ impl Cycle {
 ///
 /// Returned by [`Cycle::half_edges`].
 pub type HalfEdgesOfCycle<'a> = slice::Iter<'a, Handle<HalfEdge>>;
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Winding;
+
+    use crate::{
+        builder::FaceBuilder,
+        insert::Insert,
+        partial::{PartialFace, PartialObject},
+        services::Services,
+    };
+
+    #[test]
+    fn winding_of_polygon() {
+        let mut services = Services::new();
+
+        // The closing edge of this triangle, from the last point back to the
+        // first, determines its winding. Leaving it out would flip the result.
+        let points = [[0., 10.], [2., 10.], [1., 11.]];
+
+        let mut reversed = points;
+        reversed.reverse();
+
+        for (points, winding) in
+            [(points, Winding::Ccw), (reversed, Winding::Cw)]
+        {
+            let surface = services.objects.surfaces.xy_plane();
+            let face = PartialFace::default()
+                .with_exterior_polygon_from_points(surface, points)
+                .build(&mut services.objects)
+                .insert(&mut services.objects);
+
+            assert_eq!(face.exterior().winding(), winding);
+        }
+    }
+}
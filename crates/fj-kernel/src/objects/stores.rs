@@ -18,7 +18,13 @@ use super::{
 /// happen is simply a case of putting in the required work. See [#1021].
 ///
 /// [#1021]: https://github.com/hannobraun/Fornjot/issues/1021
-#[derive(Debug, Default)]
+///
+/// Like the stores it consists of, a clone of `Objects` refers to the same
+/// objects. This allows for inserting objects from multiple threads. See
+/// [`Service::fork`].
+///
+/// [`Service::fork`]: crate::services::Service::fork
+#[derive(Clone, Debug, Default)]
 pub struct Objects {
     /// Store for [`Curve`]s
     pub curves: Store<Curve>,
@@ -68,7 +74,7 @@ impl Objects {
 }
 
 /// Store for [`Surface`]s
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Surfaces {
     store: Store<Surface>,
 
@@ -22,9 +22,9 @@ use parking_lot::Mutex;
 /// article:
 /// <https://thinkbeforecoding.com/post/2021/12/17/functional-event-sourcing-decider>
 pub struct Service<S: State> {
-    state: S,
+    pub(super) state: S,
     events: Vec<S::Event>,
-    subscribers: Vec<Arc<Mutex<dyn Subscriber<S::Event>>>>,
+    subscribers: Vec<Arc<Mutex<dyn Subscriber<S::Event> + Send>>>,
 }
 
 impl<S: State> Service<S> {
@@ -40,7 +40,7 @@ impl<S: State> Service<S> {
     /// Add a subscriber
     pub fn subscribe(
         &mut self,
-        subscriber: Arc<Mutex<dyn Subscriber<S::Event>>>,
+        subscriber: Arc<Mutex<dyn Subscriber<S::Event> + Send>>,
     ) {
         self.subscribers.push(subscriber);
     }
@@ -83,6 +83,35 @@ impl<S: State> Service<S> {
     }
 }
 
+impl<S: State + Clone> Service<S> {
+    /// Fork the service, to execute commands on another thread
+    ///
+    /// The fork starts out with a clone of the state, and shares the
+    /// subscribers of this service. This only makes sense for state whose
+    /// clones share the underlying data, like [`Objects`], so that changes made
+    /// through the fork are visible through this service too.
+    ///
+    /// The fork logs its own events. Pass it to [`Service::join`], to add them
+    /// to the events of this service.
+    ///
+    /// [`Objects`]: crate::objects::Objects
+    pub fn fork(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            events: Vec::new(),
+            subscribers: self.subscribers.clone(),
+        }
+    }
+
+    /// Add the events of a fork to the events of this service
+    ///
+    /// Events are added in the order in which forks are joined, which doesn't
+    /// depend on which thread finished first.
+    pub fn join(&mut self, fork: Self) {
+        self.events.extend(fork.events);
+    }
+}
+
 impl<S: State> Deref for Service<S> {
     type Target = S;
 
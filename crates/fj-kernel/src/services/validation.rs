@@ -1,4 +1,6 @@
-use std::{collections::BTreeMap, thread};
+use std::{collections::BTreeMap, mem, thread};
+
+use tracing::info_span;
 
 use crate::{
     objects::{BehindHandle, Object},
@@ -6,7 +8,7 @@ use crate::{
     validate::ValidationError,
 };
 
-use super::{objects::ObjectToInsert, State};
+use super::{objects::ObjectToInsert, Service, State};
 
 /// Errors that occurred while validating the objects inserted into the stores
 #[derive(Default)]
@@ -32,12 +34,28 @@ impl Drop for Validation {
     }
 }
 
+impl Service<Validation> {
+    /// Take the validation errors, to handle them
+    ///
+    /// Errors that have been taken are no longer reported, when `Validation`
+    /// is dropped.
+    pub fn take_errors(&mut self) -> Vec<ValidationError> {
+        mem::take(&mut self.state.0)
+            .into_values()
+            .map(|event| event.err)
+            .collect()
+    }
+}
+
 impl State for Validation {
     type Command = ObjectToInsert;
     type Event = ValidationFailed;
 
     fn decide(&self, command: Self::Command, events: &mut Vec<Self::Event>) {
-        if let Err(err) = command.object.validate() {
+        let result =
+            info_span!("validate").in_scope(|| command.object.validate());
+
+        if let Err(err) = result {
             events.push(ValidationFailed {
                 object: command.object.into(),
                 err,
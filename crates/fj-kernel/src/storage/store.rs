@@ -98,6 +98,18 @@ impl<T> Store<T> {
     }
 }
 
+/// Cloning a `Store` doesn't clone the objects in it
+///
+/// The clone refers to the same storage. Objects that are inserted through
+/// either of them are visible through both.
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 impl<T> Default for Store<T> {
     fn default() -> Self {
         Self::new()
@@ -181,4 +193,18 @@ mod tests {
         let objects = store.iter().collect::<Vec<_>>();
         assert_eq!(objects, [a, b]);
     }
+
+    #[test]
+    fn clone_shares_objects() {
+        let mut store = Store::with_block_size(1);
+        let mut clone = store.clone();
+
+        let a: Handle<i32> = store.reserve();
+        let b = clone.reserve();
+        store.insert(a.clone(), 0);
+        clone.insert(b.clone(), 1);
+
+        assert_eq!(store.iter().collect::<Vec<_>>(), [a.clone(), b.clone()]);
+        assert_eq!(clone.iter().collect::<Vec<_>>(), [a, b]);
+    }
 }
@@ -0,0 +1,239 @@
+use crate::{Circle, Point, Scalar, Vector};
+
+/// An n-dimensional ellipse
+///
+/// The dimensionality of the ellipse is defined by the const generic `D`
+/// parameter.
+///
+/// The ellipse is defined by its center and the two vectors `a` and `b`. The
+/// point at ellipse coordinate `t` is `center + a * cos(t) + b * sin(t)`.
+///
+/// Unlike the vectors that define a [`Circle`], `a` and `b` don't need to be of
+/// equal length or perpendicular to each other. They are conjugate
+/// semi-diameters of the ellipse. This way, applying any affine transform to an
+/// ellipse (or a circle) results in an ellipse, without changing its coordinate
+/// system.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Ellipse<const D: usize> {
+    center: Point<D>,
+    a: Vector<D>,
+    b: Vector<D>,
+}
+
+impl<const D: usize> Ellipse<D> {
+    /// Construct an ellipse
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `a` and `b` are parallel, or if either of them is zero.
+    pub fn new(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+
+        let ellipse = Self { center, a, b };
+        assert_ne!(
+            ellipse.minor_radius(),
+            Scalar::ZERO,
+            "`a` and `b` must not be zero or parallel"
+        );
+
+        ellipse
+    }
+
+    /// Access the center point of the ellipse
+    pub fn center(&self) -> Point<D> {
+        self.center
+    }
+
+    /// Access the vector that defines the starting point of the ellipse
+    ///
+    /// The point where this vector points from the ellipse center, is the zero
+    /// coordinate of the ellipse's coordinate system.
+    ///
+    /// Please also refer to [`Self::b`].
+    pub fn a(&self) -> Vector<D> {
+        self.a
+    }
+
+    /// Access the vector that defines the plane of the ellipse
+    ///
+    /// Also defines the direction of the ellipse's coordinate system. The point
+    /// where this vector points from the ellipse center, is at ellipse
+    /// coordinate `PI / 2.`.
+    pub fn b(&self) -> Vector<D> {
+        self.b
+    }
+
+    /// Access the length of the ellipse's semi-major axis
+    pub fn major_radius(&self) -> Scalar {
+        self.radii()[1]
+    }
+
+    /// Access the length of the ellipse's semi-minor axis
+    pub fn minor_radius(&self) -> Scalar {
+        self.radii()[0]
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.b = -self.b;
+        self
+    }
+
+    /// Convert a `D`-dimensional point to ellipse coordinates
+    ///
+    /// Converts the provided point into ellipse coordinates between `0.`
+    /// (inclusive) and `PI * 2.` (exclusive).
+    ///
+    /// Like [`Circle::point_to_circle_coords`], this projects the point onto
+    /// the ellipse before computing the ellipse coordinate. Points that are
+    /// not on the ellipse will not result in an error.
+    pub fn point_to_ellipse_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        let vector = point.into() - self.center;
+
+        // Express `vector` in terms of `a` and `b`, by solving the normal
+        // equations of the (possibly skewed) coordinate system they define.
+        let [aa, ab, bb] = self.gram();
+        let [va, vb] = [vector.dot(&self.a), vector.dot(&self.b)];
+        let det = aa * bb - ab * ab;
+
+        let cos = (va * bb - vb * ab) / det;
+        let sin = (vb * aa - va * ab) / det;
+
+        let atan = Scalar::atan2(sin, cos);
+        let coord = if atan >= Scalar::ZERO {
+            atan
+        } else {
+            atan + Scalar::TAU
+        };
+        Point::from([coord])
+    }
+
+    /// Convert a point in ellipse coordinates into a `D`-dimensional point
+    pub fn point_from_ellipse_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        self.center + self.vector_from_ellipse_coords(point.into().coords)
+    }
+
+    /// Convert a vector in ellipse coordinates into a `D`-dimensional vector
+    pub fn vector_from_ellipse_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let angle = vector.into().t;
+        let (sin, cos) = angle.sin_cos();
+
+        self.a * cos + self.b * sin
+    }
+
+    fn gram(&self) -> [Scalar; 3] {
+        [
+            self.a.dot(&self.a),
+            self.a.dot(&self.b),
+            self.b.dot(&self.b),
+        ]
+    }
+
+    /// The lengths of the semi-axes, minor first
+    ///
+    /// These are the square roots of the eigenvalues of the Gram matrix of `a`
+    /// and `b`.
+    fn radii(&self) -> [Scalar; 2] {
+        let [aa, ab, bb] = self.gram();
+
+        let mean = (aa + bb) / 2.;
+        let deviation = (((aa - bb) / 2.) * ((aa - bb) / 2.) + ab * ab).sqrt();
+
+        [
+            (mean - deviation).max(Scalar::ZERO).sqrt(),
+            (mean + deviation).sqrt(),
+        ]
+    }
+}
+
+impl<const D: usize> From<Circle<D>> for Ellipse<D> {
+    fn from(circle: Circle<D>) -> Self {
+        Self {
+            center: circle.center(),
+            a: circle.a(),
+            b: circle.b(),
+        }
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for Ellipse<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.center.abs_diff_eq(&other.center, epsilon)
+            && self.a.abs_diff_eq(&other.a, epsilon)
+            && self.b.abs_diff_eq(&other.b, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar};
+
+    use super::Ellipse;
+
+    #[test]
+    fn radii() {
+        let axis_aligned = Ellipse::new([0., 0.], [2., 0.], [0., 1.]);
+        assert_eq!(axis_aligned.major_radius(), Scalar::from(2.));
+        assert_eq!(axis_aligned.minor_radius(), Scalar::ONE);
+
+        // A circle of radius 1, sheared along the u-axis.
+        let sheared = Ellipse::new([0., 0.], [1., 0.], [1., 1.]);
+        let golden_ratio = (1. + 5_f64.sqrt()) / 2.;
+        assert_abs_diff_eq!(
+            sheared.major_radius(),
+            Scalar::from(golden_ratio),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            sheared.minor_radius(),
+            Scalar::from(1. / golden_ratio),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn ellipse_coords() {
+        let ellipse = Ellipse::new([1., 2., 3.], [2., 0., 0.], [1., 1., 0.]);
+
+        for coord in [0., FRAC_PI_4, FRAC_PI_2, PI, PI * 1.5] {
+            let point = ellipse.point_from_ellipse_coords([coord]);
+            assert_abs_diff_eq!(
+                ellipse.point_to_ellipse_coords(point),
+                Point::from([coord]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn parallel_vectors() {
+        Ellipse::new([0., 0.], [1., 0.], [2., 0.]);
+    }
+}
@@ -38,6 +38,7 @@
 mod aabb;
 mod circle;
 mod coordinates;
+mod ellipse;
 mod line;
 mod plane;
 mod point;
@@ -52,6 +53,7 @@ pub use self::{
     aabb::Aabb,
     circle::Circle,
     coordinates::{Uv, Xyz, T},
+    ellipse::Ellipse,
     line::Line,
     plane::Plane,
     point::Point,
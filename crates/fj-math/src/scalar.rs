@@ -113,6 +113,16 @@ impl Scalar {
         self.0.max(other.into().0).into()
     }
 
+    /// Compute the minimum of this and another scalar
+    pub fn min(self, other: impl Into<Self>) -> Self {
+        self.0.min(other.into().0).into()
+    }
+
+    /// Compute the square root of the scalar
+    pub fn sqrt(self) -> Self {
+        self.0.sqrt().into()
+    }
+
     /// Compute the largest integer smaller than or equal to this scalar
     pub fn floor(self) -> Self {
         self.0.floor().into()
@@ -139,6 +149,11 @@ impl Scalar {
         (sin.into(), cos.into())
     }
 
+    /// Compute the arcsine
+    pub fn asin(self) -> Self {
+        self.0.asin().into()
+    }
+
     /// Compute the arccosine
     pub fn acos(self) -> Self {
         self.0.acos().into()
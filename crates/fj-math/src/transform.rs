@@ -1,8 +1,8 @@
 use std::ops;
 
-use nalgebra::Perspective3;
+use nalgebra::{Orthographic3, Perspective3};
 
-use crate::{Circle, Line, Scalar};
+use crate::{Circle, Ellipse, Line, Scalar};
 
 use super::{Aabb, Point, Segment, Triangle, Vector};
 
@@ -40,6 +40,54 @@ impl Transform {
         ))
     }
 
+    /// Construct a scaling
+    ///
+    /// Scales by the respective factor along each axis, relative to the origin.
+    /// Negative factors mirror along the respective axis.
+    pub fn scale(factors: impl Into<Vector<3>>) -> Self {
+        let factors = factors.into();
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::Matrix4::new_nonuniform_scaling(&factors.to_na()),
+        ))
+    }
+
+    /// Construct a mirroring
+    ///
+    /// Mirrors across the plane that passes through the origin and is
+    /// perpendicular to `normal`.
+    pub fn mirror(normal: impl Into<Vector<3>>) -> Self {
+        let normal = normal.into().normalize().to_na();
+        let reflection =
+            nalgebra::Matrix3::identity() - normal * normal.transpose() * 2.;
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            reflection.to_homogeneous(),
+        ))
+    }
+
+    /// Compute the determinant of the linear part of the transform
+    ///
+    /// The determinant is negative, if the transform mirrors, which flips the
+    /// orientation of anything it is applied to.
+    pub fn determinant(&self) -> Scalar {
+        Scalar::from(self.linear_part().determinant())
+    }
+
+    /// Indicate whether the transform preserves angles
+    ///
+    /// This is the case for any combination of translation, rotation, mirroring,
+    /// and uniform scaling. Only a transform like that maps circles to circles.
+    pub fn is_conformal(&self) -> bool {
+        let linear = self.linear_part();
+        let gram = linear.transpose() * linear;
+        let scale = gram.trace() / 3.;
+
+        (gram - nalgebra::Matrix3::identity() * scale)
+            .iter()
+            .all(|value| value.abs() <= scale * 1e-12)
+    }
+
     /// Transform the given point
     pub fn transform_point(&self, point: &Point<3>) -> Point<3> {
         Point::from(self.0.transform_point(&point.to_na()))
@@ -80,7 +128,18 @@ impl Transform {
     }
 
     /// Transform the given circle
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the transform is not [conformal](Self::is_conformal), for
+    /// example a non-uniform scaling. The result would not be a circle. Use
+    /// [`Self::transform_ellipse`] instead.
     pub fn transform_circle(&self, circle: &Circle<3>) -> Circle<3> {
+        assert!(
+            self.is_conformal(),
+            "Can't transform circle: Non-uniform scaling is not supported"
+        );
+
         Circle::new(
             self.transform_point(&circle.center()),
             self.transform_vector(&circle.a()),
@@ -88,6 +147,23 @@ impl Transform {
         )
     }
 
+    /// Transform the given ellipse
+    ///
+    /// Unlike [`Self::transform_circle`], this works for any transform. The
+    /// coordinate system of the ellipse is preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the transform is singular, for example a scaling by zero
+    /// along any axis. The ellipse would collapse into a line or a point.
+    pub fn transform_ellipse(&self, ellipse: &Ellipse<3>) -> Ellipse<3> {
+        Ellipse::new(
+            self.transform_point(&ellipse.center()),
+            self.transform_vector(&ellipse.a()),
+            self.transform_vector(&ellipse.b()),
+        )
+    }
+
     /// Inverse transform
     pub fn inverse(&self) -> Self {
         Self(self.0.inverse())
@@ -119,12 +195,88 @@ impl Transform {
         array.map(Scalar::from)
     }
 
+    /// Project transform using an orthographic projection, return data as an
+    /// array
+    ///
+    /// `half_width` is half the width of the visible area. Its height is
+    /// derived from that, using `aspect_ratio`. Used primarily for graphics
+    /// code, like [`Transform::project_to_array`].
+    pub fn project_orthographic_to_array(
+        &self,
+        aspect_ratio: f64,
+        half_width: f64,
+        znear: f64,
+        zfar: f64,
+    ) -> [Scalar; 16] {
+        let half_height = half_width / aspect_ratio;
+        let projection = Orthographic3::new(
+            -half_width,
+            half_width,
+            -half_height,
+            half_height,
+            znear,
+            zfar,
+        );
+
+        let mut array = [0.; 16];
+        array.copy_from_slice(
+            (projection.to_projective() * self.0).matrix().as_slice(),
+        );
+
+        array.map(Scalar::from)
+    }
+
+    /// Interpolate between this transform and another one
+    ///
+    /// Returns `self` for `t == 0.` and `other` for `t == 1.`. The rotational
+    /// parts are interpolated spherically, the translational parts linearly.
+    /// Both transforms are expected to consist only of rotation and
+    /// translation. Any other component is ignored.
+    pub fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let to_isometry = |transform: &Self| {
+            let rotation =
+                nalgebra::UnitQuaternion::from_matrix(&transform.linear_part());
+            let translation = transform.0.matrix().column(3).xyz();
+
+            (rotation, translation)
+        };
+
+        let (rotation_a, translation_a) = to_isometry(self);
+        let (rotation_b, translation_b) = to_isometry(other);
+
+        let rotation = rotation_a.slerp(&rotation_b, t);
+        let translation = translation_a.lerp(&translation_b, t);
+
+        let isometry = nalgebra::Isometry3::from_parts(
+            nalgebra::Translation3::from(translation),
+            rotation,
+        );
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            isometry.to_homogeneous(),
+        ))
+    }
+
     /// Transform the given axis-aligned bounding box
+    ///
+    /// The result contains the whole transformed AABB, which can be larger than
+    /// the original, if the transform includes a rotation.
     pub fn transform_aabb(&self, aabb: &Aabb<3>) -> Aabb<3> {
-        Aabb {
-            min: self.transform_point(&aabb.min),
-            max: self.transform_point(&aabb.max),
-        }
+        let [min, max] = [aabb.min, aabb.max];
+
+        let corners = [
+            [min.x, min.y, min.z],
+            [min.x, min.y, max.z],
+            [min.x, max.y, min.z],
+            [min.x, max.y, max.z],
+            [max.x, min.y, min.z],
+            [max.x, min.y, max.z],
+            [max.x, max.y, min.z],
+            [max.x, max.y, max.z],
+        ]
+        .map(|corner| self.transform_point(&Point::from(corner)));
+
+        Aabb::<3>::from_points(corners)
     }
 
     /// Exposes the data of this Transform as a slice of f64.
@@ -132,6 +284,10 @@ impl Transform {
         self.0.matrix().data.as_slice()
     }
 
+    fn linear_part(&self) -> nalgebra::Matrix3<f64> {
+        self.0.matrix().fixed_resize::<3, 3>(0.)
+    }
+
     /// Extract the rotation component of this transform
     pub fn extract_rotation(&self) -> Self {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -157,7 +313,7 @@ impl ops::Mul<Self> for Transform {
 mod tests {
     use approx::assert_abs_diff_eq;
 
-    use crate::{Line, Point, Scalar, Vector};
+    use crate::{Aabb, Circle, Ellipse, Line, Point, Scalar, Vector};
 
     use super::Transform;
 
@@ -212,4 +368,87 @@ mod tests {
             epsilon = 1e-8,
         );
     }
+
+    #[test]
+    fn mirror() {
+        let mirror = Transform::mirror([1., 1., 0.]);
+
+        assert_abs_diff_eq!(
+            mirror.transform_point(&Point::from([1., 0., 3.])),
+            Point::from([0., -1., 3.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert!(mirror.determinant() < Scalar::ZERO);
+        assert!(mirror.is_conformal());
+    }
+
+    #[test]
+    fn scale() {
+        let uniform = Transform::scale([2., 2., 2.]);
+        let non_uniform = Transform::scale([1., 2., 3.]);
+        let mirroring = Transform::scale([-1., 1., 1.]);
+
+        assert_eq!(uniform.determinant(), Scalar::from(8.));
+        assert_eq!(mirroring.determinant(), Scalar::from(-1.));
+
+        assert!(uniform.is_conformal());
+        assert!(mirroring.is_conformal());
+        assert!(!non_uniform.is_conformal());
+    }
+
+    #[test]
+    #[should_panic]
+    fn transform_circle_non_uniform() {
+        let circle = Circle::from_center_and_radius([0., 0., 0.], 1.);
+        Transform::scale([1., 2., 1.]).transform_circle(&circle);
+    }
+
+    #[test]
+    fn transform_ellipse_non_uniform() {
+        let circle = Circle::from_center_and_radius([0., 0., 0.], 1.);
+        let ellipse = Transform::scale([1., 2., 1.])
+            .transform_ellipse(&Ellipse::from(circle));
+
+        assert_eq!(ellipse.major_radius(), Scalar::from(2.));
+        assert_eq!(ellipse.minor_radius(), Scalar::ONE);
+    }
+
+    #[test]
+    fn transform_aabb() {
+        let aabb = Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([1., 2., 3.]),
+        };
+
+        let transformed = Transform::scale([-1., 1., 1.]).transform_aabb(&aabb);
+
+        assert_eq!(transformed.min, Point::from([-1., 0., 0.]));
+        assert_eq!(transformed.max, Point::from([0., 2., 3.]));
+    }
+
+    #[test]
+    fn interpolate() {
+        let a = Transform::translation([0., 0., -2.]);
+        let b = Transform::translation([2., 0., 0.])
+            * Transform::rotation(Vector::unit_z() * (Scalar::PI / 2.));
+
+        assert_abs_diff_eq!(
+            a.interpolate(&b, 0.).data(),
+            a.data(),
+            epsilon = 1e-8,
+        );
+        assert_abs_diff_eq!(
+            a.interpolate(&b, 1.).data(),
+            b.data(),
+            epsilon = 1e-8,
+        );
+
+        let halfway = Transform::translation([1., 0., -1.])
+            * Transform::rotation(Vector::unit_z() * (Scalar::PI / 4.));
+        assert_abs_diff_eq!(
+            a.interpolate(&b, 0.5).data(),
+            halfway.data(),
+            epsilon = 1e-8,
+        );
+    }
 }
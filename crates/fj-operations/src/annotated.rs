@@ -0,0 +1,134 @@
+use fj_interop::{annotations::Annotation, debug::DebugInfo};
+use fj_kernel::{
+    objects::{FaceSet, Objects},
+    services::Service,
+};
+use fj_math::{Aabb, Point, Transform};
+
+use crate::{cache::BrepCache, pattern, shape_processor::Error, transform};
+
+use super::Shape;
+
+impl Shape for fj::Annotated {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &BrepCache,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        self.shape().compute_brep(objects, cache, debug_info)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        self.shape().bounding_volume()
+    }
+}
+
+/// Collect the annotations of a shape, in the coordinates of the shape
+///
+/// Annotations that are attached to parts of the shape are transformed along
+/// with those parts. Each instance of a pattern carries its own copy of the
+/// annotations of the repeated shape.
+pub(crate) fn annotations(shape: &fj::Shape) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    collect(shape, &Transform::identity(), &mut annotations);
+    annotations
+}
+
+fn collect(
+    shape: &fj::Shape,
+    transform: &Transform,
+    annotations: &mut Vec<Annotation>,
+) {
+    match shape {
+        fj::Shape::Annotated(shape) => {
+            annotations.extend(
+                shape
+                    .annotations()
+                    .iter()
+                    .map(|annotation| convert(annotation).transform(transform)),
+            );
+            collect(shape.shape(), transform, annotations);
+        }
+        fj::Shape::CircularPattern(shape) => {
+            for (_, instance) in pattern::circular_transforms(shape) {
+                collect(shape.shape(), &(*transform * instance), annotations);
+            }
+        }
+        fj::Shape::Group(shape) => {
+            collect(&shape.a, transform, annotations);
+            collect(&shape.b, transform, annotations);
+        }
+        fj::Shape::LinearPattern(shape) => {
+            for (_, instance) in pattern::linear_transforms(shape) {
+                collect(shape.shape(), &(*transform * instance), annotations);
+            }
+        }
+        fj::Shape::Referenced(shape) => {
+            collect(shape.shape(), transform, annotations);
+        }
+        fj::Shape::Transform(shape) => {
+            let transform = *transform * transform::make_transform(shape);
+            collect(&shape.shape, &transform, annotations);
+        }
+        fj::Shape::Shape2d(_) | fj::Shape::Sweep(_) => {}
+    }
+}
+
+fn convert(annotation: &fj::Annotation) -> Annotation {
+    match annotation {
+        fj::Annotation::Dimension(dimension) => Annotation::Dimension {
+            name: dimension.name().to_owned(),
+            from: Point::from(dimension.from()),
+            to: Point::from(dimension.to()),
+        },
+        fj::Annotation::Label(label) => Annotation::Label {
+            text: label.text().to_owned(),
+            position: Point::from(label.position()),
+        },
+        fj::Annotation::ReferencePoint(point) => Annotation::ReferencePoint {
+            name: point.name().to_owned(),
+            position: Point::from(point.position()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::annotations::Annotation;
+    use fj_math::Point;
+
+    use super::annotations;
+
+    #[test]
+    fn annotations_follow_transforms() {
+        let square = fj::Sketch::from_points(vec![
+            [0., 0.],
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+        ]);
+        let shape: fj::Shape = square
+            .sweep([0., 0., 1.])
+            .annotate(fj::ReferencePoint::new("corner", [1., 1., 1.]))
+            .translate([1., 0., 0.])
+            .linear_pattern([0., 2., 0.], 2)
+            .into();
+
+        let positions = annotations(&shape)
+            .into_iter()
+            .map(|annotation| match annotation {
+                Annotation::ReferencePoint { position, .. } => position,
+                _ => unreachable!("Unexpected annotation: {annotation:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            positions,
+            [Point::from([2., 1., 1.]), Point::from([2., 3., 1.])],
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Reuse of B-reps and meshes between runs of the shape processor
+//!
+//! See [`Cache`].
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Mutex, MutexGuard},
+};
+
+use fj_interop::{debug::DebugInfo, mesh::Mesh};
+use fj_kernel::{
+    algorithms::approx::Tolerance,
+    objects::{FaceSet, Sketch},
+    services::Services,
+    storage::ObjectId,
+};
+use fj_math::Point;
+
+use crate::shape_processor::Error;
+
+/// The number of objects after which the cache starts over
+///
+/// Objects can't be removed from their stores, so the stores keep growing, as
+/// long as the cache is used. Starting over limits how much memory that takes.
+const MAX_OBJECTS: usize = 1 << 20;
+
+/// Caches B-reps and meshes, to reuse them when a shape is processed again
+///
+/// Models are usually changed one feature at a time. The parts of the shape
+/// that didn't change result in the same B-rep and mesh as before, and don't
+/// need to be computed again.
+#[derive(Default)]
+pub struct Cache {
+    pub(crate) services: Services,
+    pub(crate) breps: BrepCache,
+    meshes: HashMap<(ObjectId, Tolerance), Mesh<Point<3>>>,
+}
+
+impl Cache {
+    /// Construct an empty instance of `Cache`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start over, if the cache has grown too large
+    pub(crate) fn limit_size(&mut self) {
+        if self.services.objects.events().count() > MAX_OBJECTS {
+            *self = Self::new();
+        }
+    }
+
+    /// Triangulate faces, reusing the meshes of faces that are known already
+    ///
+    /// `triangulate` is called with the faces that aren't known, and must
+    /// return their meshes in the same order. Only the meshes of `faces` are
+    /// kept for the next call.
+    pub(crate) fn meshes(
+        &mut self,
+        faces: &FaceSet,
+        tolerance: Tolerance,
+        triangulate: impl FnOnce(&FaceSet) -> Vec<Mesh<Point<3>>>,
+    ) -> Vec<Mesh<Point<3>>> {
+        let unknown = faces
+            .into_iter()
+            .filter(|face| !self.meshes.contains_key(&(face.id(), tolerance)))
+            .cloned()
+            .collect::<FaceSet>();
+        let meshes = triangulate(&unknown);
+        let mut meshes = unknown
+            .into_iter()
+            .map(|face| (face.id(), tolerance))
+            .zip(meshes)
+            .collect::<HashMap<_, _>>();
+
+        for face in faces {
+            let key = (face.id(), tolerance);
+            if let Some(mesh) = self.meshes.remove(&key) {
+                meshes.insert(key, mesh);
+            }
+        }
+        self.meshes = meshes;
+
+        faces
+            .into_iter()
+            .map(|face| self.meshes[&(face.id(), tolerance)].clone())
+            .collect()
+    }
+}
+
+/// Caches the B-reps of shapes
+///
+/// Shapes are recognized by their hash, then compared to the shape that the
+/// B-rep was computed from. The debug info that was collected while computing
+/// a B-rep is cached along with it. The cache can be used from multiple threads
+/// at once.
+#[derive(Default)]
+pub struct BrepCache {
+    faces: Entries<fj::Shape, FaceSet>,
+    sketches: Entries<fj::Shape2d, Sketch>,
+}
+
+impl BrepCache {
+    /// Return the cached B-rep of a 3D shape, or compute and cache it
+    pub(crate) fn faces(
+        &self,
+        shape: &fj::Shape,
+        debug_info: &mut DebugInfo,
+        compute: impl FnOnce(&mut DebugInfo) -> Result<FaceSet, Error>,
+    ) -> Result<FaceSet, Error> {
+        self.faces.get_or_compute(shape, debug_info, compute)
+    }
+
+    /// Return the cached B-rep of a 2D shape, or compute and cache it
+    pub(crate) fn sketch(
+        &self,
+        shape: &fj::Shape2d,
+        debug_info: &mut DebugInfo,
+        compute: impl FnOnce(&mut DebugInfo) -> Result<Sketch, Error>,
+    ) -> Result<Sketch, Error> {
+        self.sketches.get_or_compute(shape, debug_info, compute)
+    }
+}
+
+struct Entries<S, B> {
+    inner: Mutex<HashMap<u64, Vec<Entry<S, B>>>>,
+}
+
+struct Entry<S, B> {
+    shape: S,
+    brep: B,
+    debug_info: DebugInfo,
+}
+
+impl<S, B> Entries<S, B>
+where
+    S: Clone + Hash + PartialEq,
+    B: Clone,
+{
+    fn get_or_compute(
+        &self,
+        shape: &S,
+        debug_info: &mut DebugInfo,
+        compute: impl FnOnce(&mut DebugInfo) -> Result<B, Error>,
+    ) -> Result<B, Error> {
+        let mut hasher = DefaultHasher::new();
+        shape.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let cached = self.lock().get(&hash).and_then(|entries| {
+            entries
+                .iter()
+                .find(|entry| entry.shape == *shape)
+                .map(|entry| (entry.brep.clone(), entry.debug_info.clone()))
+        });
+        if let Some((brep, cached_debug_info)) = cached {
+            debug_info.extend(cached_debug_info);
+            return Ok(brep);
+        }
+
+        // The lock isn't held while computing the B-rep, as that would block
+        // other threads from using the cache. If another thread computes the
+        // same B-rep at the same time, one of them ends up in the cache.
+        let mut computed_debug_info = DebugInfo::new();
+        let brep = compute(&mut computed_debug_info)?;
+        self.lock().entry(hash).or_default().push(Entry {
+            shape: shape.clone(),
+            brep: brep.clone(),
+            debug_info: computed_debug_info.clone(),
+        });
+        debug_info.extend(computed_debug_info);
+
+        Ok(brep)
+    }
+
+    fn lock(&self) -> MutexGuard<HashMap<u64, Vec<Entry<S, B>>>> {
+        // The lock is only poisoned, if computing a B-rep panicked on another
+        // thread. The entries are still intact in that case.
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<S, B> Default for Entries<S, B> {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use fj_interop::debug::DebugInfo;
+    use fj_kernel::objects::FaceSet;
+
+    use super::BrepCache;
+
+    #[test]
+    fn reuse_brep_of_equal_shape() {
+        let cache = BrepCache::default();
+        let computed = Cell::new(0);
+        let mut debug_info = DebugInfo::new();
+        let compute = |_: &mut DebugInfo| {
+            computed.set(computed.get() + 1);
+            Ok(FaceSet::new())
+        };
+
+        let square = |size: f64| -> fj::Shape {
+            fj::Sketch::from_points(vec![
+                [0., 0.],
+                [size, 0.],
+                [size, size],
+                [0., size],
+            ])
+            .into()
+        };
+
+        cache.faces(&square(1.), &mut debug_info, compute).unwrap();
+        cache.faces(&square(1.), &mut debug_info, compute).unwrap();
+        assert_eq!(computed.get(), 1);
+
+        cache.faces(&square(2.), &mut debug_info, compute).unwrap();
+        assert_eq!(computed.get(), 2);
+    }
+}
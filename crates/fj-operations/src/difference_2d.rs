@@ -1,16 +1,15 @@
 use std::ops::Deref;
 
-use fj_interop::{debug::DebugInfo, ext::ArrayExt, mesh::Color};
+use fj_interop::{debug::DebugInfo, mesh::Color};
 use fj_kernel::{
-    algorithms::reverse::Reverse,
-    insert::Insert,
-    iter::ObjectIters,
+    algorithms::boolean::Boolean2d,
     objects::{Objects, Sketch},
-    partial::{Partial, PartialFace, PartialObject},
     services::Service,
 };
 use fj_math::Aabb;
 
+use crate::{cache::BrepCache, parallel, shape_processor::Error};
+
 use super::Shape;
 
 impl Shape for fj::Difference2d {
@@ -19,80 +18,25 @@ impl Shape for fj::Difference2d {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &BrepCache,
         debug_info: &mut DebugInfo,
-    ) -> Self::Brep {
-        // This method assumes that `b` is fully contained within `a`:
-        // https://github.com/hannobraun/Fornjot/issues/92
-
-        let mut faces = Vec::new();
-
-        let mut exteriors = Vec::new();
-        let mut interiors = Vec::new();
-
-        let [a, b] = self
-            .shapes()
-            .each_ref_ext()
-            .map(|shape| shape.compute_brep(objects, debug_info));
-
-        if let Some(face) = a.face_iter().next() {
-            // If there's at least one face to subtract from, we can proceed.
-
-            let surface = face.surface();
-
-            for face in a.face_iter() {
-                assert_eq!(
-                    surface,
-                    face.surface(),
-                    "Trying to subtract faces with different surfaces.",
-                );
-
-                exteriors.push(face.exterior().clone());
-                for cycle in face.interiors() {
-                    interiors.push(Partial::from_full_entry_point(
-                        cycle.clone().reverse(objects),
-                    ));
-                }
-            }
-
-            for face in b.face_iter() {
-                assert_eq!(
-                    surface,
-                    face.surface(),
-                    "Trying to subtract faces with different surfaces.",
-                );
-
-                interiors.push(Partial::from_full_entry_point(
-                    face.exterior().clone().reverse(objects),
-                ));
-            }
-
-            // Faces only support one exterior, while the code here comes from
-            // the time when a face could have multiple exteriors. This was only
-            // a special case, i.e. faces that connected to themselves, and I
-            // have my doubts that this code was ever correct in the first
-            // place.
-            //
-            // Anyway, the following should make sure that at least any problems
-            // this code causes become obvious. I don't know if this can ever
-            // trigger, but better safe than sorry.
-            let exterior = exteriors
-                .pop()
-                .expect("Can't construct face without an exterior");
-            assert!(
-                exteriors.is_empty(),
-                "Can't construct face with multiple exteriors"
-            );
-
-            let face = PartialFace {
-                exterior: Partial::from_full_entry_point(exterior),
-                interiors,
-                color: Some(Color(self.color())),
-            };
-            faces.push(face.build(objects).insert(objects));
+    ) -> Result<Self::Brep, Error> {
+        let [a, b] = self.shapes();
+        let (a, b) = parallel::join(
+            objects,
+            debug_info,
+            |objects, debug_info| a.compute_brep(objects, cache, debug_info),
+            |objects, debug_info| b.compute_brep(objects, cache, debug_info),
+        );
+        let (a, b) = (
+            a.map_err(|err| err.in_child(0))?,
+            b.map_err(|err| err.in_child(1))?,
+        );
+
+        match a.difference(&b, Color(self.color()), objects) {
+            Ok(difference) => Ok(difference.deref().clone()),
+            Err(source) => Err(Error::boolean_2d(source)),
         }
-
-        let difference = Sketch::builder().with_faces(faces).build(objects);
-        difference.deref().clone()
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
@@ -5,6 +5,8 @@ use fj_kernel::{
 };
 use fj_math::Aabb;
 
+use crate::{cache::BrepCache, parallel, shape_processor::Error};
+
 use super::Shape;
 
 impl Shape for fj::Group {
@@ -13,17 +15,30 @@ impl Shape for fj::Group {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &BrepCache,
         debug_info: &mut DebugInfo,
-    ) -> Self::Brep {
+    ) -> Result<Self::Brep, Error> {
         let mut faces = FaceSet::new();
 
-        let a = self.a.compute_brep(objects, debug_info);
-        let b = self.b.compute_brep(objects, debug_info);
+        let (a, b) = parallel::join(
+            objects,
+            debug_info,
+            |objects, debug_info| {
+                self.a.compute_brep(objects, cache, debug_info)
+            },
+            |objects, debug_info| {
+                self.b.compute_brep(objects, cache, debug_info)
+            },
+        );
+        let (a, b) = (
+            a.map_err(|err| err.in_child(0))?,
+            b.map_err(|err| err.in_child(1))?,
+        );
 
         faces.extend(a);
         faces.extend(b);
 
-        faces
+        Ok(faces)
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
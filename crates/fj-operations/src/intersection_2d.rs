@@ -0,0 +1,47 @@
+use std::ops::Deref;
+
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::boolean::Boolean2d,
+    objects::{Objects, Sketch},
+    services::Service,
+};
+use fj_math::Aabb;
+
+use crate::{cache::BrepCache, parallel, shape_processor::Error};
+
+use super::Shape;
+
+impl Shape for fj::Intersection2d {
+    type Brep = Sketch;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &BrepCache,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        let [a, b] = self.shapes();
+        let (a, b) = parallel::join(
+            objects,
+            debug_info,
+            |objects, debug_info| a.compute_brep(objects, cache, debug_info),
+            |objects, debug_info| b.compute_brep(objects, cache, debug_info),
+        );
+        let (a, b) = (
+            a.map_err(|err| err.in_child(0))?,
+            b.map_err(|err| err.in_child(1))?,
+        );
+
+        match a.intersection(&b, Color(self.color()), objects) {
+            Ok(intersection) => Ok(intersection.deref().clone()),
+            Err(source) => Err(Error::boolean_2d(source)),
+        }
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // This is a conservative estimate of the bounding box: The
+        // intersection is never going to be bigger than either of the shapes.
+        self.shapes()[0].bounding_volume()
+    }
+}
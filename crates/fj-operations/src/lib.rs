@@ -21,13 +21,22 @@
 // infrastructure is in flux anyway. Maybe the problem will take care of itself.
 #![allow(clippy::result_large_err)]
 
+pub mod cache;
 pub mod shape_processor;
 
+mod annotated;
 mod difference_2d;
 mod group;
+mod intersection_2d;
+mod mesh_file;
+mod offset_2d;
+mod parallel;
+mod pattern;
+mod referenced;
 mod sketch;
 mod sweep;
 mod transform;
+mod union_2d;
 
 use fj_interop::debug::DebugInfo;
 use fj_kernel::{
@@ -35,6 +44,9 @@ use fj_kernel::{
     services::Service,
 };
 use fj_math::Aabb;
+use tracing::{field, info_span};
+
+use self::{cache::BrepCache, shape_processor::Error};
 
 /// Implemented for all operations from the [`fj`] crate
 pub trait Shape {
@@ -42,11 +54,18 @@ pub trait Shape {
     type Brep;
 
     /// Compute the boundary representation of the shape
+    ///
+    /// Returns an error, if the kernel doesn't support an operation for the
+    /// shapes it is applied to. The error names the shape that failed.
+    ///
+    /// The B-reps of [`fj::Shape`] and [`fj::Shape2d`] are looked up in
+    /// `cache`, and only computed, if they aren't found there.
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &BrepCache,
         debug_info: &mut DebugInfo,
-    ) -> Self::Brep;
+    ) -> Result<Self::Brep, Error>;
 
     /// Access the axis-aligned bounding box of a shape
     ///
@@ -61,30 +80,70 @@ impl Shape for fj::Shape {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &BrepCache,
         debug_info: &mut DebugInfo,
-    ) -> Self::Brep {
-        match self {
-            Self::Shape2d(shape) => {
-                shape.compute_brep(objects, debug_info).faces().clone()
-            }
-            Self::Group(shape) => shape.compute_brep(objects, debug_info),
-            Self::Sweep(shape) => shape
-                .compute_brep(objects, debug_info)
-                .shells()
-                .map(|shell| shell.faces().clone())
-                .reduce(|mut a, b| {
-                    a.extend(b);
-                    a
-                })
-                .unwrap_or_default(),
-            Self::Transform(shape) => shape.compute_brep(objects, debug_info),
-        }
+    ) -> Result<Self::Brep, Error> {
+        let name = match self {
+            Self::Annotated(_) => "Annotated",
+            Self::CircularPattern(_) => "CircularPattern",
+            Self::Shape2d(_) => "Shape2d",
+            Self::Group(_) => "Group",
+            Self::LinearPattern(_) => "LinearPattern",
+            Self::Referenced(_) => "Referenced",
+            Self::Sweep(_) => "Sweep",
+            Self::Transform(_) => "Transform",
+        };
+
+        instrumented(name, objects, |objects, computed| {
+            cache.faces(self, debug_info, |debug_info| {
+                *computed = true;
+
+                match self {
+                    Self::Annotated(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::CircularPattern(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::Shape2d(shape) => Ok(shape
+                        .compute_brep(objects, cache, debug_info)?
+                        .faces()
+                        .clone()),
+                    Self::Group(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::LinearPattern(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::Referenced(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::Sweep(shape) => Ok(shape
+                        .compute_brep(objects, cache, debug_info)?
+                        .shells()
+                        .map(|shell| shell.faces().clone())
+                        .reduce(|mut a, b| {
+                            a.extend(b);
+                            a
+                        })
+                        .unwrap_or_default()),
+                    Self::Transform(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                }
+            })
+        })
+        .map_err(|err| err.within(name))
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
         match self {
+            Self::Annotated(shape) => shape.bounding_volume(),
+            Self::CircularPattern(shape) => shape.bounding_volume(),
             Self::Shape2d(shape) => shape.bounding_volume(),
             Self::Group(shape) => shape.bounding_volume(),
+            Self::LinearPattern(shape) => shape.bounding_volume(),
+            Self::Referenced(shape) => shape.bounding_volume(),
             Self::Sweep(shape) => shape.bounding_volume(),
             Self::Transform(shape) => shape.bounding_volume(),
         }
@@ -97,18 +156,78 @@ impl Shape for fj::Shape2d {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &BrepCache,
         debug_info: &mut DebugInfo,
-    ) -> Self::Brep {
-        match self {
-            Self::Difference(shape) => shape.compute_brep(objects, debug_info),
-            Self::Sketch(shape) => shape.compute_brep(objects, debug_info),
-        }
+    ) -> Result<Self::Brep, Error> {
+        let name = match self {
+            Self::Difference(_) => "Difference",
+            Self::Intersection(_) => "Intersection",
+            Self::Offset(_) => "Offset",
+            Self::Sketch(_) => "Sketch",
+            Self::Union(_) => "Union",
+        };
+
+        instrumented(name, objects, |objects, computed| {
+            cache.sketch(self, debug_info, |debug_info| {
+                *computed = true;
+
+                match self {
+                    Self::Difference(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::Intersection(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::Offset(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::Sketch(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                    Self::Union(shape) => {
+                        shape.compute_brep(objects, cache, debug_info)
+                    }
+                }
+            })
+        })
+        .map_err(|err| err.within(name))
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
         match self {
             Self::Difference(shape) => shape.bounding_volume(),
+            Self::Intersection(shape) => shape.bounding_volume(),
+            Self::Offset(shape) => shape.bounding_volume(),
             Self::Sketch(shape) => shape.bounding_volume(),
+            Self::Union(shape) => shape.bounding_volume(),
         }
     }
 }
+
+/// Compute a B-rep within a span, for profiling
+///
+/// `compute` must set the flag it is passed, if it actually computed the B-rep
+/// instead of taking it from the cache. The span records that, as well as the
+/// number of objects that were inserted.
+fn instrumented<B>(
+    shape: &'static str,
+    objects: &mut Service<Objects>,
+    compute: impl FnOnce(&mut Service<Objects>, &mut bool) -> Result<B, Error>,
+) -> Result<B, Error> {
+    let span = info_span!(
+        "compute_brep",
+        shape,
+        cached = field::Empty,
+        objects = field::Empty,
+    );
+    let _entered = span.enter();
+
+    let inserted_before = objects.events().count();
+    let mut computed = false;
+    let brep = compute(objects, &mut computed);
+
+    span.record("cached", !computed);
+    span.record("objects", objects.events().count() - inserted_before);
+
+    brep
+}
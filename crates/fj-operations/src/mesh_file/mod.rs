@@ -0,0 +1,60 @@
+//! Loading of triangle meshes from STL and 3MF files
+
+mod stl;
+mod threemf;
+
+use std::{fs, io, path::Path};
+
+use fj_math::Point;
+
+/// Load the triangles of the mesh file at the given path
+///
+/// The file type is chosen based on the file extension. The points of the
+/// triangles are converted to millimeters.
+pub(crate) fn load(path: &Path) -> Result<Vec<[Point<3>; 3]>, LoadError> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let bytes = match extension.as_str() {
+        "stl" | "3mf" => fs::read(path)?,
+        _ => return Err(LoadError::UnknownFormat(extension)),
+    };
+
+    let triangles = if extension == "stl" {
+        stl::parse(&bytes)?
+    } else {
+        threemf::parse(&bytes)?
+    };
+
+    Ok(triangles
+        .into_iter()
+        .map(|triangle| triangle.map(Point::from))
+        .collect())
+}
+
+/// Error loading a mesh file
+#[derive(Debug, thiserror::Error)]
+pub enum LoadError {
+    /// I/O error while reading the file
+    #[error("I/O error while reading mesh file")]
+    Io(#[from] io::Error),
+
+    /// The file extension doesn't match a supported file type
+    #[error("Unsupported mesh file extension `{0}` (expected `stl` or `3mf`)")]
+    UnknownFormat(String),
+
+    /// The file is not a valid STL file
+    #[error("Invalid STL file: {0}")]
+    Stl(String),
+
+    /// The 3MF archive could not be read
+    #[error("Error reading 3MF archive")]
+    Zip(#[from] zip::result::ZipError),
+
+    /// The 3MF archive doesn't contain a valid model
+    #[error("Invalid 3MF file: {0}")]
+    ThreeMf(String),
+}
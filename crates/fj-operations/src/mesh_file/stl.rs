@@ -0,0 +1,118 @@
+//! Parsing of binary and ASCII STL files
+
+use super::LoadError;
+
+/// Parse an STL file
+///
+/// STL files don't specify a unit. Their coordinates are taken to be in
+/// millimeters, as is common practice.
+pub(super) fn parse(bytes: &[u8]) -> Result<Vec<[[f64; 3]; 3]>, LoadError> {
+    // Binary files can start with "solid" too, so their size is checked first.
+    // It's fully determined by the number of triangles.
+    if let Some(count) = bytes.get(80..84) {
+        let count =
+            u32::from_le_bytes([count[0], count[1], count[2], count[3]]);
+        if bytes.len() as u64 == 84 + 50 * u64::from(count) {
+            return Ok(parse_binary(&bytes[84..]));
+        }
+    }
+
+    let text = std::str::from_utf8(bytes).map_err(|_| {
+        LoadError::Stl(String::from("File is neither binary nor ASCII STL"))
+    })?;
+    if !text.trim_start().starts_with("solid") {
+        return Err(LoadError::Stl(String::from(
+            "File is neither binary nor ASCII STL",
+        )));
+    }
+
+    parse_ascii(text)
+}
+
+fn parse_binary(records: &[u8]) -> Vec<[[f64; 3]; 3]> {
+    // Each record consists of the normal, the three vertices, and an attribute
+    // byte count. The normal is ignored, as it's implied by the winding.
+    records
+        .chunks_exact(50)
+        .map(|record| {
+            [0, 1, 2].map(|vertex| {
+                [0, 1, 2].map(|component| {
+                    let offset = 12 + vertex * 12 + component * 4;
+                    let bytes = &record[offset..offset + 4];
+                    let value = f32::from_le_bytes([
+                        bytes[0], bytes[1], bytes[2], bytes[3],
+                    ]);
+                    f64::from(value)
+                })
+            })
+        })
+        .collect()
+}
+
+fn parse_ascii(text: &str) -> Result<Vec<[[f64; 3]; 3]>, LoadError> {
+    let mut vertices = Vec::new();
+    let mut tokens = text.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        if token != "vertex" {
+            continue;
+        }
+
+        let mut vertex = [0.; 3];
+        for component in &mut vertex {
+            let token = tokens.next().unwrap_or_default();
+            *component = token.parse().map_err(|_| {
+                LoadError::Stl(format!("Invalid coordinate `{token}`"))
+            })?;
+        }
+
+        vertices.push(vertex);
+    }
+
+    if vertices.len() % 3 != 0 {
+        return Err(LoadError::Stl(String::from(
+            "Number of vertices is not a multiple of three",
+        )));
+    }
+
+    Ok(vertices
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn binary_and_ascii() {
+        let triangle = [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]];
+
+        let mut binary = b"solid looks like ASCII, but isn't".to_vec();
+        binary.resize(80, 0);
+        binary.extend(1u32.to_le_bytes());
+        binary.extend([0.0f32, 0., 1.].iter().flat_map(|v| v.to_le_bytes()));
+        for vertex in triangle {
+            for component in vertex {
+                binary.extend((component as f32).to_le_bytes());
+            }
+        }
+        binary.extend([0, 0]);
+
+        assert_eq!(parse(&binary).unwrap(), [triangle]);
+
+        let ascii = "\
+solid triangle
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1e0 0 0
+      vertex 0 1.0 0
+    endloop
+  endfacet
+endsolid triangle
+";
+        assert_eq!(parse(ascii.as_bytes()).unwrap(), [triangle]);
+    }
+}
@@ -0,0 +1,353 @@
+//! Parsing of 3MF files
+//!
+//! Only the core specification is supported: the meshes and components of the
+//! objects, and the items of the build. Materials and other extensions are
+//! ignored.
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read},
+};
+
+use quick_xml::{events::Event, name::LocalName, Reader};
+use zip::ZipArchive;
+
+use super::LoadError;
+
+/// The relationship type of the 3D model part
+const MODEL_RELATIONSHIP: &str =
+    "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel";
+
+/// The number of nested components after which a cycle is assumed
+const MAX_DEPTH: usize = 32;
+
+/// Parse a 3MF file, converting the coordinates to millimeters
+pub(super) fn parse(bytes: &[u8]) -> Result<Vec<[[f64; 3]; 3]>, LoadError> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+
+    let relationships = read(&mut archive, "_rels/.rels")?;
+    let part = tags(&relationships)?
+        .iter()
+        .find(|tag| {
+            tag.name == "Relationship"
+                && tag.attribute("Type") == Some(MODEL_RELATIONSHIP)
+        })
+        .and_then(|tag| tag.attribute("Target"))
+        .map(|target| target.trim_start_matches('/').to_owned())
+        .unwrap_or_else(|| String::from("3D/3dmodel.model"));
+
+    parse_model(&read(&mut archive, &part)?)
+}
+
+fn read(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<String, LoadError> {
+    let mut file = archive.by_name(name)?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+
+    Ok(content)
+}
+
+/// Parse the 3D model part of a 3MF file
+fn parse_model(xml: &str) -> Result<Vec<[[f64; 3]; 3]>, LoadError> {
+    let mut scale = 1.;
+    let mut objects = HashMap::new();
+    let mut items = Vec::new();
+
+    let mut object = None;
+    let mut vertices = Vec::new();
+
+    let tags = tags(xml)?;
+    for tag in &tags {
+        match (tag.name.as_str(), tag.is_end) {
+            ("model", false) => {
+                scale = match tag.attribute("unit").unwrap_or("millimeter") {
+                    "micron" => 0.001,
+                    "millimeter" => 1.,
+                    "centimeter" => 10.,
+                    "inch" => 25.4,
+                    "foot" => 304.8,
+                    "meter" => 1000.,
+                    unit => {
+                        return Err(LoadError::ThreeMf(format!(
+                            "Unknown unit `{unit}`"
+                        )))
+                    }
+                };
+            }
+            ("object", false) => {
+                let id = required(tag, "id")?;
+                objects.insert(id, Object::default());
+                object = Some(id);
+                vertices.clear();
+            }
+            ("object", true) => object = None,
+            ("vertex", false) => {
+                let [x, y, z] = ["x", "y", "z"].map(|name| number(tag, name));
+                vertices.push([x?, y?, z?]);
+            }
+            ("triangle", false) => {
+                let triangle = ["v1", "v2", "v3"].map(|name| {
+                    let index = required(tag, name)?;
+                    index
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| vertices.get(index))
+                        .copied()
+                        .ok_or_else(|| {
+                            LoadError::ThreeMf(format!(
+                                "Invalid vertex index `{index}`"
+                            ))
+                        })
+                });
+                let [a, b, c] = triangle;
+                current(&mut objects, object)?.triangles.push([a?, b?, c?]);
+            }
+            ("component", false) => {
+                if tag.attribute("path").is_some() {
+                    return Err(LoadError::ThreeMf(String::from(
+                        "Components in other model parts are not supported",
+                    )));
+                }
+
+                let component = Reference::from_tag(tag)?;
+                current(&mut objects, object)?.components.push(component);
+            }
+            ("item", false) => items.push(Reference::from_tag(tag)?),
+            _ => {}
+        }
+    }
+
+    let mut triangles = Vec::new();
+    for item in &items {
+        let place = |point: [f64; 3]| item.transform.apply(point);
+        collect(&objects, item.object, &place, 0, &mut triangles)?;
+    }
+
+    Ok(triangles
+        .into_iter()
+        .map(|triangle| triangle.map(|point| point.map(|c| c * scale)))
+        .collect())
+}
+
+/// Collect the triangles of an object and its components
+fn collect(
+    objects: &HashMap<&str, Object>,
+    id: &str,
+    place: &dyn Fn([f64; 3]) -> [f64; 3],
+    depth: usize,
+    triangles: &mut Vec<[[f64; 3]; 3]>,
+) -> Result<(), LoadError> {
+    if depth > MAX_DEPTH {
+        return Err(LoadError::ThreeMf(String::from(
+            "Components are nested too deeply",
+        )));
+    }
+
+    let object = objects.get(id).ok_or_else(|| {
+        LoadError::ThreeMf(format!("Reference to unknown object `{id}`"))
+    })?;
+
+    triangles
+        .extend(object.triangles.iter().map(|triangle| triangle.map(place)));
+
+    for component in &object.components {
+        let place = |point| place(component.transform.apply(point));
+        collect(objects, component.object, &place, depth + 1, triangles)?;
+    }
+
+    Ok(())
+}
+
+fn current<'r, 'a>(
+    objects: &'r mut HashMap<&'a str, Object<'a>>,
+    object: Option<&'a str>,
+) -> Result<&'r mut Object<'a>, LoadError> {
+    object.and_then(|id| objects.get_mut(id)).ok_or_else(|| {
+        LoadError::ThreeMf(String::from("Mesh data outside of object"))
+    })
+}
+
+#[derive(Default)]
+struct Object<'a> {
+    triangles: Vec<[[f64; 3]; 3]>,
+    components: Vec<Reference<'a>>,
+}
+
+/// A component of an object, or an item of the build
+struct Reference<'a> {
+    object: &'a str,
+    transform: Matrix,
+}
+
+impl<'a> Reference<'a> {
+    fn from_tag(tag: &'a Tag) -> Result<Self, LoadError> {
+        let transform = match tag.attribute("transform") {
+            Some(transform) => Matrix::parse(transform)?,
+            None => Matrix::IDENTITY,
+        };
+
+        Ok(Self {
+            object: required(tag, "objectid")?,
+            transform,
+        })
+    }
+}
+
+/// An affine transform, as its 4x3 matrix in row-major order
+///
+/// 3MF multiplies points as row vectors from the left, so the last row is the
+/// translation.
+struct Matrix([f64; 12]);
+
+impl Matrix {
+    const IDENTITY: Self =
+        Self([1., 0., 0., 0., 1., 0., 0., 0., 1., 0., 0., 0.]);
+
+    fn parse(value: &str) -> Result<Self, LoadError> {
+        let invalid =
+            || LoadError::ThreeMf(format!("Invalid transform `{value}`"));
+
+        let values = value
+            .split_whitespace()
+            .map(|value| value.parse().map_err(|_| invalid()))
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        let values = values.try_into().map_err(|_| invalid())?;
+        Ok(Self(values))
+    }
+
+    fn apply(&self, [x, y, z]: [f64; 3]) -> [f64; 3] {
+        let m = &self.0;
+        [0, 1, 2].map(|i| x * m[i] + y * m[3 + i] + z * m[6 + i] + m[9 + i])
+    }
+}
+
+fn required<'a>(tag: &'a Tag, name: &str) -> Result<&'a str, LoadError> {
+    tag.attribute(name).ok_or_else(|| {
+        LoadError::ThreeMf(format!("`{}` without `{name}`", tag.name))
+    })
+}
+
+fn number(tag: &Tag, name: &str) -> Result<f64, LoadError> {
+    let value = required(tag, name)?;
+    value
+        .parse()
+        .map_err(|_| LoadError::ThreeMf(format!("Invalid number `{value}`")))
+}
+
+/// An XML tag
+///
+/// Names are stripped of their namespace prefix.
+struct Tag {
+    name: String,
+    attributes: Vec<(String, String)>,
+    is_end: bool,
+}
+
+impl Tag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Split an XML document into its tags
+///
+/// Self-closing tags are reported as a start tag followed by an end tag.
+fn tags(xml: &str) -> Result<Vec<Tag>, LoadError> {
+    let invalid = |err: quick_xml::Error| {
+        LoadError::ThreeMf(format!("Invalid XML: {err}"))
+    };
+    let local_name =
+        |name: LocalName| String::from_utf8_lossy(name.as_ref()).into_owned();
+
+    let mut reader = Reader::from_str(xml);
+    reader.expand_empty_elements(true);
+
+    let mut tags = Vec::new();
+
+    loop {
+        let tag = match reader.read_event().map_err(invalid)? {
+            Event::Start(start) => Tag {
+                name: local_name(start.local_name()),
+                attributes: start
+                    .attributes()
+                    .map(|attribute| {
+                        let attribute =
+                            attribute.map_err(quick_xml::Error::from)?;
+                        let value = attribute.unescape_value()?.into_owned();
+                        Ok((local_name(attribute.key.local_name()), value))
+                    })
+                    .collect::<Result<_, _>>()
+                    .map_err(invalid)?,
+                is_end: false,
+            },
+            Event::End(end) => Tag {
+                name: local_name(end.local_name()),
+                attributes: Vec::new(),
+                is_end: true,
+            },
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        tags.push(tag);
+    }
+
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_model;
+
+    #[test]
+    fn components_and_units() {
+        let model = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="centimeter" xml:lang="en-US"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <!-- A single triangle, used twice -->
+  <resources>
+    <object id="1" type="model">
+      <mesh>
+        <vertices>
+          <vertex x="0" y="0" z="0" />
+          <vertex x="1" y="0" z="0" />
+          <vertex x="0" y="1" z="0" />
+        </vertices>
+        <triangles>
+          <triangle v1="0" v2="1" v3="2" />
+        </triangles>
+      </mesh>
+    </object>
+    <object id="2" type="model">
+      <components>
+        <component objectid="1" />
+        <component objectid="1" transform="1 0 0 0 1 0 0 0 1 0 0 2" />
+      </components>
+    </object>
+  </resources>
+  <build>
+    <item objectid="2" transform="0 1 0 -1 0 0 0 0 1 5 0 0" />
+  </build>
+</model>
+"#;
+
+        // The item rotates by 90 degrees around the z-axis, then translates
+        // along the x-axis.
+        let triangles = parse_model(model).unwrap();
+        assert_eq!(
+            triangles,
+            [
+                [[50., 0., 0.], [50., 10., 0.], [40., 0., 0.]],
+                [[50., 0., 20.], [50., 10., 20.], [40., 0., 20.]],
+            ]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::ops::Deref;
+
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::offset::{Join, Offset2d, MITER_LIMIT},
+    objects::{Objects, Sketch},
+    services::Service,
+};
+use fj_math::{Aabb, Vector};
+
+use crate::{cache::BrepCache, shape_processor::Error};
+
+use super::Shape;
+
+impl Shape for fj::Offset2d {
+    type Brep = Sketch;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &BrepCache,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        let shape = self.shape().compute_brep(objects, cache, debug_info)?;
+
+        let join = match self.join() {
+            fj::Join::Round => Join::Round,
+            fj::Join::Miter => Join::Miter,
+        };
+
+        match shape.offset(self.distance(), join, objects) {
+            Ok(offset) => Ok(offset.deref().clone()),
+            Err(source) => Err(Error::boolean_2d(source)),
+        }
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let aabb = self.shape().bounding_volume();
+
+        // Shrinking the shape can only make it smaller, so the original
+        // bounding volume still fits.
+        if self.distance() <= 0. {
+            return aabb;
+        }
+
+        // The tips of miter joins can extend further than the offset distance.
+        let margin = match self.join() {
+            fj::Join::Round => self.distance(),
+            fj::Join::Miter => self.distance() * MITER_LIMIT,
+        };
+        let margin = Vector::from([margin, margin, 0.]);
+
+        Aabb {
+            min: aabb.min - margin,
+            max: aabb.max + margin,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Parallel computation of B-reps
+//!
+//! Each parallel computation inserts its objects through its own fork of the
+//! objects service. The forks are joined in a fixed order afterwards, so the
+//! result doesn't depend on which computation finished first.
+//!
+//! The computations run within the current span, even if they end up on
+//! another thread.
+
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{objects::Objects, services::Service};
+use rayon::prelude::*;
+use tracing::Span;
+
+/// Run two computations in parallel
+pub(crate) fn join<A, B>(
+    objects: &mut Service<Objects>,
+    debug_info: &mut DebugInfo,
+    a: impl FnOnce(&mut Service<Objects>, &mut DebugInfo) -> A + Send,
+    b: impl FnOnce(&mut Service<Objects>, &mut DebugInfo) -> B + Send,
+) -> (A, B)
+where
+    A: Send,
+    B: Send,
+{
+    let mut fork_a = Fork::new(objects);
+    let mut fork_b = Fork::new(objects);
+
+    let span = Span::current();
+    let (a, b) = rayon::join(
+        || span.in_scope(|| a(&mut fork_a.objects, &mut fork_a.debug_info)),
+        || span.in_scope(|| b(&mut fork_b.objects, &mut fork_b.debug_info)),
+    );
+
+    fork_a.join(objects, debug_info);
+    fork_b.join(objects, debug_info);
+
+    (a, b)
+}
+
+/// Run a computation for each item in parallel
+///
+/// The results are returned in the order of the items.
+pub(crate) fn map<T, R>(
+    items: impl IntoIterator<Item = T>,
+    objects: &mut Service<Objects>,
+    debug_info: &mut DebugInfo,
+    f: impl Fn(T, &mut Service<Objects>, &mut DebugInfo) -> R + Sync,
+) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+{
+    let items = items
+        .into_iter()
+        .map(|item| (item, Fork::new(objects)))
+        .collect::<Vec<_>>();
+
+    let span = Span::current();
+    let results = items
+        .into_par_iter()
+        .map(|(item, mut fork)| {
+            let result = span
+                .in_scope(|| f(item, &mut fork.objects, &mut fork.debug_info));
+            (result, fork)
+        })
+        .collect::<Vec<_>>();
+
+    results
+        .into_iter()
+        .map(|(result, fork)| {
+            fork.join(objects, debug_info);
+            result
+        })
+        .collect()
+}
+
+struct Fork {
+    objects: Service<Objects>,
+    debug_info: DebugInfo,
+}
+
+impl Fork {
+    fn new(objects: &Service<Objects>) -> Self {
+        Self {
+            objects: objects.fork(),
+            debug_info: DebugInfo::new(),
+        }
+    }
+
+    fn join(self, objects: &mut Service<Objects>, debug_info: &mut DebugInfo) {
+        objects.join(self.objects);
+        debug_info.extend(self.debug_info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::DebugInfo;
+    use fj_kernel::{
+        geometry::{path::GlobalPath, surface::SurfaceGeometry},
+        insert::Insert,
+        objects::{BehindHandle, Object, Surface},
+        services::Services,
+    };
+    use fj_math::Vector;
+
+    #[test]
+    fn map_joins_forks_in_order() {
+        let mut services = Services::new();
+        let mut debug_info = DebugInfo::new();
+
+        let surfaces = super::map(
+            0..16,
+            &mut services.objects,
+            &mut debug_info,
+            |i, objects, _| {
+                Surface::new(SurfaceGeometry {
+                    u: GlobalPath::x_axis(),
+                    v: Vector::from([0., 0., f64::from(i) + 1.]),
+                })
+                .insert(objects)
+            },
+        );
+
+        // The objects are available through the original service, and their
+        // insertion was logged in the order of the items.
+        let logged = services
+            .objects
+            .events()
+            .map(|event| Object::<BehindHandle>::from(event.object.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(logged.len(), surfaces.len());
+        for ((i, surface), object) in (0..).zip(&surfaces).zip(logged) {
+            let v = Vector::from([0., 0., f64::from(i) + 1.]);
+            assert_eq!(surface.geometry().v, v);
+            assert_eq!(object.id(), surface.id());
+        }
+    }
+}
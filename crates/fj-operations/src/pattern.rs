@@ -0,0 +1,164 @@
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::transform::TransformObject,
+    insert::Insert,
+    objects::{Face, FaceSet, Objects},
+    services::Service,
+};
+use fj_math::{Aabb, Transform, Vector};
+
+use crate::{cache::BrepCache, parallel, shape_processor::Error};
+
+use super::Shape;
+
+impl Shape for fj::LinearPattern {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &BrepCache,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        let faces = self.shape().compute_brep(objects, cache, debug_info)?;
+
+        let instances = parallel::map(
+            linear_transforms(self),
+            objects,
+            debug_info,
+            |(index, transform), objects, _| {
+                let color = self.instance_color(index);
+                instance(&faces, index, &transform, color, objects)
+            },
+        );
+
+        Ok(instances.into_iter().flatten().collect())
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        bounding_volume(self.shape(), linear_transforms(self))
+    }
+}
+
+impl Shape for fj::CircularPattern {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &BrepCache,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        if !is_valid_axis(self.axis()) {
+            return Err(Error::PatternAxis(self.axis()));
+        }
+
+        let faces = self.shape().compute_brep(objects, cache, debug_info)?;
+
+        let instances = parallel::map(
+            circular_transforms(self),
+            objects,
+            debug_info,
+            |(index, transform), objects, _| {
+                let color = self.instance_color(index);
+                instance(&faces, index, &transform, color, objects)
+            },
+        );
+
+        Ok(instances.into_iter().flatten().collect())
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        bounding_volume(self.shape(), circular_transforms(self))
+    }
+}
+
+pub(crate) fn linear_transforms(
+    pattern: &fj::LinearPattern,
+) -> impl Iterator<Item = (u32, Transform)> {
+    let offset = Vector::from(pattern.offset());
+
+    (0..pattern.count()).map(move |index| {
+        (index, Transform::translation(offset * f64::from(index)))
+    })
+}
+
+pub(crate) fn circular_transforms(
+    pattern: &fj::CircularPattern,
+) -> impl Iterator<Item = (u32, Transform)> {
+    let center = Vector::from(pattern.center());
+
+    // The instances of a pattern with an invalid axis are left in place,
+    // instead of rotating them around an undefined axis. `compute_brep`
+    // rejects such a pattern.
+    let axis = if is_valid_axis(pattern.axis()) {
+        Vector::from(pattern.axis()).normalize()
+    } else {
+        Vector::from([0., 0., 0.])
+    };
+    let angle = pattern.angle();
+
+    (0..pattern.count()).map(move |index| {
+        let angle = angle * f64::from(index);
+
+        let transform = Transform::translation(center)
+            * Transform::rotation(axis * angle.rad())
+            * Transform::translation(-center);
+
+        (index, transform)
+    })
+}
+
+/// Whether `axis` defines a direction that can be rotated around
+pub(crate) fn is_valid_axis(axis: [f64; 3]) -> bool {
+    let magnitude = axis
+        .iter()
+        .map(|component| component * component)
+        .sum::<f64>();
+    magnitude.is_finite() && magnitude > 0.
+}
+
+/// Create an instance of a pattern from the faces of the original shape
+///
+/// The B-rep of the original shape is only computed once, and then transformed
+/// for each instance. The first instance is the original shape itself.
+fn instance(
+    faces: &FaceSet,
+    index: u32,
+    transform: &Transform,
+    color: Option<[u8; 4]>,
+    objects: &mut Service<Objects>,
+) -> FaceSet {
+    let faces = if index == 0 {
+        faces.clone()
+    } else {
+        faces.clone().transform(transform, objects)
+    };
+
+    match color {
+        Some(color) => faces
+            .into_iter()
+            .map(|face| {
+                Face::new(
+                    face.exterior().clone(),
+                    face.interiors().cloned(),
+                    Color(color),
+                )
+                .insert(objects)
+            })
+            .collect(),
+        None => faces,
+    }
+}
+
+fn bounding_volume(
+    shape: &fj::Shape,
+    transforms: impl Iterator<Item = (u32, Transform)>,
+) -> Aabb<3> {
+    let aabb = shape.bounding_volume();
+
+    transforms
+        .map(|(_, transform)| transform.transform_aabb(&aabb))
+        .reduce(|a, b| a.merged(&b))
+        .unwrap_or(aabb)
+}
@@ -0,0 +1,197 @@
+use std::{collections::HashMap, path::Path};
+
+use fj_interop::{
+    debug::DebugInfo,
+    mesh::{Color, Mesh},
+    processed_shape::Reference,
+};
+use fj_kernel::{
+    objects::{FaceSet, Objects},
+    services::Service,
+};
+use fj_math::{Aabb, Point, Scalar, Transform, Triangle};
+
+use crate::{
+    cache::BrepCache, mesh_file, pattern, shape_processor::Error, transform,
+};
+
+use super::Shape;
+
+impl Shape for fj::Referenced {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &BrepCache,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        self.shape().compute_brep(objects, cache, debug_info)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        self.shape().bounding_volume()
+    }
+}
+
+/// Load the reference bodies of a shape, in the coordinates of the shape
+///
+/// Like annotations, reference bodies are transformed along with the parts of
+/// the shape they are attached to. Each file is only loaded once, even if it's
+/// referenced multiple times.
+pub(crate) fn references(shape: &fj::Shape) -> Result<Vec<Reference>, Error> {
+    let mut loader = Loader {
+        files: HashMap::new(),
+        references: Vec::new(),
+    };
+    loader.collect(shape, &Transform::identity())?;
+
+    Ok(loader.references)
+}
+
+struct Loader<'a> {
+    files: HashMap<&'a str, Vec<[Point<3>; 3]>>,
+    references: Vec<Reference>,
+}
+
+impl<'a> Loader<'a> {
+    fn collect(
+        &mut self,
+        shape: &'a fj::Shape,
+        transform: &Transform,
+    ) -> Result<(), Error> {
+        match shape {
+            fj::Shape::Annotated(shape) => {
+                self.collect(shape.shape(), transform)?;
+            }
+            fj::Shape::CircularPattern(shape) => {
+                for (_, instance) in pattern::circular_transforms(shape) {
+                    self.collect(shape.shape(), &(*transform * instance))?;
+                }
+            }
+            fj::Shape::Group(shape) => {
+                self.collect(&shape.a, transform)?;
+                self.collect(&shape.b, transform)?;
+            }
+            fj::Shape::LinearPattern(shape) => {
+                for (_, instance) in pattern::linear_transforms(shape) {
+                    self.collect(shape.shape(), &(*transform * instance))?;
+                }
+            }
+            fj::Shape::Referenced(shape) => {
+                for reference in shape.references() {
+                    let reference = self.load(reference, transform)?;
+                    self.references.push(reference);
+                }
+                self.collect(shape.shape(), transform)?;
+            }
+            fj::Shape::Transform(shape) => {
+                let transform = *transform * transform::make_transform(shape);
+                self.collect(&shape.shape, &transform)?;
+            }
+            fj::Shape::Shape2d(_) | fj::Shape::Sweep(_) => {}
+        }
+
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        reference: &'a fj::Reference,
+        transform: &Transform,
+    ) -> Result<Reference, Error> {
+        let path = reference.path();
+        if !self.files.contains_key(path) {
+            let triangles =
+                mesh_file::load(Path::new(path)).map_err(|source| {
+                    Error::Reference {
+                        path: path.to_owned(),
+                        source,
+                    }
+                })?;
+            self.files.insert(path, triangles);
+        }
+
+        // A mirroring transform would turn the triangles inside out.
+        let is_mirrored = transform.determinant() < Scalar::ZERO;
+        let color = Color(reference.color());
+
+        let mut mesh = Mesh::new();
+        for triangle in &self.files[path] {
+            let [a, b, c] =
+                triangle.map(|point| transform.transform_point(&point));
+            let points = if is_mirrored { [a, c, b] } else { [a, b, c] };
+
+            // Mesh files can contain degenerate triangles, which don't
+            // contribute anything to the shape of the body.
+            if let Ok(triangle) = Triangle::from_points(points) {
+                mesh.push_triangle(triangle, color);
+            }
+        }
+
+        Ok(Reference {
+            mesh,
+            is_exported: reference.is_exported(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use fj::syntax::*;
+    use fj_math::Point;
+
+    use super::references;
+
+    #[test]
+    fn references_follow_transforms() {
+        let path = env::temp_dir().join("fj-operations-reference.stl");
+        fs::write(
+            &path,
+            "solid t\n\
+             facet normal 0 0 1\n\
+             outer loop\n\
+             vertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\n\
+             endloop\n\
+             endfacet\n\
+             endsolid t\n",
+        )
+        .unwrap();
+
+        let square = fj::Sketch::from_points(vec![
+            [0., 0.],
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+        ]);
+        let reference = fj::Reference::from_path(path.to_str().unwrap())
+            .with_color([0, 0, 255, 128])
+            .with_export(true);
+        let shape: fj::Shape = square
+            .sweep([0., 0., 1.])
+            .with_reference(reference)
+            .translate([0., 0., 2.])
+            .linear_pattern([3., 0., 0.], 2)
+            .into();
+
+        let references = references(&shape).unwrap();
+        fs::remove_file(path).unwrap();
+
+        let first_points = references
+            .iter()
+            .map(|reference| {
+                assert!(reference.is_exported);
+
+                let triangle = reference.mesh.triangles().next().unwrap();
+                assert_eq!(triangle.color.0, [0, 0, 255, 128]);
+                triangle.inner.points()[0]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            first_points,
+            [Point::from([0., 0., 2.]), Point::from([3., 0., 2.])]
+        );
+    }
+}
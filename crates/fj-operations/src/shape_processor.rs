@@ -1,28 +1,74 @@
 //! API for processing shapes
 
-use fj_interop::{debug::DebugInfo, processed_shape::ProcessedShape};
+use std::collections::HashMap;
+
+use fj_interop::{
+    debug::DebugInfo,
+    edges::{Edge, EdgeGeometry},
+    mesh::Mesh,
+    processed_shape::{FaceStats, MeshStats, ProcessedShape},
+};
 use fj_kernel::{
     algorithms::{
-        approx::{InvalidTolerance, Tolerance},
-        triangulate::Triangulate,
+        approx::{
+            curve::{self, CurveCache, UnsupportedCurve},
+            Approx, InvalidTolerance, Tolerance,
+        },
+        boolean::Boolean2dError,
+        sweep::SweepError,
+        triangulate::{refine, TooManyTriangles, Triangulate},
+    },
+    geometry::{
+        path::{GlobalPath, SurfacePath},
+        surface::SurfaceGeometry,
     },
-    services::Services,
+    objects::{FaceSet, HalfEdge},
     validate::ValidationError,
 };
-use fj_math::Scalar;
+use fj_math::{Point, Scalar, Segment, Vector};
+use rayon::prelude::*;
+use tracing::info_span;
+
+use crate::{
+    annotated::annotations, cache::Cache, referenced::references, Shape as _,
+};
 
-use crate::Shape as _;
+pub use crate::mesh_file::LoadError;
 
 /// Processes an [`fj::Shape`] into a [`ProcessedShape`]
 pub struct ShapeProcessor {
     /// The tolerance value used for creating the triangle mesh
     pub tolerance: Option<Tolerance>,
+
+    /// The maximum angle between neighboring segments of curved edges
+    ///
+    /// The angle is given in radians. Overrides the angle of `tolerance`.
+    pub angle: Option<Scalar>,
+
+    /// The maximum length of the edges of the triangle mesh
+    ///
+    /// Overrides the maximum edge length of `tolerance`.
+    pub max_edge_length: Option<Scalar>,
+
+    /// The B-reps and meshes from previous calls to [`ShapeProcessor::process`]
+    ///
+    /// Parts of the shape that haven't changed since then are not computed
+    /// again.
+    pub cache: Cache,
 }
 
 impl ShapeProcessor {
     /// Process an [`fj::Shape`] into [`ProcessedShape`]
-    pub fn process(&self, shape: &fj::Shape) -> Result<ProcessedShape, Error> {
+    pub fn process(
+        &mut self,
+        shape: &fj::Shape,
+    ) -> Result<ProcessedShape, Error> {
+        let _span = info_span!("process_shape").entered();
+
         let aabb = shape.bounding_volume();
+        let annotations = annotations(shape);
+        let references =
+            info_span!("load_references").in_scope(|| references(shape))?;
 
         let tolerance = match self.tolerance {
             None => {
@@ -41,20 +87,406 @@ impl ShapeProcessor {
             }
             Some(user_defined_tolerance) => user_defined_tolerance,
         };
+        let tolerance = match self.angle {
+            Some(angle) => {
+                tolerance.with_angle(angle).map_err(Error::Tolerance)?
+            }
+            None => tolerance,
+        };
+        let tolerance = match self.max_edge_length {
+            Some(length) => tolerance
+                .with_max_edge_length(length)
+                .map_err(Error::Tolerance)?,
+            None => tolerance,
+        };
+
+        self.cache.limit_size();
 
-        let mut services = Services::new();
         let mut debug_info = DebugInfo::new();
-        let shape = shape.compute_brep(&mut services.objects, &mut debug_info);
-        let mesh = (&shape, tolerance).triangulate();
+        let shape = shape.compute_brep(
+            &mut self.cache.services.objects,
+            &self.cache.breps,
+            &mut debug_info,
+        );
+
+        // Objects that failed validation must not be reused, so the cache
+        // starts over. The errors are reported to the caller instead.
+        let validation_errors =
+            self.cache.services.validation.lock().take_errors();
+        if !validation_errors.is_empty() {
+            self.cache = Cache::new();
+        }
+
+        let shape = shape?;
+        if let Some(err) = validation_errors.into_iter().next() {
+            return Err(Error::ToShape(err));
+        }
+
+        // Approximating a curve that isn't supported panics, so this needs to
+        // be checked first.
+        for face in &shape {
+            for half_edge in face.all_cycles().flat_map(|c| c.half_edges()) {
+                curve::check_supported(half_edge.curve())?;
+            }
+        }
+        let (mesh, mesh_stats) =
+            triangulate(&shape, tolerance, &mut self.cache)?;
+        let edges =
+            info_span!("collect_edges").in_scope(|| edges(&shape, tolerance));
 
         Ok(ProcessedShape {
             aabb,
             mesh,
+            mesh_stats,
+            edges,
+            annotations,
+            references,
             debug_info,
         })
     }
 }
 
+/// Triangulate the faces of the shape, and measure the result
+fn triangulate(
+    faces: &FaceSet,
+    tolerance: Tolerance,
+    cache: &mut Cache,
+) -> Result<(Mesh<Point<3>>, MeshStats), Error> {
+    let _span =
+        info_span!("triangulate", faces = faces.into_iter().count()).entered();
+
+    // Approximating all faces at once validates the approximation. The curve
+    // approximations are cached, so approximating each face again afterwards
+    // is cheap.
+    let mut curves = CurveCache::new();
+    info_span!("approximate")
+        .in_scope(|| faces.approx_with_cache(tolerance, &mut curves));
+
+    let mut meshes = cache.meshes(faces, tolerance, |faces| {
+        let _span =
+            info_span!("triangulate_faces", faces = faces.into_iter().count())
+                .entered();
+
+        let approx = faces
+            .into_iter()
+            .map(|face| face.approx_with_cache(tolerance, &mut curves))
+            .collect::<Vec<_>>();
+
+        // Triangulating the faces is where most of the time is spent, and they
+        // can be triangulated independently of each other.
+        approx
+            .into_par_iter()
+            .map(|approx| approx.triangulate())
+            .collect()
+    });
+    if let Some(max_edge_length) = tolerance.max_edge_length() {
+        info_span!("refine")
+            .in_scope(|| refine(&mut meshes, max_edge_length))
+            .map_err(Error::Refine)?;
+    }
+
+    let mut mesh = Mesh::new();
+    let mut stats = MeshStats::default();
+
+    for (face, face_mesh) in faces.into_iter().zip(&meshes) {
+        let surface = face.surface().geometry();
+
+        let mut face_stats = FaceStats {
+            triangles: 0,
+            max_deviation: Scalar::ZERO,
+            max_edge_length: Scalar::ZERO,
+        };
+
+        for triangle in face_mesh.triangles() {
+            let [a, b, c] = triangle.inner.points();
+
+            // The triangles of curved faces deviate the most from the face in
+            // the middle of their edges. The centroid is checked for good
+            // measure.
+            let samples = [
+                a + (b - a) / 2.,
+                b + (c - b) / 2.,
+                c + (a - c) / 2.,
+                a + ((b - a) + (c - a)) / 3.,
+            ];
+            for point in samples {
+                face_stats.max_deviation = face_stats
+                    .max_deviation
+                    .max(distance_to_surface(&surface, point));
+            }
+
+            for [p, q] in [[a, b], [b, c], [c, a]] {
+                face_stats.max_edge_length =
+                    face_stats.max_edge_length.max(p.distance_to(&q));
+            }
+
+            face_stats.triangles += 1;
+            mesh.push_triangle(triangle.inner, triangle.color);
+        }
+
+        stats.faces.push(face_stats);
+    }
+
+    Ok((mesh, stats))
+}
+
+/// Compute the distance of a point from a surface
+///
+/// Surfaces are swept from a path along their v-axis. The point is moved along
+/// the v-axis into the plane of the path, and the distance is measured there.
+/// This is exact for planes and right cylinders, which covers all surfaces that
+/// sweeps currently create.
+fn distance_to_surface(surface: &SurfaceGeometry, point: Point<3>) -> Scalar {
+    let into_plane = |center: Point<3>, [a, b]: [Vector<3>; 2]| {
+        let normal = a.cross(&b);
+        let distance = (point - center).dot(&normal) / surface.v.dot(&normal);
+        point - surface.v * distance
+    };
+
+    match surface.u {
+        GlobalPath::Line(line) => {
+            let normal = line.direction().cross(&surface.v).normalize();
+            (point - line.origin()).dot(&normal).abs()
+        }
+        GlobalPath::Circle(circle) => {
+            let point = into_plane(circle.center(), [circle.a(), circle.b()]);
+            let closest = circle
+                .point_from_circle_coords(circle.point_to_circle_coords(point));
+            point.distance_to(&closest)
+        }
+        GlobalPath::Ellipse(ellipse) => {
+            let point =
+                into_plane(ellipse.center(), [ellipse.a(), ellipse.b()]);
+            let closest = ellipse.point_from_ellipse_coords(
+                ellipse.point_to_ellipse_coords(point),
+            );
+            point.distance_to(&closest)
+        }
+    }
+}
+
+/// Collect the edges of the shape, with their exact geometry where available
+///
+/// Each edge is shared by two faces, and its exact geometry might only be known
+/// in the context of one of them. The circular edge of a cylinder, for example,
+/// is a straight line in the coordinates of the cylinder's curved surface.
+///
+/// The faces don't necessarily share edge objects, so edges are deduplicated
+/// based on their approximations.
+fn edges(faces: &FaceSet, tolerance: Tolerance) -> Vec<Edge> {
+    // The approximations of the same edge, as seen from different faces, can
+    // differ by up to the tolerance each.
+    let epsilon = tolerance.inner() * 2.;
+
+    // The edges are indexed by the cells of a grid that their ends are in.
+    // Since the cells are as large as `epsilon`, the ends of the same edge are
+    // in the same or in neighboring cells.
+    let cell = |point: &Point<3>| {
+        point
+            .coords
+            .components
+            .map(|c| (c / epsilon).floor().into_f64() as i64)
+    };
+    let mut cells: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+
+    let mut edges: Vec<Edge> = Vec::new();
+
+    for face in faces {
+        for cycle in face.all_cycles() {
+            for half_edge in cycle.half_edges() {
+                let edge = Edge {
+                    geometry: edge_geometry(half_edge),
+                    points: edge_points(half_edge, tolerance),
+                };
+
+                // The same edge might run in the opposite direction, so either
+                // of its ends can be close to the start of this one.
+                let existing = edge.points.first().and_then(|start| {
+                    neighbors(cell(start))
+                        .filter_map(|neighbor| cells.get(&neighbor))
+                        .flatten()
+                        .copied()
+                        .find(|&i| is_same_edge(&edges[i], &edge, epsilon))
+                });
+
+                match existing {
+                    Some(i) => {
+                        let existing = &mut edges[i];
+                        if existing.geometry.is_none() {
+                            existing.geometry = edge.geometry.map(|geometry| {
+                                if is_reversed(existing, &edge) {
+                                    geometry.reverse()
+                                } else {
+                                    geometry
+                                }
+                            });
+                        }
+                    }
+                    None => {
+                        let mut ends =
+                            [edge.points.first(), edge.points.last()]
+                                .into_iter()
+                                .flatten()
+                                .map(cell)
+                                .collect::<Vec<_>>();
+                        ends.dedup();
+
+                        for end in ends {
+                            cells.entry(end).or_default().push(edges.len());
+                        }
+                        edges.push(edge);
+                    }
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+/// Indicate whether two edges, that are the same, run in opposite directions
+fn is_reversed(a: &Edge, b: &Edge) -> bool {
+    // Compare the points following the start point, as start and end point are
+    // the same for closed edges.
+    let (Some(a_next), Some(b_next), Some(b_previous)) = (
+        a.points.get(1),
+        b.points.get(1),
+        b.points.len().checked_sub(2).and_then(|i| b.points.get(i)),
+    ) else {
+        return false;
+    };
+
+    a_next.distance_to(b_previous) < a_next.distance_to(b_next)
+}
+
+/// Iterate over a cell of the grid that edges are indexed by, and its neighbors
+fn neighbors([x, y, z]: [i64; 3]) -> impl Iterator<Item = [i64; 3]> {
+    let offsets = [-1, 0, 1];
+    offsets.into_iter().flat_map(move |dx| {
+        offsets.into_iter().flat_map(move |dy| {
+            offsets.into_iter().map(move |dz| {
+                [
+                    x.saturating_add(dx),
+                    y.saturating_add(dy),
+                    z.saturating_add(dz),
+                ]
+            })
+        })
+    })
+}
+
+fn is_same_edge(a: &Edge, b: &Edge, epsilon: Scalar) -> bool {
+    let ends = |edge: &Edge| match edge.points.as_slice() {
+        [first, .., last] => Some([*first, *last]),
+        _ => None,
+    };
+    let (Some([a_start, a_end]), Some([b_start, b_end])) = (ends(a), ends(b))
+    else {
+        return false;
+    };
+
+    let is_close = |p: Point<3>, q: Point<3>| p.distance_to(&q) <= epsilon;
+    let ends_match = (is_close(a_start, b_start) && is_close(a_end, b_end))
+        || (is_close(a_start, b_end) && is_close(a_end, b_start));
+
+    // Edges with the same ends can still be different, like the two halves of
+    // a circle.
+    let middle = b.points[b.points.len() / 2];
+    ends_match
+        && a.closest_point(&middle)
+            .map(|closest| is_close(closest, middle))
+            .unwrap_or(false)
+}
+
+fn edge_points(half_edge: &HalfEdge, tolerance: Tolerance) -> Vec<Point<3>> {
+    let mut points = half_edge
+        .approx(tolerance)
+        .points()
+        .into_iter()
+        .map(|point| point.global_form)
+        .collect::<Vec<_>>();
+
+    // The approximation leaves off the last vertex of the edge.
+    let [_, end] = half_edge.vertices();
+    points.push(end.global_form().position());
+
+    points
+}
+
+fn edge_geometry(half_edge: &HalfEdge) -> Option<EdgeGeometry> {
+    let surface = half_edge.surface().geometry();
+    let [start, end] = half_edge.vertices();
+
+    // Surface coordinates map to model coordinates linearly along the v-axis of
+    // the surface. Along the u-axis, that's only the case for planes.
+    let is_plane = matches!(surface.u, GlobalPath::Line(_));
+
+    match (half_edge.curve().path(), surface.u) {
+        (SurfacePath::Line(line), _)
+            if is_plane || line.direction().u == Scalar::ZERO =>
+        {
+            let points =
+                [start, end].map(|vertex| vertex.global_form().position());
+            Some(EdgeGeometry::Segment(Segment::from_points(points)))
+        }
+        (SurfacePath::Line(line), GlobalPath::Circle(circle))
+            if line.direction().v == Scalar::ZERO =>
+        {
+            // A line along the u-axis of a surface that was swept from a
+            // circle, is a copy of that circle.
+            let center = circle.center() + surface.v * line.origin().v;
+            let [start, end] = [start, end].map(|vertex| vertex.position().t);
+            let angle = line.direction().u * (end - start);
+
+            Some(arc(
+                center,
+                circle.radius(),
+                [circle.a(), circle.b()],
+                angle,
+            ))
+        }
+        (SurfacePath::Circle(circle), _) if is_plane => {
+            let center = surface.point_from_surface_coords(circle.center());
+            let [a, b] = [circle.a(), circle.b()]
+                .map(|vector| surface.vector_from_surface_coords(vector));
+
+            // Planes with skewed or scaled axes turn circles into ellipses.
+            let radius = a.magnitude();
+            let epsilon = radius * 1e-9;
+            if (b.magnitude() - radius).abs() > epsilon
+                || a.dot(&b).abs() > radius * epsilon
+            {
+                return None;
+            }
+
+            let [start, end] = [start, end].map(|vertex| vertex.position().t);
+
+            Some(arc(center, radius, [a, b], end - start))
+        }
+        _ => None,
+    }
+}
+
+/// Create the geometry of an arc on the circle that is spanned by `axes`
+///
+/// A positive `angle` runs from the first axis towards the second.
+fn arc(
+    center: Point<3>,
+    radius: Scalar,
+    [a, b]: [Vector<3>; 2],
+    angle: Scalar,
+) -> EdgeGeometry {
+    let normal = a.cross(&b).normalize();
+    let normal = if angle.is_negative() { -normal } else { normal };
+
+    EdgeGeometry::Arc {
+        center,
+        radius,
+        normal,
+        angle: angle.abs(),
+    }
+}
+
 /// A shape processing error
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, thiserror::Error)]
@@ -66,4 +498,274 @@ pub enum Error {
     /// Model has zero size
     #[error("Model has zero size")]
     Extent(#[from] InvalidTolerance),
+
+    /// The angle or maximum edge length of the tolerance is invalid
+    #[error("Invalid tolerance")]
+    Tolerance(#[source] InvalidTolerance),
+
+    /// Failed to load a reference body
+    #[error("Failed to load reference body from `{path}`")]
+    Reference {
+        /// The path of the file that the reference body is loaded from
+        path: String,
+
+        /// The error that occurred while loading the file
+        source: LoadError,
+    },
+
+    /// A 2D operation is not supported for the shapes it is applied to
+    #[error("Failed to compute `{shape}`")]
+    Boolean2d {
+        /// The path to the shape that failed, from the root of the model
+        ///
+        /// Lists the kind of each shape on the way, like
+        /// `Transform > Group[1] > Sweep > Difference`. The index in brackets
+        /// identifies the child of a shape that has more than one.
+        shape: String,
+
+        /// The error reported by the kernel
+        source: Boolean2dError,
+    },
+
+    /// The axis of a circular pattern doesn't define a direction
+    #[error("Invalid axis of `CircularPattern`: {0:?}")]
+    PatternAxis([f64; 3]),
+
+    /// The rotation axis of a transform doesn't define a direction
+    #[error("Invalid rotation axis of `Transform`: {0:?}")]
+    TransformAxis([f64; 3]),
+
+    /// A transform scales a shape by a factor that is zero or not finite
+    #[error("Invalid scale factors of `Transform`: {0:?}")]
+    TransformScale([f64; 3]),
+
+    /// The shape contains a curve that can't be approximated
+    #[error("Failed to approximate shape")]
+    Approx(#[from] UnsupportedCurve),
+
+    /// Refining the mesh to the maximum edge length creates too many triangles
+    #[error("Maximum edge length is too small for the size of the model")]
+    Refine(#[source] TooManyTriangles),
+
+    /// A sweep is not supported for the shape that is swept
+    #[error("Failed to compute `Sweep`")]
+    Sweep(#[source] SweepError),
+}
+
+impl Error {
+    /// Create an error for a 2D operation that failed
+    ///
+    /// The path to the shape is filled in by [`Error::within`], as the error
+    /// is returned through the shapes that contain it.
+    pub(crate) fn boolean_2d(source: Boolean2dError) -> Self {
+        Self::Boolean2d {
+            shape: String::new(),
+            source,
+        }
+    }
+
+    /// Prepend a shape to the path of the shape that failed
+    ///
+    /// Only changes [`Error::Boolean2d`]; all other errors are returned as-is.
+    pub(crate) fn within(self, shape: &str) -> Self {
+        self.map_path(|path| {
+            if path.is_empty() {
+                shape.to_owned()
+            } else if path.starts_with('[') {
+                format!("{shape}{path}")
+            } else {
+                format!("{shape} > {path}")
+            }
+        })
+    }
+
+    /// Record which child of a shape the failed shape is in
+    ///
+    /// Only changes [`Error::Boolean2d`]; all other errors are returned as-is.
+    pub(crate) fn in_child(self, index: usize) -> Self {
+        self.map_path(|path| format!("[{index}] > {path}"))
+    }
+
+    fn map_path(self, f: impl FnOnce(&str) -> String) -> Self {
+        match self {
+            Self::Boolean2d { shape, source } => Self::Boolean2d {
+                shape: f(&shape),
+                source,
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_kernel::algorithms::{approx::Tolerance, boolean::Boolean2dError};
+    use fj_math::Scalar;
+
+    use crate::cache::Cache;
+
+    use super::{Error, ShapeProcessor};
+
+    #[test]
+    fn mesh_stats() {
+        let cylinder: fj::Shape =
+            fj::Sketch::from_circle(fj::Circle::from_radius(1.))
+                .sweep([0., 0., 4.])
+                .into();
+
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+        let mut processor = ShapeProcessor {
+            tolerance: Some(tolerance),
+            angle: None,
+            max_edge_length: None,
+            cache: Cache::new(),
+        };
+
+        let stats = processor.process(&cylinder).unwrap().mesh_stats;
+        assert_eq!(stats.faces.len(), 3);
+        assert!(stats.max_deviation() > Scalar::ZERO);
+        assert!(stats.max_deviation() <= tolerance.inner());
+
+        // The sides of the cylinder are 4 long. Limiting the edge length
+        // requires more triangles, but doesn't change the deviation.
+        processor.max_edge_length = Some(Scalar::from(0.5));
+
+        let refined = processor.process(&cylinder).unwrap().mesh_stats;
+        assert!(refined.max_edge_length() <= Scalar::from(0.5));
+        assert!(refined.triangles() > stats.triangles());
+        assert!(refined.max_deviation() <= tolerance.inner());
+    }
+
+    #[test]
+    fn deduplicate_edges() {
+        let cube: fj::Shape = fj::Sketch::from_points(vec![
+            [0., 0.],
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+        ])
+        .sweep([0., 0., 1.])
+        .into();
+        let cylinder: fj::Shape =
+            fj::Sketch::from_circle(fj::Circle::from_radius(1.))
+                .sweep([0., 0., 4.])
+                .into();
+
+        let mut processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            angle: None,
+            max_edge_length: None,
+            cache: Cache::new(),
+        };
+
+        let edges = processor.process(&cube).unwrap().edges;
+        assert_eq!(edges.len(), 12);
+
+        // The top and bottom circles, and the seam of the side
+        let edges = processor.process(&cylinder).unwrap().edges;
+        assert_eq!(edges.len(), 3);
+        assert!(edges.iter().all(|edge| edge.geometry.is_some()));
+    }
+
+    #[test]
+    fn reuse_unchanged_parts() {
+        let cube = |size: f64| {
+            [[0., 0.], [size, 0.], [size, size], [0., size]]
+                .sketch()
+                .sweep([0., 0., size])
+        };
+        let cylinder = |radius: f64| {
+            fj::Sketch::from_circle(fj::Circle::from_radius(radius))
+                .sweep([0., 0., 1.])
+                .translate([4., 0., 0.])
+        };
+
+        let mut processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            angle: None,
+            max_edge_length: None,
+            cache: Cache::new(),
+        };
+        let inserted = |processor: &mut ShapeProcessor, shape: fj::Group| {
+            let before = processor.cache.services.objects.events().count();
+            let triangles = processor
+                .process(&shape.into())
+                .unwrap()
+                .mesh_stats
+                .triangles();
+            let after = processor.cache.services.objects.events().count();
+
+            (after - before, triangles)
+        };
+
+        let (all, triangles) =
+            inserted(&mut processor, cube(1.).group(&cylinder(1.)));
+        assert!(all > 0);
+
+        // Nothing has changed, so everything is reused.
+        let (none, same_triangles) =
+            inserted(&mut processor, cube(1.).group(&cylinder(1.)));
+        assert_eq!(none, 0);
+        assert_eq!(same_triangles, triangles);
+
+        // Only the cylinder has changed, so only it is computed again.
+        let (some, _) = inserted(&mut processor, cube(1.).group(&cylinder(2.)));
+        assert!(some > 0);
+        assert!(some < all);
+    }
+
+    #[test]
+    fn circular_pattern_with_zero_axis() {
+        let pattern: fj::Shape = [[1., 0.], [2., 0.], [1., 1.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .circular_pattern([0., 0., 0.], 3)
+            .into();
+
+        let mut processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            angle: None,
+            max_edge_length: None,
+            cache: Cache::new(),
+        };
+
+        let result = processor.process(&pattern);
+        assert!(matches!(result, Err(Error::PatternAxis(_))));
+    }
+
+    #[test]
+    fn transform_with_zero_scale() {
+        let scaled: fj::Shape =
+            fj::Sketch::from_circle(fj::Circle::from_radius(1.))
+                .sweep([0., 0., 1.])
+                .scale([1., 0., 1.])
+                .into();
+
+        let mut processor = ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(0.01).unwrap()),
+            angle: None,
+            max_edge_length: None,
+            cache: Cache::new(),
+        };
+
+        let result = processor.process(&scaled);
+        assert!(matches!(result, Err(Error::TransformScale(_))));
+    }
+
+    #[test]
+    fn path_to_failed_2d_operation() {
+        let error = Error::boolean_2d(Boolean2dError::Ellipse)
+            .within("Sketch")
+            .in_child(1)
+            .within("Difference")
+            .within("Sweep")
+            .in_child(0)
+            .within("Group");
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to compute `Group[0] > Sweep > Difference[1] > Sketch`",
+        );
+    }
 }
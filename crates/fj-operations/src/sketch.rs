@@ -13,6 +13,8 @@ use fj_kernel::{
 };
 use fj_math::{Aabb, Point};
 
+use crate::{cache::BrepCache, shape_processor::Error};
+
 use super::Shape;
 
 impl Shape for fj::Sketch {
@@ -21,8 +23,9 @@ impl Shape for fj::Sketch {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        _: &BrepCache,
         _: &mut DebugInfo,
-    ) -> Self::Brep {
+    ) -> Result<Self::Brep, Error> {
         let surface = objects.surfaces.xy_plane();
 
         let face = match self.chain() {
@@ -96,7 +99,7 @@ impl Shape for fj::Sketch {
         };
 
         let sketch = Sketch::builder().with_faces([face]).build(objects);
-        sketch.deref().clone()
+        Ok(sketch.deref().clone())
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
@@ -9,6 +9,8 @@ use fj_kernel::{
 };
 use fj_math::{Aabb, Vector};
 
+use crate::{cache::BrepCache, shape_processor::Error};
+
 use super::Shape;
 
 impl Shape for fj::Sweep {
@@ -17,15 +19,16 @@ impl Shape for fj::Sweep {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &BrepCache,
         debug_info: &mut DebugInfo,
-    ) -> Self::Brep {
-        let sketch = self.shape().compute_brep(objects, debug_info);
+    ) -> Result<Self::Brep, Error> {
+        let sketch = self.shape().compute_brep(objects, cache, debug_info)?;
         let sketch = sketch.insert(objects);
 
         let path = Vector::from(self.path());
 
-        let solid = sketch.sweep(path, objects);
-        solid.deref().clone()
+        let solid = sketch.sweep(path, objects).map_err(Error::Sweep)?;
+        Ok(solid.deref().clone())
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
@@ -6,6 +6,8 @@ use fj_kernel::{
 };
 use fj_math::{Aabb, Transform, Vector};
 
+use crate::{cache::BrepCache, pattern::is_valid_axis, shape_processor::Error};
+
 use super::Shape;
 
 impl Shape for fj::Transform {
@@ -14,11 +16,18 @@ impl Shape for fj::Transform {
     fn compute_brep(
         &self,
         objects: &mut Service<Objects>,
+        cache: &BrepCache,
         debug_info: &mut DebugInfo,
-    ) -> Self::Brep {
-        self.shape
-            .compute_brep(objects, debug_info)
-            .transform(&make_transform(self), objects)
+    ) -> Result<Self::Brep, Error> {
+        if !is_valid_axis(self.axis) {
+            return Err(Error::TransformAxis(self.axis));
+        }
+        if !is_valid_scale(self.scale) {
+            return Err(Error::TransformScale(self.scale));
+        }
+
+        let faces = self.shape.compute_brep(objects, cache, debug_info)?;
+        Ok(faces.transform(&make_transform(self), objects))
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
@@ -26,8 +35,28 @@ impl Shape for fj::Transform {
     }
 }
 
-fn make_transform(transform: &fj::Transform) -> Transform {
-    let axis = Vector::from(transform.axis).normalize();
+pub(crate) fn make_transform(transform: &fj::Transform) -> Transform {
+    // An invalid rotation or scaling is left out, instead of resulting in an
+    // undefined transformation. `compute_brep` rejects such a transform.
+    let rotation = if is_valid_axis(transform.axis) {
+        Vector::from(transform.axis).normalize() * transform.angle.rad()
+    } else {
+        Vector::from([0., 0., 0.])
+    };
+    let scale = if is_valid_scale(transform.scale) {
+        transform.scale
+    } else {
+        [1.; 3]
+    };
+
     Transform::translation(transform.offset)
-        * Transform::rotation(axis * transform.angle.rad())
+        * Transform::rotation(rotation)
+        * Transform::scale(scale)
+}
+
+/// Whether scaling by `factors` results in a shape that still has volume
+fn is_valid_scale(factors: [f64; 3]) -> bool {
+    factors
+        .iter()
+        .all(|factor| factor.is_finite() && *factor != 0.)
 }
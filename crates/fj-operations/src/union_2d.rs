@@ -0,0 +1,46 @@
+use std::ops::Deref;
+
+use fj_interop::{debug::DebugInfo, ext::ArrayExt, mesh::Color};
+use fj_kernel::{
+    algorithms::boolean::Boolean2d,
+    objects::{Objects, Sketch},
+    services::Service,
+};
+use fj_math::Aabb;
+
+use crate::{cache::BrepCache, parallel, shape_processor::Error};
+
+use super::Shape;
+
+impl Shape for fj::Union2d {
+    type Brep = Sketch;
+
+    fn compute_brep(
+        &self,
+        objects: &mut Service<Objects>,
+        cache: &BrepCache,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        let [a, b] = self.shapes();
+        let (a, b) = parallel::join(
+            objects,
+            debug_info,
+            |objects, debug_info| a.compute_brep(objects, cache, debug_info),
+            |objects, debug_info| b.compute_brep(objects, cache, debug_info),
+        );
+        let (a, b) = (
+            a.map_err(|err| err.in_child(0))?,
+            b.map_err(|err| err.in_child(1))?,
+        );
+
+        match a.union(&b, Color(self.color()), objects) {
+            Ok(union) => Ok(union.deref().clone()),
+            Err(source) => Err(Error::boolean_2d(source)),
+        }
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let [a, b] = self.shapes().each_ref_ext().map(Shape::bounding_volume);
+        a.merged(&b)
+    }
+}
@@ -1,5 +1,5 @@
 //! Viewer camera module
-use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
 
 use fj_interop::{mesh::Mesh, processed_shape::ProcessedShape};
 use fj_math::{Aabb, Point, Scalar, Transform, Vector};
@@ -20,6 +20,18 @@ pub struct Camera {
     /// The distance to the far plane
     far_plane: f64,
 
+    /// The distance from the camera to the center of the model
+    ///
+    /// Determines the size of the visible area, when using an orthographic
+    /// projection.
+    view_distance: f64,
+
+    /// The projection that is used to render the model
+    projection: Projection,
+
+    /// The animation towards a new view, if one is currently running
+    animation: Option<Animation>,
+
     /// The rotational part of the transform
     pub rotation: Transform,
 
@@ -33,11 +45,21 @@ impl Camera {
 
     const INITIAL_FIELD_OF_VIEW_IN_X: f64 = FRAC_PI_2; // 90 degrees
 
+    /// The number of frames it takes to animate towards a new view
+    const ANIMATION_FRAMES: f64 = 20.;
+
+    /// How much space to leave around the model, when zooming to fit it
+    const ZOOM_TO_FIT_MARGIN: f64 = 1.1;
+
     /// Returns a new camera aligned for viewing a bounding box
     pub fn new() -> Self {
         Self {
             near_plane: Self::DEFAULT_NEAR_PLANE,
             far_plane: Self::DEFAULT_FAR_PLANE,
+            view_distance: Self::DEFAULT_FAR_PLANE,
+
+            projection: Projection::Perspective,
+            animation: None,
 
             rotation: Transform::identity(),
             translation: Transform::identity(),
@@ -59,6 +81,33 @@ impl Camera {
         Self::INITIAL_FIELD_OF_VIEW_IN_X
     }
 
+    /// Returns the vertical field of view of the camera.
+    pub fn field_of_view_in_y(&self, aspect_ratio: f64) -> f64 {
+        2. * ((self.field_of_view_in_x() / 2.).tan() / aspect_ratio).atan()
+    }
+
+    /// Returns the projection that is used to render the model
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    /// Switch between perspective and orthographic projection
+    pub fn toggle_projection(&mut self) {
+        self.projection = match self.projection {
+            Projection::Perspective => Projection::Orthographic,
+            Projection::Orthographic => Projection::Perspective,
+        };
+    }
+
+    /// Returns half the width of the visible area, in model units
+    ///
+    /// Only relevant for the orthographic projection. The visible area at the
+    /// center of the model is the same as for the perspective projection, so
+    /// switching between the two keeps the model at roughly the same size.
+    pub fn orthographic_half_width(&self) -> f64 {
+        (self.field_of_view_in_x() / 2.).tan() * self.view_distance
+    }
+
     /// Returns the position of the camera in world space.
     pub fn position(&self) -> Point<3> {
         self.camera_to_model()
@@ -71,13 +120,46 @@ impl Camera {
         cursor: NormalizedScreenPosition,
     ) -> Point<3> {
         // Cursor position in camera space.
-        let f = (self.field_of_view_in_x() / 2.).tan() * self.near_plane();
+        let f = match self.projection {
+            Projection::Perspective => {
+                (self.field_of_view_in_x() / 2.).tan() * self.near_plane()
+            }
+            Projection::Orthographic => self.orthographic_half_width(),
+        };
         let cursor = Point::origin()
             + Vector::from([cursor.x * f, cursor.y * f, -self.near_plane()]);
 
         self.camera_to_model().inverse_transform_point(&cursor)
     }
 
+    /// Transform a point in model space to a normalized screen position
+    ///
+    /// This is the inverse of [`Camera::cursor_to_model_space`]. Returns
+    /// `None`, if the point is behind the near plane.
+    pub fn model_to_cursor_space(
+        &self,
+        point: Point<3>,
+    ) -> Option<NormalizedScreenPosition> {
+        let point = self.camera_to_model().transform_point(&point);
+
+        let depth = -point.z;
+        if depth < Scalar::from(self.near_plane()) {
+            return None;
+        }
+
+        let f = match self.projection {
+            Projection::Perspective => {
+                (self.field_of_view_in_x() / 2.).tan() * depth.into_f64()
+            }
+            Projection::Orthographic => self.orthographic_half_width(),
+        };
+
+        Some(NormalizedScreenPosition {
+            x: point.x.into_f64() / f,
+            y: point.y.into_f64() / f,
+        })
+    }
+
     /// Compute the point on the model, that the cursor currently points to.
     pub fn focus_point(
         &self,
@@ -93,10 +175,36 @@ impl Camera {
         cursor: Option<NormalizedScreenPosition>,
         mesh: &Mesh<Point<3>>,
     ) -> Option<FocusPoint> {
+        let pick = self.pick(cursor?, mesh)?;
+        Some(FocusPoint(pick.point))
+    }
+
+    /// Find the point on the model that the cursor points to
+    ///
+    /// Returns `None`, if the cursor doesn't point at the model.
+    pub fn pick(
+        &self,
+        cursor: NormalizedScreenPosition,
+        mesh: &Mesh<Point<3>>,
+    ) -> Option<Pick> {
         // Transform camera and cursor positions to model space.
-        let origin = self.position();
-        let cursor = self.cursor_to_model_space(cursor?);
-        let dir = (cursor - origin).normalize();
+        let cursor = self.cursor_to_model_space(cursor);
+        let (origin, dir) = match self.projection {
+            Projection::Perspective => {
+                let origin = self.position();
+                (origin, (cursor - origin).normalize())
+            }
+            Projection::Orthographic => {
+                // All rays are parallel to the view direction. They start at
+                // the cursor position on the near plane.
+                let dir = self
+                    .camera_to_model()
+                    .inverse()
+                    .transform_vector(&Vector::from([0., 0., -1.]))
+                    .normalize();
+                (cursor, dir)
+            }
+        };
 
         let mut min_t = None;
 
@@ -107,13 +215,18 @@ impl Camera {
                     .cast_local_ray(origin, dir, f64::INFINITY, true);
 
             if let Some(t) = t {
-                if t <= min_t.unwrap_or(t) {
-                    min_t = Some(t);
+                if t <= min_t.map(|(min_t, _)| min_t).unwrap_or(t) {
+                    min_t = Some((t, triangle));
                 }
             }
         }
 
-        Some(FocusPoint(origin + dir * min_t?))
+        let (t, triangle) = min_t?;
+
+        Some(Pick {
+            point: origin + dir * t,
+            normal: triangle.inner.normal(),
+        })
     }
 
     /// Access the transform from camera to model space.
@@ -176,13 +289,115 @@ impl Camera {
         self.translation = translation;
     }
 
+    /// Switch to a standard view, zoomed to fit the model
+    ///
+    /// The camera is animated towards the new view. Call [`Camera::animate`]
+    /// once per frame, to advance the animation.
+    pub fn view_preset(
+        &mut self,
+        preset: ViewPreset,
+        aabb: &Aabb<3>,
+        aspect_ratio: f64,
+    ) {
+        let rotation = preset.rotation();
+        let translation = self.fit_translation(&rotation, aabb, aspect_ratio);
+
+        self.animate_to(rotation, translation);
+    }
+
+    /// Center the model, and zoom so it fits the screen
+    ///
+    /// Keeps the current rotation. Like [`Camera::view_preset`], this is
+    /// animated.
+    pub fn zoom_to_fit(&mut self, aabb: &Aabb<3>, aspect_ratio: f64) {
+        let rotation = self.rotation;
+        let translation = self.fit_translation(&rotation, aabb, aspect_ratio);
+
+        self.animate_to(rotation, translation);
+    }
+
+    /// Advance the animation towards a new view by one frame
+    ///
+    /// Does nothing, if no animation is running.
+    pub fn animate(&mut self) {
+        let animation = match &mut self.animation {
+            Some(animation) => animation,
+            None => return,
+        };
+
+        animation.progress =
+            (animation.progress + 1. / Self::ANIMATION_FRAMES).min(1.);
+
+        // Ease in and out, so the movement starts and stops smoothly.
+        let t = animation.progress;
+        let t = t * t * (3. - 2. * t);
+
+        self.rotation = animation
+            .from_rotation
+            .interpolate(&animation.to_rotation, t);
+        self.translation = animation
+            .from_translation
+            .interpolate(&animation.to_translation, t);
+
+        if animation.progress >= 1. {
+            self.animation = None;
+        }
+    }
+
+    /// Stop the animation towards a new view, if one is running
+    ///
+    /// Call this, if the user moves the camera manually.
+    pub fn stop_animation(&mut self) {
+        self.animation = None;
+    }
+
+    fn animate_to(&mut self, rotation: Transform, translation: Transform) {
+        self.animation = Some(Animation {
+            from_rotation: self.rotation,
+            from_translation: self.translation,
+            to_rotation: rotation,
+            to_translation: translation,
+            progress: 0.,
+        });
+    }
+
+    /// Compute the translation that centers the model, filling the screen
+    fn fit_translation(
+        &self,
+        rotation: &Transform,
+        aabb: &Aabb<3>,
+        aspect_ratio: f64,
+    ) -> Transform {
+        // Fit the bounding sphere of the model into the narrower of the two
+        // fields of view. That works regardless of the rotation.
+        let radius = aabb.size().magnitude().into_f64() / 2.;
+        let field_of_view = self
+            .field_of_view_in_x()
+            .min(self.field_of_view_in_y(aspect_ratio));
+        let distance =
+            radius / (field_of_view / 2.).sin() * Self::ZOOM_TO_FIT_MARGIN;
+
+        let center = rotation.transform_point(&aabb.center());
+
+        Transform::translation([-center.x, -center.y, -center.z - distance])
+    }
+
     /// Update the max and minimum rendering distance for this camera.
     pub fn update_planes(&mut self, aabb: &Aabb<3>) {
         let view_transform = self.camera_to_model();
         let view_direction = Vector::from([0., 0., -1.]);
 
+        let center = view_transform.transform_point(&aabb.center());
+        self.view_distance = center
+            .coords
+            .dot(&view_direction)
+            .into_f64()
+            .max(Self::DEFAULT_NEAR_PLANE);
+
         let mut dist_min = f64::INFINITY;
         let mut dist_max = f64::NEG_INFINITY;
+        let mut depth_min = f64::INFINITY;
+        let mut depth_max = f64::NEG_INFINITY;
 
         for vertex in aabb.vertices() {
             let point = view_transform.transform_point(&vertex);
@@ -203,13 +418,37 @@ impl Camera {
             if dist > dist_max {
                 dist_max = dist;
             }
+
+            // Unlike the distance above, the depth is negative for points
+            // behind the camera.
+            let depth = a.dot(&b).into_f64();
+            depth_min = depth_min.min(depth);
+            depth_max = depth_max.max(depth);
+        }
+
+        if self.projection == Projection::Orthographic {
+            // The projection maps the depth range between the planes to
+            // [-1, 1], like OpenGL does, but wgpu only renders [0, 1]. Place
+            // the planes such that the model ends up in the back half of the
+            // range.
+            //
+            // Without perspective, there's no reason to keep the near plane in
+            // front of the camera. This way, zooming in doesn't clip the model.
+            let margin =
+                ((depth_max - depth_min) * 0.1).max(Self::DEFAULT_NEAR_PLANE);
+
+            self.far_plane = depth_max + margin;
+            self.near_plane = 2. * (depth_min - margin) - self.far_plane;
+
+            return;
         }
 
         self.near_plane = if dist_min > 0. {
-            // Setting `self.near_plane` to `dist_min` should theoretically
-            // work, but results in the front of the model being clipped. I
-            // wasn't able to figure out why, and for the time being, this
-            // factor seems to work well enough.
+            // As above, only the back half of the depth range between the
+            // planes is rendered. With perspective, that half starts at
+            // `2 * far * near / (far + near)`. A near plane at half of
+            // `dist_min` moves that in front of the model, while one at
+            // `dist_min` itself would clip the front of the model.
             dist_min * 0.5
         } else {
             Self::DEFAULT_NEAR_PLANE
@@ -228,9 +467,183 @@ impl Default for Camera {
     }
 }
 
+/// The projection that is used to render the model
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Projection {
+    /// Perspective projection
+    ///
+    /// Parts of the model that are further away from the camera appear
+    /// smaller.
+    Perspective,
+
+    /// Orthographic projection
+    ///
+    /// Parts of the model appear at the same size, regardless of their
+    /// distance from the camera. Useful for checking dimensions.
+    Orthographic,
+}
+
+/// A standard view of the model
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ViewPreset {
+    /// View from the front, looking along the positive y-axis
+    Front,
+
+    /// View from the top, looking along the negative z-axis
+    Top,
+
+    /// View from the right, looking along the negative x-axis
+    Right,
+
+    /// Isometric view from the front right, above the model
+    Isometric,
+}
+
+impl ViewPreset {
+    /// The camera rotation that results in this view
+    ///
+    /// With the exception of [`ViewPreset::Top`], the z-axis points up on the
+    /// screen.
+    pub fn rotation(&self) -> Transform {
+        let tilt_to_front = Transform::rotation(Vector::unit_x() * -FRAC_PI_2);
+
+        match self {
+            Self::Front => tilt_to_front,
+            Self::Top => Transform::identity(),
+            Self::Right => {
+                tilt_to_front
+                    * Transform::rotation(Vector::unit_z() * -FRAC_PI_2)
+            }
+            Self::Isometric => {
+                // The angle between the z-axis and the space diagonal of a
+                // cube.
+                let tilt = 2_f64.sqrt().atan();
+
+                Transform::rotation(Vector::unit_x() * -tilt)
+                    * Transform::rotation(Vector::unit_z() * -FRAC_PI_4)
+            }
+        }
+    }
+}
+
+/// An animation of the camera towards a new view
+#[derive(Debug)]
+struct Animation {
+    from_rotation: Transform,
+    from_translation: Transform,
+    to_rotation: Transform,
+    to_translation: Transform,
+
+    /// The progress of the animation, from `0.` to `1.`
+    progress: f64,
+}
+
+/// A point on the model, that the cursor points to
+///
+/// See [`Camera::pick`].
+#[derive(Clone, Copy, Debug)]
+pub struct Pick {
+    /// The picked point
+    pub point: Point<3>,
+
+    /// The normal of the model's surface at the picked point
+    pub normal: Vector<3>,
+}
+
 /// The point around which camera movement happens.
 ///
 /// This will be the point on the model that the cursor is currently pointing at if such a point exists,
 /// falling back to the center point of the model's bounding volume otherwise.
 #[derive(Clone, Copy)]
 pub struct FocusPoint(pub Point<3>);
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Scalar, Vector};
+
+    use crate::screen::NormalizedScreenPosition;
+
+    use super::{Camera, ViewPreset};
+
+    #[test]
+    fn view_presets() {
+        let assert_maps_to = |preset: ViewPreset, from: Vector<3>, to| {
+            let mapped = preset.rotation().transform_vector(&from.normalize());
+            assert!((mapped - to).magnitude() < Scalar::from(1e-12));
+        };
+
+        // Each view is checked by the direction pointing towards the camera,
+        // and the direction pointing up on the screen.
+        let views = [
+            (ViewPreset::Front, [0., -1., 0.], [0., 0., 1.]),
+            (ViewPreset::Top, [0., 0., 1.], [0., 1., 0.]),
+            (ViewPreset::Right, [1., 0., 0.], [0., 0., 1.]),
+        ];
+
+        for (preset, towards_camera, up) in views {
+            assert_maps_to(preset, towards_camera.into(), Vector::unit_z());
+            assert_maps_to(preset, up.into(), Vector::unit_y());
+        }
+
+        assert_maps_to(
+            ViewPreset::Isometric,
+            Vector::from([1., -1., 1.]),
+            Vector::unit_z(),
+        );
+    }
+
+    #[test]
+    fn zoom_to_fit() {
+        let aabb = Aabb {
+            min: Point::from([1., 1., 1.]),
+            max: Point::from([3., 3., 3.]),
+        };
+
+        let mut camera = Camera::new();
+        camera.view_preset(ViewPreset::Isometric, &aabb, 1.);
+        for _ in 0..Camera::ANIMATION_FRAMES as usize {
+            camera.animate();
+        }
+        assert!(camera.animation.is_none());
+
+        // The center of the model ends up in front of the camera.
+        let center = camera.camera_to_model().transform_point(&aabb.center());
+        assert!(center.x.abs() < Scalar::from(1e-12));
+        assert!(center.y.abs() < Scalar::from(1e-12));
+        assert!(center.z < Scalar::ZERO);
+
+        // All of the model is within the field of view.
+        for vertex in aabb.vertices() {
+            let vertex = camera.camera_to_model().transform_point(&vertex);
+            assert!(vertex.x.abs() < -vertex.z);
+            assert!(vertex.y.abs() < -vertex.z);
+        }
+    }
+
+    #[test]
+    fn model_to_cursor_space() {
+        let aabb = Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        };
+
+        let mut camera = Camera::new();
+        camera.init_planes(&aabb);
+        camera.update_planes(&aabb);
+
+        for _ in 0..2 {
+            let cursor = NormalizedScreenPosition { x: 0.25, y: -0.5 };
+            let point = camera.cursor_to_model_space(cursor);
+
+            let mapped = camera.model_to_cursor_space(point).unwrap();
+            assert!((mapped.x - cursor.x).abs() < 1e-12);
+            assert!((mapped.y - cursor.y).abs() < 1e-12);
+
+            // The camera position is behind the near plane.
+            assert!(camera.model_to_cursor_space(camera.position()).is_none());
+
+            camera.toggle_projection();
+            camera.update_planes(&aabb);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use fj_math::{Aabb, Scalar};
+
 /// High level configuration for rendering the active model
 #[derive(Debug)]
 pub struct DrawConfig {
@@ -9,6 +11,46 @@ pub struct DrawConfig {
 
     /// Toggle for displaying model debug information
     pub draw_debug: bool,
+
+    /// Toggle for displaying the annotations that the model defines
+    pub draw_annotations: bool,
+
+    /// Toggle for displaying the reference bodies that the model defines
+    pub draw_references: bool,
+
+    /// Planes that cut away parts of the model, one for each axis
+    ///
+    /// The planes are perpendicular to the x, y, and z axes, in that order.
+    pub clipping_planes: [ClippingPlane; 3],
+
+    /// Toggle for filling in the surfaces where the clipping planes cut the
+    /// model
+    pub cap_clipping_planes: bool,
+}
+
+impl DrawConfig {
+    /// Indicate whether any of the clipping planes is enabled
+    pub fn is_clipping(&self) -> bool {
+        self.clipping_planes.iter().any(|plane| plane.enabled)
+    }
+
+    /// Move all enabled clipping planes along their axes
+    ///
+    /// `delta` is relative to the size of the model along the respective axis.
+    /// The planes are kept within the model's bounding box.
+    pub fn move_clipping_planes(&mut self, delta: f64, aabb: &Aabb<3>) {
+        for (i, plane) in self.clipping_planes.iter_mut().enumerate() {
+            if !plane.enabled {
+                continue;
+            }
+
+            let [min, max] =
+                [aabb.min, aabb.max].map(|point| point.coords.components[i]);
+            let offset = Scalar::from(plane.offset) + (max - min) * delta;
+
+            plane.offset = offset.max(min).min(max).into_f64();
+        }
+    }
 }
 
 impl Default for DrawConfig {
@@ -17,6 +59,55 @@ impl Default for DrawConfig {
             draw_model: true,
             draw_mesh: false,
             draw_debug: false,
+            draw_annotations: true,
+            draw_references: true,
+            clipping_planes: [ClippingPlane::default(); 3],
+            cap_clipping_planes: true,
         }
     }
 }
+
+/// A plane that cuts away part of the model, revealing its interior
+///
+/// The plane is perpendicular to one of the coordinate axes. See
+/// [`DrawConfig::clipping_planes`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClippingPlane {
+    /// Toggle for cutting away part of the model
+    pub enabled: bool,
+
+    /// The position of the plane along its axis
+    pub offset: f64,
+
+    /// Toggle for which side of the plane is cut away
+    ///
+    /// By default, the part of the model in positive axis direction is cut
+    /// away. If this is set, the part in negative direction is cut away
+    /// instead.
+    pub flipped: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point};
+
+    use super::DrawConfig;
+
+    #[test]
+    fn move_clipping_planes() {
+        let aabb = Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([2., 4., 8.]),
+        };
+
+        let mut config = DrawConfig::default();
+        config.clipping_planes[1].enabled = true;
+        config.clipping_planes[2].enabled = true;
+        config.clipping_planes[2].offset = 7.;
+
+        config.move_clipping_planes(0.25, &aabb);
+
+        let offsets = config.clipping_planes.map(|plane| plane.offset);
+        assert_eq!(offsets, [0., 1., 8.]);
+    }
+}
@@ -7,6 +7,8 @@ pub struct Drawables<'r> {
     pub model: Drawable<'r>,
     pub mesh: Drawable<'r>,
     pub lines: Drawable<'r>,
+    pub annotations: Drawable<'r>,
+    pub references: Drawable<'r>,
 }
 
 impl<'r> Drawables<'r> {
@@ -14,8 +16,18 @@ impl<'r> Drawables<'r> {
         let model = Drawable::new(&geometries.mesh, &pipelines.model);
         let mesh = Drawable::new(&geometries.mesh, &pipelines.mesh);
         let lines = Drawable::new(&geometries.lines, &pipelines.lines);
+        let annotations =
+            Drawable::new(&geometries.annotations, &pipelines.lines);
+        let references =
+            Drawable::new(&geometries.references, &pipelines.model);
 
-        Self { model, mesh, lines }
+        Self {
+            model,
+            mesh,
+            lines,
+            annotations,
+            references,
+        }
     }
 }
 
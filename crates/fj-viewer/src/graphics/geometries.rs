@@ -8,6 +8,8 @@ use super::vertices::{Vertex, Vertices};
 pub struct Geometries {
     pub mesh: Geometry,
     pub lines: Geometry,
+    pub annotations: Geometry,
+    pub references: Geometry,
 }
 
 impl Geometries {
@@ -15,12 +17,33 @@ impl Geometries {
         device: &wgpu::Device,
         mesh: &Vertices,
         debug_info: &Vertices,
+        references: &Vertices,
     ) -> Self {
         let mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
         let lines =
             Geometry::new(device, debug_info.vertices(), debug_info.indices());
+        let annotations = Geometry::new(device, &[], &[]);
+        let references =
+            Geometry::new(device, references.vertices(), references.indices());
 
-        Self { mesh, lines }
+        Self {
+            mesh,
+            lines,
+            annotations,
+            references,
+        }
+    }
+
+    pub fn update_annotations(
+        &mut self,
+        device: &wgpu::Device,
+        annotations: &Vertices,
+    ) {
+        self.annotations = Geometry::new(
+            device,
+            annotations.vertices(),
+            annotations.indices(),
+        );
     }
 }
 
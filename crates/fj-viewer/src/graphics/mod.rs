@@ -11,9 +11,11 @@ mod uniforms;
 mod vertices;
 
 pub use self::{
-    draw_config::DrawConfig,
+    draw_config::{ClippingPlane, DrawConfig},
     renderer::{DrawError, Renderer, RendererInitError},
 };
 
+pub(crate) use self::vertices::Vertices;
+
 pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
 pub const SAMPLE_COUNT: u32 = 4;
@@ -178,8 +178,12 @@ impl Renderer {
             label: None,
         });
 
-        let geometries =
-            Geometries::new(&device, &Vertices::empty(), &Vertices::empty());
+        let geometries = Geometries::new(
+            &device,
+            &Vertices::empty(),
+            &Vertices::empty(),
+            &Vertices::empty(),
+        );
         let pipelines =
             Pipelines::new(&device, &bind_group_layout, color_format);
 
@@ -206,8 +210,20 @@ impl Renderer {
     }
 
     /// Updates the geometry of the model being rendered.
-    pub fn update_geometry(&mut self, mesh: Vertices, lines: Vertices) {
-        self.geometries = Geometries::new(&self.device, &mesh, &lines);
+    pub fn update_geometry(
+        &mut self,
+        mesh: Vertices,
+        lines: Vertices,
+        references: Vertices,
+    ) {
+        self.geometries =
+            Geometries::new(&self.device, &mesh, &lines, &references);
+    }
+
+    /// Updates the annotations that are drawn on top of the model
+    pub fn update_annotations(&mut self, annotations: Vertices) {
+        self.geometries
+            .update_annotations(&self.device, &annotations);
     }
 
     /// Resizes the render surface.
@@ -239,6 +255,11 @@ impl Renderer {
         let uniforms = Uniforms {
             transform: Transform::for_vertices(camera, aspect_ratio),
             transform_normals: Transform::for_normals(camera),
+            clipping_planes: Uniforms::clipping_planes(config),
+            cap_clipping_planes: u32::from(
+                config.cap_clipping_planes && config.is_clipping(),
+            ),
+            _padding: [0; 3],
         };
 
         self.queue.write_buffer(
@@ -319,6 +340,12 @@ impl Renderer {
                 drawables.model.draw(&mut render_pass);
             }
 
+            // Reference bodies can be semi-transparent, so they're drawn after
+            // the model.
+            if config.draw_references {
+                drawables.references.draw(&mut render_pass);
+            }
+
             if self.is_line_drawing_available() {
                 if config.draw_mesh {
                     drawables.mesh.draw(&mut render_pass);
@@ -326,6 +353,7 @@ impl Renderer {
                 if config.draw_debug {
                     drawables.lines.draw(&mut render_pass);
                 }
+                drawables.annotations.draw(&mut render_pass);
             }
 
             gui.draw(&mut render_pass, &clipped_primitives, &screen_descriptor);
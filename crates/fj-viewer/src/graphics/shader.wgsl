@@ -1,6 +1,8 @@
 struct Uniforms {
     transform: mat4x4<f32>,
     transform_normals: mat4x4<f32>,
+    clipping_planes: array<vec4<f32>, 3>,
+    cap_clipping_planes: u32,
 };
 
 @group(0) @binding(0)
@@ -16,6 +18,7 @@ struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) normal: vec3<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) model_position: vec3<f32>,
 };
 
 struct FragmentOutput {
@@ -27,6 +30,7 @@ fn vertex(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.normal = (uniforms.transform_normals * vec4<f32>(in.normal, 0.0)).xyz;
     out.position = uniforms.transform * vec4<f32>(in.position, 1.0);
+    out.model_position = in.position;
     // We use premultiplied alpha blending.
     out.color = vec4<f32>(in.color.rgb * in.color.a, in.color.a);
 
@@ -35,8 +39,34 @@ fn vertex(in: VertexInput) -> VertexOutput {
 
 let pi: f32 = 3.14159265359;
 
+fn is_clipped(position: vec3<f32>) -> bool {
+    let planes = uniforms.clipping_planes;
+
+    return dot(planes[0].xyz, position) > planes[0].w
+        || dot(planes[1].xyz, position) > planes[1].w
+        || dot(planes[2].xyz, position) > planes[2].w;
+}
+
 @fragment
-fn frag_model(in: VertexOutput) -> FragmentOutput {
+fn frag_model(
+    in: VertexOutput,
+    @builtin(front_facing) front_facing: bool
+) -> FragmentOutput {
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
+
+    // Where the model is cut open, we're looking at the back of the faces on
+    // the other side of the cut. Rendering those in a flat color makes the cut
+    // look like a solid surface.
+    if (!front_facing && uniforms.cap_clipping_planes != 0u) {
+        let cap_color = vec3<f32>(0.8, 0.3, 0.3);
+
+        var out: FragmentOutput;
+        out.color = vec4<f32>(cap_color * in.color.a, in.color.a);
+        return out;
+    }
+
     let light = vec3<f32>(0.0, 0.0, -1.0);
 
     let angle = acos(dot(light, -in.normal));
@@ -52,6 +82,10 @@ fn frag_model(in: VertexOutput) -> FragmentOutput {
 
 @fragment
 fn frag_mesh(in: VertexOutput) -> FragmentOutput {
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
+
     var out: FragmentOutput;
     out.color = vec4<f32>(1.0 - in.color.rgb, in.color.a);
     return out;
@@ -59,6 +93,10 @@ fn frag_mesh(in: VertexOutput) -> FragmentOutput {
 
 @fragment
 fn frag_lines(in: VertexOutput) -> FragmentOutput {
+    if (is_clipped(in.model_position)) {
+        discard;
+    }
+
     var out: FragmentOutput;
     out.color = vec4<f32>(in.color.rgb, in.color.a);
     return out;
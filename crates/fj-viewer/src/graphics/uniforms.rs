@@ -1,12 +1,52 @@
 use bytemuck::{Pod, Zeroable};
 
-use super::transform::Transform;
+use super::{
+    draw_config::{ClippingPlane, DrawConfig},
+    transform::Transform,
+};
 
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
 pub struct Uniforms {
     pub transform: Transform,
     pub transform_normals: Transform,
+
+    /// The clipping planes, as normal (`xyz`) and distance from origin (`w`)
+    ///
+    /// A point is clipped, if its projection onto the normal is larger than
+    /// the distance. Disabled planes are all zero, which never clips anything.
+    pub clipping_planes: [[f32; 4]; 3],
+    pub cap_clipping_planes: u32,
+
+    // Uniform buffers need to have a size that is a multiple of 16 bytes.
+    pub _padding: [u32; 3],
+}
+
+impl Uniforms {
+    pub fn clipping_planes(config: &DrawConfig) -> [[f32; 4]; 3] {
+        let mut planes = [[0.; 4]; 3];
+
+        for (i, (uniform, plane)) in
+            planes.iter_mut().zip(config.clipping_planes).enumerate()
+        {
+            let ClippingPlane {
+                enabled,
+                offset,
+                flipped,
+            } = plane;
+
+            if !enabled {
+                continue;
+            }
+
+            let sign = if flipped { -1. } else { 1. };
+
+            uniform[i] = sign;
+            uniform[3] = (offset * f64::from(sign)) as f32;
+        }
+
+        planes
+    }
 }
 
 impl Default for Uniforms {
@@ -14,6 +54,9 @@ impl Default for Uniforms {
         Self {
             transform: Transform::identity(),
             transform_normals: Transform::identity(),
+            clipping_planes: [[0.; 4]; 3],
+            cap_clipping_planes: 0,
+            _padding: [0; 3],
         }
     }
 }
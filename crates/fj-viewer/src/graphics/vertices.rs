@@ -1,10 +1,13 @@
 use bytemuck::{Pod, Zeroable};
 use fj_interop::{
+    annotations::Annotation,
     debug::DebugInfo,
     mesh::{Index, Mesh},
 };
 use fj_math::{Point, Vector};
 
+use crate::measure::Measurement;
+
 #[derive(Debug)]
 pub struct Vertices {
     vertices: Vec<Vertex>,
@@ -70,6 +73,37 @@ impl Vertices {
             color,
         );
     }
+
+    pub fn push_annotation(&mut self, annotation: &Annotation) {
+        let normal = [0.; 3];
+        let green = [0., 0.5, 0., 1.];
+
+        match annotation {
+            Annotation::Dimension { from, to, .. } => {
+                self.push_cross(*from, normal, green);
+                self.push_cross(*to, normal, green);
+                self.push_line([*from, *to], normal, green);
+            }
+            Annotation::Label { position, .. }
+            | Annotation::ReferencePoint { position, .. } => {
+                self.push_cross(*position, normal, green);
+            }
+        }
+    }
+
+    pub fn push_measurement(&mut self, measurement: &Measurement) {
+        let normal = [0.; 3];
+
+        for pick in measurement.picks() {
+            let magenta = [1., 0., 1., 1.];
+            self.push_cross(pick.point, normal, magenta);
+        }
+
+        for &line in measurement.lines() {
+            let blue = [0., 0.3, 1., 1.];
+            self.push_line(line, normal, blue);
+        }
+    }
 }
 
 impl From<&Mesh<fj_math::Point<3>>> for Vertices {
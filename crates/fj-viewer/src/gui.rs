@@ -14,7 +14,7 @@
 //!
 //! <https://github.com/gfx-rs/wgpu/issues/1492>
 
-use std::path::PathBuf;
+use std::{fmt::Write as _, path::PathBuf};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::env::current_dir;
@@ -22,11 +22,12 @@ use std::env::current_dir;
 #[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
 
+use fj_interop::profile::{Profile, Span};
 use fj_math::{Aabb, Scalar};
 
 use crate::{
     graphics::{DrawConfig, DEPTH_FORMAT, SAMPLE_COUNT},
-    StatusReport,
+    MeasureTool, Measurement, NormalizedScreenPosition, StatusReport,
 };
 
 /// The GUI
@@ -79,6 +80,7 @@ impl Gui {
         &self.context
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn update(
         &mut self,
         pixels_per_point: f32,
@@ -86,8 +88,9 @@ impl Gui {
         config: &mut DrawConfig,
         aabb: &Aabb<3>,
         line_drawing_available: bool,
+        measurement: &mut Option<Measurement>,
         state: GuiState,
-    ) -> Option<PathBuf> {
+    ) -> Option<GuiAction> {
         self.context.set_pixels_per_point(pixels_per_point);
         self.context.begin_frame(egui_input);
 
@@ -96,9 +99,54 @@ impl Gui {
             format!("Model bounding box size:\n{x:0.1} {y:0.1} {z:0.1}")
         };
 
+        let mut action = None;
+
         egui::SidePanel::left("fj-left-panel").show(&self.context, |ui| {
             ui.add_space(16.0);
 
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Open model...").clicked() {
+                        action = show_file_dialog().map(GuiAction::OpenModel);
+                    }
+                    if ui
+                        .add_enabled(
+                            state.model_available,
+                            egui::Button::new("Reload model"),
+                        )
+                        .on_hover_text_at_pointer("Reload with F5")
+                        .clicked()
+                    {
+                        action = Some(GuiAction::ReloadModel);
+                    }
+                });
+
+                if !state.recent_models.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label("Recent models:");
+
+                    for path in state.recent_models {
+                        let name = path
+                            .file_name()
+                            .unwrap_or(path.as_os_str())
+                            .to_string_lossy()
+                            .into_owned();
+
+                        if ui
+                            .button(name)
+                            .on_hover_text_at_pointer(
+                                path.display().to_string(),
+                            )
+                            .clicked()
+                        {
+                            action = Some(GuiAction::OpenModel(path.clone()));
+                        }
+                    }
+                }
+            });
+
+            ui.add_space(16.0);
+
             ui.group(|ui| {
                 ui.checkbox(&mut config.draw_model, "Render model")
                     .on_hover_text_at_pointer("Toggle with 1");
@@ -112,12 +160,97 @@ impl Gui {
                     .on_disabled_hover_text(
                         "Rendering device does not have line rendering feature support"
                     );
+                ui.add_enabled(line_drawing_available, egui::Checkbox::new(&mut config.draw_annotations, "Render annotations"))
+                    .on_hover_text_at_pointer("Toggle with 4")
+                    .on_disabled_hover_text(
+                        "Rendering device does not have line rendering feature support"
+                    );
+                ui.checkbox(&mut config.draw_references, "Render reference bodies")
+                    .on_hover_text_at_pointer("Toggle with 5");
                 ui.add_space(16.0);
                 ui.strong(bounding_box_size);
             });
 
             ui.add_space(16.0);
 
+            ui.group(|ui| {
+                ui.label("Clipping planes:");
+
+                for (i, (plane, axis)) in config
+                    .clipping_planes
+                    .iter_mut()
+                    .zip(["X", "Y", "Z"])
+                    .enumerate()
+                {
+                    let [min, max] = [aabb.min, aabb.max]
+                        .map(|point| point.coords.components[i].into_f64());
+
+                    ui.horizontal(|ui| {
+                        if ui.checkbox(&mut plane.enabled, axis).changed()
+                            && plane.enabled
+                        {
+                            plane.offset = (min + max) / 2.;
+                        }
+                        ui.add_enabled(
+                            plane.enabled,
+                            egui::Slider::new(&mut plane.offset, min..=max),
+                        );
+                        ui.add_enabled(
+                            plane.enabled,
+                            egui::Checkbox::new(&mut plane.flipped, "Flip"),
+                        );
+                    });
+                }
+
+                ui.checkbox(
+                    &mut config.cap_clipping_planes,
+                    "Fill in cut surfaces",
+                );
+                ui.label("Move enabled planes with Shift + mouse wheel");
+            });
+
+            ui.add_space(16.0);
+
+            ui.group(|ui| {
+                ui.label("Measure:");
+
+                let mut tool = measurement.as_ref().map(Measurement::tool);
+                ui.horizontal_wrapped(|ui| {
+                    ui.selectable_value(&mut tool, None, "Off");
+                    for t in MeasureTool::ALL {
+                        ui.selectable_value(&mut tool, Some(t), t.name());
+                    }
+                });
+                if tool != measurement.as_ref().map(Measurement::tool) {
+                    *measurement = tool.map(Measurement::new);
+                }
+
+                if let Some(measurement) = measurement {
+                    ui.label(measurement.status());
+                }
+            });
+
+            ui.add_space(16.0);
+
+            if let Some(profile) = state.profile {
+                ui.group(|ui| {
+                    let id = egui::Id::new("fj-profile");
+
+                    egui::CollapsingHeader::new(format!(
+                        "Profile: {:.1} ms",
+                        profile.duration().as_secs_f64() * 1000.
+                    ))
+                    .id_source(id)
+                    .show(ui, |ui| {
+                        for (i, span) in profile.spans.iter().enumerate() {
+                            show_span(ui, span, id.with(i));
+                        }
+                    });
+                });
+
+                ui.add_space(16.0);
+            }
+
             {
                 ui.group(|ui| {
                     ui.checkbox(
@@ -249,8 +382,6 @@ impl Gui {
             })
         });
 
-        let mut new_model_path = None;
-
         if !state.model_available {
             egui::Area::new("ask-model")
                 .anchor(egui::Align2::CENTER_CENTER, [0_f32, -5_f32])
@@ -264,13 +395,45 @@ impl Gui {
                             .button(egui::RichText::new("Pick a model"))
                             .clicked()
                         {
-                            new_model_path = show_file_dialog();
+                            action =
+                                show_file_dialog().map(GuiAction::OpenModel);
                         }
                     })
                 });
         }
 
-        new_model_path
+        action
+    }
+
+    /// Draw text labels at the given screen positions
+    ///
+    /// Must be called after [`Gui::update`], as part of the same frame.
+    pub(crate) fn draw_labels(
+        &self,
+        labels: &[(NormalizedScreenPosition, String)],
+    ) {
+        let screen = self.context.input().screen_rect();
+        let aspect_ratio = screen.width() / screen.height();
+
+        // Don't draw labels on top of the side panel.
+        let painter = self
+            .context
+            .layer_painter(egui::LayerId::background())
+            .with_clip_rect(self.context.available_rect());
+
+        for (position, text) in labels {
+            let x = (position.x as f32 + 1.) / 2. * screen.width();
+            let y =
+                (1. - position.y as f32 * aspect_ratio) / 2. * screen.height();
+
+            painter.text(
+                screen.min + egui::vec2(x, y),
+                egui::Align2::LEFT_BOTTOM,
+                text,
+                egui::FontId::proportional(14.),
+                egui::Color32::from_rgb(0, 100, 0),
+            );
+        }
     }
 
     pub(crate) fn prepare_draw(
@@ -316,6 +479,34 @@ impl Gui {
     }
 }
 
+/// Show a span of the profile, with its children nested below it
+///
+/// The `id` must stay the same when the profile is updated, so expanded spans
+/// stay expanded.
+fn show_span(ui: &mut egui::Ui, span: &Span, id: egui::Id) {
+    let mut text = String::from(span.name);
+    for (name, value) in &span.fields {
+        let _ = write!(text, " {name}={value}");
+    }
+    if span.count > 1 {
+        let _ = write!(text, " (x{})", span.count);
+    }
+    let _ = write!(text, ": {:.1} ms", span.duration.as_secs_f64() * 1000.);
+
+    if span.children.is_empty() {
+        ui.label(text);
+        return;
+    }
+
+    egui::CollapsingHeader::new(text)
+        .id_source(id)
+        .show(ui, |ui| {
+            for (i, child) in span.children.iter().enumerate() {
+                show_span(ui, child, id.with(i));
+            }
+        });
+}
+
 fn show_file_dialog() -> Option<PathBuf> {
     #[cfg(not(target_arch = "wasm32"))]
     return FileDialog::new()
@@ -348,4 +539,20 @@ pub struct GuiState<'a> {
 
     /// Indicates whether a model is currently available
     pub model_available: bool,
+
+    /// The models that were recently opened, most recent first
+    pub recent_models: &'a [PathBuf],
+
+    /// How long evaluating and processing the current model took
+    pub profile: Option<&'a Profile>,
+}
+
+/// An action that the user requested through the GUI
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GuiAction {
+    /// Open the model at the given path, replacing the current one
+    OpenModel(PathBuf),
+
+    /// Evaluate the current model again, even if it didn't change
+    ReloadModel,
 }
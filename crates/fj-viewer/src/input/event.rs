@@ -20,4 +20,12 @@ pub enum InputEvent {
 
     /// Move the view forwards and backwards
     Zoom(f64),
+
+    /// Move the enabled clipping planes along their axes
+    ///
+    /// The value is relative to the size of the model along the respective
+    /// axis. See [`DrawConfig::move_clipping_planes`].
+    ///
+    /// [`DrawConfig::move_clipping_planes`]: crate::DrawConfig::move_clipping_planes
+    ClippingPlanes(f64),
 }
@@ -24,6 +24,9 @@ impl InputHandler {
             InputEvent::Zoom(zoom_delta) => {
                 Zoom::apply(zoom_delta, focus_point, camera);
             }
+            InputEvent::ClippingPlanes(_) => {
+                // Doesn't affect the camera. Handled by `Viewer` instead.
+            }
         }
     }
 }
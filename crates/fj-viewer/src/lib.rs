@@ -18,15 +18,17 @@ mod camera;
 mod graphics;
 mod gui;
 mod input;
+mod measure;
 mod screen;
 mod status_report;
 mod viewer;
 
 pub use self::{
-    camera::Camera,
-    graphics::{DrawConfig, Renderer, RendererInitError},
-    gui::{Gui, GuiState},
+    camera::{Camera, Pick, Projection, ViewPreset},
+    graphics::{ClippingPlane, DrawConfig, Renderer, RendererInitError},
+    gui::{Gui, GuiAction, GuiState},
     input::{InputEvent, InputHandler},
+    measure::{MeasureError, MeasureResult, MeasureTool, Measurement},
     screen::{NormalizedScreenPosition, Screen, ScreenSize},
     status_report::StatusReport,
     viewer::Viewer,
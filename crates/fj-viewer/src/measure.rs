@@ -0,0 +1,451 @@
+//! Measuring the model
+
+use std::fmt;
+
+use fj_interop::{
+    edges::{Edge, EdgeGeometry},
+    processed_shape::ProcessedShape,
+};
+use fj_math::{Point, Scalar};
+
+use crate::camera::Pick;
+
+/// A tool for measuring the model
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MeasureTool {
+    /// Measure the distance between two points
+    Distance,
+
+    /// Measure the length of an edge
+    EdgeLength,
+
+    /// Measure the angle between two faces
+    Angle,
+
+    /// Measure the radius of a circular edge
+    Radius,
+}
+
+impl MeasureTool {
+    /// All available measurement tools
+    pub const ALL: [Self; 4] =
+        [Self::Distance, Self::EdgeLength, Self::Angle, Self::Radius];
+
+    /// The name of the tool, as displayed in the GUI
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Distance => "Distance",
+            Self::EdgeLength => "Edge length",
+            Self::Angle => "Angle",
+            Self::Radius => "Radius",
+        }
+    }
+
+    /// The number of points that need to be picked for a measurement
+    pub fn num_picks(&self) -> usize {
+        match self {
+            Self::Distance | Self::Angle => 2,
+            Self::EdgeLength | Self::Radius => 1,
+        }
+    }
+}
+
+/// A measurement, either in progress or complete
+#[derive(Debug)]
+pub struct Measurement {
+    tool: MeasureTool,
+    picks: Vec<Pick>,
+    result: Option<Result<MeasureResult, MeasureError>>,
+    lines: Vec<[Point<3>; 2]>,
+}
+
+impl Measurement {
+    /// Start a new measurement with the given tool
+    pub fn new(tool: MeasureTool) -> Self {
+        Self {
+            tool,
+            picks: Vec::new(),
+            result: None,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Access the tool used for this measurement
+    pub fn tool(&self) -> MeasureTool {
+        self.tool
+    }
+
+    /// Access the points that have been picked so far
+    pub fn picks(&self) -> &[Pick] {
+        &self.picks
+    }
+
+    /// Access the result of the measurement, if it is complete
+    pub fn result(&self) -> Option<&Result<MeasureResult, MeasureError>> {
+        self.result.as_ref()
+    }
+
+    /// Access the lines that visualize the result of the measurement
+    pub fn lines(&self) -> &[[Point<3>; 2]] {
+        &self.lines
+    }
+
+    /// Add a picked point to the measurement
+    ///
+    /// Computes the result, once enough points have been picked. If the
+    /// measurement is already complete, this starts a new one.
+    pub fn add_pick(&mut self, pick: Pick, shape: &ProcessedShape) {
+        if self.result.is_some() {
+            *self = Self::new(self.tool);
+        }
+
+        self.picks.push(pick);
+
+        if self.picks.len() >= self.tool.num_picks() {
+            let result = self.compute(shape);
+
+            self.lines = match &result {
+                Ok(result) => result.lines(shape),
+                Err(_) => Vec::new(),
+            };
+            self.result = Some(result);
+        }
+    }
+
+    /// Describe the state of the measurement, for display to the user
+    pub fn status(&self) -> String {
+        match &self.result {
+            Some(Ok(result)) => result.to_string(),
+            Some(Err(err)) => err.to_string(),
+            None => format!(
+                "Click on the model to pick point {} of {}",
+                self.picks.len() + 1,
+                self.tool.num_picks()
+            ),
+        }
+    }
+
+    fn compute(
+        &self,
+        shape: &ProcessedShape,
+    ) -> Result<MeasureResult, MeasureError> {
+        let max_distance = shape.aabb.size().magnitude() * PICK_DISTANCE;
+
+        match (self.tool, self.picks.as_slice()) {
+            (MeasureTool::Distance, [a, b]) => {
+                let [from, to] = [a, b].map(|pick| {
+                    snap_to_vertex(pick.point, &shape.edges, max_distance)
+                });
+                Ok(MeasureResult::Distance { from, to })
+            }
+            (MeasureTool::EdgeLength, [pick]) => {
+                let edge = closest_edge(pick.point, &shape.edges, max_distance)
+                    .ok_or(MeasureError::NoEdge)?;
+
+                Ok(MeasureResult::EdgeLength {
+                    length: edge.length(),
+                    is_exact: edge.geometry.is_some(),
+                    points: edge.points.clone(),
+                })
+            }
+            (MeasureTool::Angle, [a, b]) => {
+                let cos =
+                    a.normal.dot(&b.normal).max(-Scalar::ONE).min(Scalar::ONE);
+
+                Ok(MeasureResult::Angle {
+                    picks: [*a, *b],
+                    angle: cos.acos(),
+                })
+            }
+            (MeasureTool::Radius, [pick]) => {
+                let edge = closest_edge(pick.point, &shape.edges, max_distance)
+                    .ok_or(MeasureError::NoEdge)?;
+
+                let Some(EdgeGeometry::Arc { center, radius, normal, .. }) =
+                    edge.geometry
+                else {
+                    return Err(MeasureError::NotCircular);
+                };
+
+                // Project the picked point onto the circle, to show the radius
+                // there.
+                let offset = pick.point - center;
+                let offset = offset - normal * offset.dot(&normal);
+                let point_on_circle = if offset.magnitude() == Scalar::ZERO {
+                    edge.points[0]
+                } else {
+                    center + offset.normalize() * radius
+                };
+
+                Ok(MeasureResult::Radius {
+                    center,
+                    radius,
+                    point_on_circle,
+                    points: edge.points.clone(),
+                })
+            }
+            _ => {
+                unreachable!("Measurement computed with wrong number of picks")
+            }
+        }
+    }
+}
+
+/// The result of a complete [`Measurement`]
+#[derive(Debug)]
+pub enum MeasureResult {
+    /// The distance between two points
+    Distance {
+        /// The first point
+        from: Point<3>,
+
+        /// The second point
+        to: Point<3>,
+    },
+
+    /// The length of an edge
+    EdgeLength {
+        /// The length of the edge
+        length: Scalar,
+
+        /// Whether the length was computed from the exact edge geometry
+        ///
+        /// If not, it was computed from the edge's approximation.
+        is_exact: bool,
+
+        /// The points that approximate the edge
+        points: Vec<Point<3>>,
+    },
+
+    /// The angle between two faces
+    Angle {
+        /// The points picked on the faces
+        picks: [Pick; 2],
+
+        /// The angle between the normals of the faces, in radians
+        angle: Scalar,
+    },
+
+    /// The radius of a circular edge
+    Radius {
+        /// The center of the circle
+        center: Point<3>,
+
+        /// The radius of the circle
+        radius: Scalar,
+
+        /// The point on the circle, closest to the picked point
+        point_on_circle: Point<3>,
+
+        /// The points that approximate the edge
+        points: Vec<Point<3>>,
+    },
+}
+
+impl MeasureResult {
+    fn lines(&self, shape: &ProcessedShape) -> Vec<[Point<3>; 2]> {
+        let polyline = |points: &[Point<3>]| {
+            points
+                .windows(2)
+                .map(|points| [points[0], points[1]])
+                .collect::<Vec<_>>()
+        };
+
+        match self {
+            Self::Distance { from, to } => vec![[*from, *to]],
+            Self::EdgeLength { points, .. } => polyline(points),
+            Self::Angle { picks, .. } => {
+                let length = shape.aabb.size().magnitude() * NORMAL_LENGTH;
+
+                picks
+                    .iter()
+                    .map(|pick| [pick.point, pick.point + pick.normal * length])
+                    .collect()
+            }
+            Self::Radius {
+                center,
+                point_on_circle,
+                points,
+                ..
+            } => {
+                let mut lines = polyline(points);
+                lines.push([*center, *point_on_circle]);
+                lines
+            }
+        }
+    }
+}
+
+impl fmt::Display for MeasureResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Distance { from, to } => {
+                let [dx, dy, dz] = (*to - *from).components;
+                write!(
+                    f,
+                    "Distance: {:.3}\n(dx {:.3}, dy {:.3}, dz {:.3})",
+                    from.distance_to(to),
+                    dx.abs(),
+                    dy.abs(),
+                    dz.abs()
+                )
+            }
+            Self::EdgeLength {
+                length, is_exact, ..
+            } => {
+                write!(f, "Edge length: {length:.3}")?;
+                if !is_exact {
+                    write!(f, " (approximated)")?;
+                }
+                Ok(())
+            }
+            Self::Angle { angle, .. } => {
+                write!(f, "Angle: {:.2}°", angle.into_f64().to_degrees())
+            }
+            Self::Radius { radius, .. } => {
+                write!(f, "Radius: {radius:.3}\nDiameter: {:.3}", *radius * 2.)
+            }
+        }
+    }
+}
+
+/// An error that prevented a [`Measurement`] from being completed
+#[derive(Debug, thiserror::Error)]
+pub enum MeasureError {
+    /// No edge was found close to the picked point
+    #[error("No edge found near the picked point")]
+    NoEdge,
+
+    /// The edge closest to the picked point is not circular
+    #[error("The edge near the picked point is not circular")]
+    NotCircular,
+}
+
+/// How far away from the picked point edges and vertices are considered
+///
+/// Relative to the size of the model's bounding box.
+const PICK_DISTANCE: f64 = 0.02;
+
+/// The length of the lines that visualize face normals
+///
+/// Relative to the size of the model's bounding box.
+const NORMAL_LENGTH: f64 = 0.1;
+
+fn closest_edge(
+    point: Point<3>,
+    edges: &[Edge],
+    max_distance: Scalar,
+) -> Option<&Edge> {
+    edges
+        .iter()
+        .filter_map(|edge| {
+            let distance = edge.closest_point(&point)?.distance_to(&point);
+            Some((edge, distance))
+        })
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(edge, _)| edge)
+}
+
+/// Snap the point to the closest vertex, if it is close enough
+fn snap_to_vertex(
+    point: Point<3>,
+    edges: &[Edge],
+    max_distance: Scalar,
+) -> Point<3> {
+    edges
+        .iter()
+        .flat_map(|edge| [edge.points.first(), edge.points.last()])
+        .flatten()
+        .copied()
+        .filter(|vertex| vertex.distance_to(&point) <= max_distance)
+        .min_by_key(|vertex| vertex.distance_to(&point))
+        .unwrap_or(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{
+        debug::DebugInfo,
+        edges::{Edge, EdgeGeometry},
+        mesh::Mesh,
+        processed_shape::{MeshStats, ProcessedShape},
+    };
+    use fj_math::{Aabb, Point, Scalar, Segment, Vector};
+
+    use crate::camera::Pick;
+
+    use super::{MeasureError, MeasureResult, MeasureTool, Measurement};
+
+    #[test]
+    fn measure() {
+        let straight = [[0., 0., 0.], [1., 0., 0.]].map(Point::from);
+        let curved = [[1., 0., 0.], [0., 1., 0.]].map(Point::from);
+
+        let shape = ProcessedShape {
+            aabb: Aabb {
+                min: Point::from([0., 0., 0.]),
+                max: Point::from([1., 1., 1.]),
+            },
+            mesh: Mesh::new(),
+            mesh_stats: MeshStats::default(),
+            edges: vec![
+                Edge {
+                    geometry: Some(EdgeGeometry::Segment(
+                        Segment::from_points(straight),
+                    )),
+                    points: straight.to_vec(),
+                },
+                Edge {
+                    geometry: Some(EdgeGeometry::Arc {
+                        center: Point::origin(),
+                        radius: Scalar::ONE,
+                        normal: Vector::unit_z(),
+                        angle: Scalar::PI / 2.,
+                    }),
+                    points: curved.to_vec(),
+                },
+            ],
+            annotations: Vec::new(),
+            references: Vec::new(),
+            debug_info: DebugInfo::new(),
+        };
+        let pick = |point: [f64; 3]| Pick {
+            point: Point::from(point),
+            normal: Vector::unit_z(),
+        };
+
+        // Picked points are snapped to the nearby vertex.
+        let mut measurement = Measurement::new(MeasureTool::Distance);
+        measurement.add_pick(pick([0.01, 0., 0.]), &shape);
+        assert!(measurement.result().is_none());
+        measurement.add_pick(pick([0.5, 0.5, 1.]), &shape);
+        assert!(matches!(
+            measurement.result(),
+            Some(Ok(MeasureResult::Distance { from, .. }))
+                if *from == Point::origin()
+        ));
+
+        let mut measurement = Measurement::new(MeasureTool::EdgeLength);
+        measurement.add_pick(pick([0.5, 0.01, 0.]), &shape);
+        assert!(matches!(
+            measurement.result(),
+            Some(Ok(MeasureResult::EdgeLength { length, is_exact: true, .. }))
+                if *length == Scalar::ONE
+        ));
+
+        let mut measurement = Measurement::new(MeasureTool::Radius);
+        measurement.add_pick(pick([0.5, 0.51, 0.]), &shape);
+        assert!(matches!(
+            measurement.result(),
+            Some(Ok(MeasureResult::Radius { radius, .. }))
+                if *radius == Scalar::ONE
+        ));
+
+        measurement.add_pick(pick([0.5, 0., 0.]), &shape);
+        assert!(matches!(
+            measurement.result(),
+            Some(Err(MeasureError::NotCircular))
+        ));
+    }
+}
@@ -1,13 +1,12 @@
-use std::path::PathBuf;
-
-use fj_interop::processed_shape::ProcessedShape;
+use fj_interop::{mesh::Mesh, processed_shape::ProcessedShape};
 use fj_math::Aabb;
 use tracing::warn;
 
 use crate::{
-    camera::FocusPoint, gui::Gui, Camera, DrawConfig, GuiState, InputEvent,
-    InputHandler, NormalizedScreenPosition, Renderer, RendererInitError,
-    Screen, ScreenSize,
+    camera::FocusPoint, graphics::Vertices, gui::Gui, Camera, DrawConfig,
+    GuiAction, GuiState, InputEvent, InputHandler, MeasureTool, Measurement,
+    NormalizedScreenPosition, Renderer, RendererInitError, Screen, ScreenSize,
+    ViewPreset,
 };
 
 /// The Fornjot model viewer
@@ -30,9 +29,15 @@ pub struct Viewer {
     /// The input handler
     pub input_handler: InputHandler,
 
+    /// The current measurement, if a measurement tool is active
+    pub measurement: Option<Measurement>,
+
     /// The renderer
     pub renderer: Renderer,
 
+    /// The size of the screen
+    pub screen_size: ScreenSize,
+
     /// The shape
     pub shape: Option<ProcessedShape>,
 }
@@ -50,7 +55,9 @@ impl Viewer {
             focus_point: None,
             gui,
             input_handler: InputHandler::default(),
+            measurement: None,
             renderer,
+            screen_size: screen.size(),
             shape: None,
         })
     }
@@ -74,26 +81,145 @@ impl Viewer {
         }
     }
 
+    /// Toggle the "draw annotations" setting
+    pub fn toggle_draw_annotations(&mut self) {
+        if self.renderer.is_line_drawing_available() {
+            self.draw_config.draw_annotations =
+                !self.draw_config.draw_annotations;
+            self.update_annotations();
+        }
+    }
+
+    /// Toggle the "draw references" setting
+    pub fn toggle_draw_references(&mut self) {
+        self.draw_config.draw_references = !self.draw_config.draw_references;
+    }
+
+    /// Toggle between perspective and orthographic projection
+    pub fn toggle_projection(&mut self) {
+        self.camera.toggle_projection();
+    }
+
+    /// Switch to a standard view, zoomed to fit the model
+    pub fn view_preset(&mut self, preset: ViewPreset) {
+        if let Some(shape) = &self.shape {
+            self.camera
+                .view_preset(preset, &shape.aabb, self.aspect_ratio());
+        }
+    }
+
+    /// Center the model, and zoom so it fits the screen
+    pub fn zoom_to_fit(&mut self) {
+        if let Some(shape) = &self.shape {
+            self.camera.zoom_to_fit(&shape.aabb, self.aspect_ratio());
+        }
+    }
+
     /// Handle the shape being updated
     pub fn handle_shape_update(&mut self, shape: ProcessedShape) {
-        self.renderer
-            .update_geometry((&shape.mesh).into(), (&shape.debug_info).into());
+        let mut references = Mesh::new();
+        for reference in &shape.references {
+            for triangle in reference.mesh.triangles() {
+                references.push_triangle(triangle.inner, triangle.color);
+            }
+        }
+
+        self.renderer.update_geometry(
+            (&shape.mesh).into(),
+            (&shape.debug_info).into(),
+            (&references).into(),
+        );
 
         let aabb = shape.aabb;
         if self.shape.replace(shape).is_none() {
             self.camera.init_planes(&aabb);
         }
+
+        // Any measurement refers to the previous shape.
+        let tool = self.measurement.as_ref().map(Measurement::tool);
+        self.select_measure_tool(tool);
+    }
+
+    /// Select the measurement tool, or disable measuring with `None`
+    ///
+    /// This discards the current measurement.
+    pub fn select_measure_tool(&mut self, tool: Option<MeasureTool>) {
+        self.measurement = tool.map(Measurement::new);
+        self.update_annotations();
+    }
+
+    /// Pick the point under the cursor for the current measurement
+    ///
+    /// Does nothing, if no measurement tool is active, or if the cursor doesn't
+    /// point at the model.
+    pub fn pick_measurement_point(&mut self) {
+        let (Some(measurement), Some(shape), Some(cursor)) =
+            (&mut self.measurement, &self.shape, self.cursor)
+        else {
+            return;
+        };
+
+        if let Some(pick) = self.camera.pick(cursor, &shape.mesh) {
+            measurement.add_pick(pick, shape);
+            self.update_annotations();
+        }
+    }
+
+    fn update_annotations(&mut self) {
+        let mut annotations = Vertices::empty();
+
+        if let Some(shape) = &self.shape {
+            if self.draw_config.draw_annotations {
+                for annotation in &shape.annotations {
+                    annotations.push_annotation(annotation);
+                }
+            }
+        }
+        if let Some(measurement) = &self.measurement {
+            annotations.push_measurement(measurement);
+        }
+
+        self.renderer.update_annotations(annotations);
+    }
+
+    /// Compute where to place the text of the model's annotations
+    fn annotation_labels(&self) -> Vec<(NormalizedScreenPosition, String)> {
+        let Some(shape) = &self.shape else {
+            return Vec::new();
+        };
+        if !self.draw_config.draw_annotations {
+            return Vec::new();
+        }
+
+        shape
+            .annotations
+            .iter()
+            .filter_map(|annotation| {
+                let position =
+                    self.camera.model_to_cursor_space(annotation.anchor())?;
+                Some((position, annotation.text()))
+            })
+            .collect()
     }
 
     /// Handle an input event
     pub fn handle_input_event(&mut self, event: InputEvent) {
+        if let InputEvent::ClippingPlanes(delta) = event {
+            if let Some(shape) = &self.shape {
+                self.draw_config.move_clipping_planes(delta, &shape.aabb);
+            }
+            return;
+        }
+
         if let Some(focus_point) = self.focus_point {
+            self.camera.stop_animation();
             InputHandler::handle_event(event, focus_point, &mut self.camera);
         }
     }
 
     /// Handle the screen being resized
     pub fn handle_screen_resize(&mut self, screen_size: ScreenSize) {
+        self.screen_size = screen_size;
         self.renderer.handle_resize(screen_size);
     }
 
@@ -119,24 +245,37 @@ impl Viewer {
         pixels_per_point: f32,
         egui_input: egui::RawInput,
         gui_state: GuiState,
-    ) -> Option<PathBuf> {
+    ) -> Option<GuiAction> {
         let aabb = self
             .shape
             .as_ref()
             .map(|shape| shape.aabb)
             .unwrap_or_else(Aabb::default);
 
+        self.camera.animate();
         self.camera.update_planes(&aabb);
 
-        let new_model_path = self.gui.update(
+        let measure_tool = self.measurement.as_ref().map(Measurement::tool);
+        let draw_annotations = self.draw_config.draw_annotations;
+
+        let action = self.gui.update(
             pixels_per_point,
             egui_input,
             &mut self.draw_config,
             &aabb,
             self.renderer.is_line_drawing_available(),
+            &mut self.measurement,
             gui_state,
         );
 
+        self.gui.draw_labels(&self.annotation_labels());
+
+        if self.measurement.as_ref().map(Measurement::tool) != measure_tool
+            || self.draw_config.draw_annotations != draw_annotations
+        {
+            self.update_annotations();
+        }
+
         if let Err(err) = self.renderer.draw(
             &self.camera,
             &self.draw_config,
@@ -146,6 +285,11 @@ impl Viewer {
             warn!("Draw error: {}", err);
         }
 
-        new_model_path
+        action
+    }
+
+    fn aspect_ratio(&self) -> f64 {
+        let [width, height] = self.screen_size.as_f64();
+        width / height
     }
 }
@@ -1,19 +1,28 @@
-use fj_host::{Host, Model, ModelEvent, Parameters};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use fj_host::{Host, Model, ModelEvent, Profiler, Stage};
+use fj_interop::profile::Profile;
 use fj_operations::shape_processor::{self, ShapeProcessor};
 use fj_viewer::{
-    GuiState, InputEvent, NormalizedScreenPosition, Screen, ScreenSize,
-    StatusReport, Viewer,
+    GuiAction, GuiState, InputEvent, NormalizedScreenPosition, Screen,
+    ScreenSize, StatusReport, ViewPreset, Viewer,
 };
 use winit::{
     dpi::PhysicalPosition,
     event::{
-        ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta,
-        VirtualKeyCode, WindowEvent,
+        ElementState, Event, KeyboardInput, ModifiersState, MouseButton,
+        MouseScrollDelta, VirtualKeyCode, WindowEvent,
     },
     event_loop::ControlFlow,
 };
 
-use crate::window::Window;
+use crate::{recent_models::RecentModels, window::Window};
+
+/// Loads the model at the given path
+pub type LoadModel = Box<dyn FnMut(&Path) -> Result<Model, fj_host::Error>>;
 
 pub struct EventLoopHandler {
     pub invert_zoom: bool,
@@ -22,9 +31,27 @@ pub struct EventLoopHandler {
     pub viewer: Viewer,
     pub egui_winit_state: egui_winit::State,
     pub host: Option<Host>,
+
+    /// Loads models that are opened from within the viewer
+    pub load_model: LoadModel,
+
+    pub recent_models: RecentModels,
     pub status: StatusReport,
     pub held_mouse_button: Option<MouseButton>,
 
+    /// Collects how long evaluating and processing the model takes
+    pub profiler: Profiler,
+
+    /// Where to write the profile of the model, each time it was processed
+    pub profile_path: Option<PathBuf>,
+
+    /// The profile of the last time the model was processed
+    pub profile: Option<Profile>,
+
+    /// The cursor position, when the left mouse button was last pressed
+    pub click_position: Option<NormalizedScreenPosition>,
+    pub modifiers: ModifiersState,
+
     /// Only handle resize events once every frame. This filters out spurious
     /// resize events that can lead to wgpu warnings. See this issue for some
     /// context:
@@ -39,9 +66,8 @@ impl EventLoopHandler {
         event: Event<()>,
         control_flow: &mut ControlFlow,
     ) -> Result<(), Error> {
-        if let Some(host) = &self.host {
+        if let Some(events) = self.host.as_ref().map(|host| host.events()) {
             loop {
-                let events = host.events();
                 let event = events
                     .try_recv()
                     .map_err(|err| {
@@ -61,15 +87,22 @@ impl EventLoopHandler {
                         self.status.update_status(
                             "Change in model detected. Evaluating model...",
                         );
+
+                        // Whatever has been recorded so far belongs to
+                        // previous evaluations, which failed or have been
+                        // cancelled.
+                        self.profiler.take();
+                    }
+                    ModelEvent::Progress(stage) => {
+                        self.status.update_status(stage_status(stage));
                     }
                     ModelEvent::Evaluation(evaluation) => {
-                        self.status.update_status(
-                            "Model evaluated. Processing model...",
-                        );
+                        self.status.update_status(stage_status(Stage::Meshing));
 
                         let shape =
                             self.shape_processor.process(&evaluation.shape)?;
                         self.viewer.handle_shape_update(shape);
+                        self.update_profile();
 
                         self.status.update_status("Model processed.");
                     }
@@ -129,6 +162,33 @@ impl EventLoopHandler {
                 VirtualKeyCode::Key3 => {
                     self.viewer.toggle_draw_debug();
                 }
+                VirtualKeyCode::Key4 => {
+                    self.viewer.toggle_draw_annotations();
+                }
+                VirtualKeyCode::Key5 => {
+                    self.viewer.toggle_draw_references();
+                }
+                VirtualKeyCode::F => {
+                    self.viewer.view_preset(ViewPreset::Front);
+                }
+                VirtualKeyCode::T => {
+                    self.viewer.view_preset(ViewPreset::Top);
+                }
+                VirtualKeyCode::R => {
+                    self.viewer.view_preset(ViewPreset::Right);
+                }
+                VirtualKeyCode::I => {
+                    self.viewer.view_preset(ViewPreset::Isometric);
+                }
+                VirtualKeyCode::Z => {
+                    self.viewer.zoom_to_fit();
+                }
+                VirtualKeyCode::O => {
+                    self.viewer.toggle_projection();
+                }
+                VirtualKeyCode::F5 => {
+                    self.reload_model();
+                }
                 _ => {}
             },
             Event::WindowEvent {
@@ -140,6 +200,12 @@ impl EventLoopHandler {
                     height: size.height,
                 });
             }
+            Event::WindowEvent {
+                event: WindowEvent::ModifiersChanged(modifiers),
+                ..
+            } => {
+                self.modifiers = modifiers;
+            }
             Event::WindowEvent {
                 event: WindowEvent::MouseInput { state, button, .. },
                 ..
@@ -147,10 +213,25 @@ impl EventLoopHandler {
                 ElementState::Pressed => {
                     self.held_mouse_button = Some(button);
                     self.viewer.add_focus_point();
+
+                    if button == MouseButton::Left {
+                        self.click_position = self.viewer.cursor;
+                    }
                 }
                 ElementState::Released => {
                     self.held_mouse_button = None;
                     self.viewer.remove_focus_point();
+
+                    // Clicking without moving the mouse picks a point to
+                    // measure. Moving the mouse rotates the model instead.
+                    let click_position = self.click_position.take();
+                    if let (MouseButton::Left, Some(pressed), Some(released)) =
+                        (button, click_position, self.viewer.cursor)
+                    {
+                        if pressed.x == released.x && pressed.y == released.y {
+                            self.viewer.pick_measurement_point();
+                        }
+                    }
                 }
             },
             Event::WindowEvent {
@@ -177,15 +258,20 @@ impl EventLoopHandler {
                 let gui_state = GuiState {
                     status: &self.status,
                     model_available: self.host.is_some(),
+                    recent_models: self.recent_models.paths(),
+                    profile: self.profile.as_ref(),
                 };
-                let new_model_path =
+                let action =
                     self.viewer.draw(pixels_per_point, egui_input, gui_state);
 
-                if let Some(model_path) = new_model_path {
-                    let model =
-                        Model::new(model_path, Parameters::empty()).unwrap();
-                    let new_host = Host::from_model(model)?;
-                    self.host = Some(new_host);
+                match action {
+                    Some(GuiAction::OpenModel(model_path)) => {
+                        self.open_model(model_path)?;
+                    }
+                    Some(GuiAction::ReloadModel) => {
+                        self.reload_model();
+                    }
+                    None => {}
                 }
             }
             _ => {}
@@ -196,6 +282,7 @@ impl EventLoopHandler {
             &self.window,
             &self.held_mouse_button,
             &mut self.viewer.cursor,
+            self.modifiers,
             self.invert_zoom,
         );
         if let Some(input_event) = input_event {
@@ -204,6 +291,64 @@ impl EventLoopHandler {
 
         Ok(())
     }
+
+    /// Open a model, replacing the current one
+    ///
+    /// The new model is loaded and watched for changes, like the one the
+    /// application was started with.
+    #[allow(clippy::result_large_err)]
+    fn open_model(&mut self, model_path: PathBuf) -> Result<(), Error> {
+        self.status.update_status(&format!(
+            "Opening model at {}...",
+            model_path.display()
+        ));
+
+        let model = (self.load_model)(&model_path)?;
+        self.host = Some(Host::from_model(model)?);
+
+        if let Err(err) = self.recent_models.add(model_path) {
+            self.status.update_status(&format!(
+                "Failed to save list of recent models: {err}"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Take the profile of the model that was just processed
+    fn update_profile(&mut self) {
+        let profile = self.profiler.take();
+
+        if let Some(path) = &self.profile_path {
+            if let Err(err) =
+                fs::write(path, fj_host::profile_to_json(&profile))
+            {
+                self.status.update_status(&format!(
+                    "Failed to write profile to {}: {err}",
+                    path.display()
+                ));
+            }
+        }
+
+        self.profile = Some(profile);
+    }
+
+    /// Evaluate the current model again, if there is one
+    fn reload_model(&mut self) {
+        if let Some(host) = &self.host {
+            self.status.update_status("Reloading model...");
+            host.reload();
+        }
+    }
+}
+
+fn stage_status(stage: Stage) -> &'static str {
+    match stage {
+        Stage::Compiling => "Compiling model...",
+        Stage::Loading => "Loading model...",
+        Stage::Evaluating => "Evaluating model...",
+        Stage::Meshing => "Model evaluated. Processing model...",
+    }
 }
 
 fn input_event<T>(
@@ -211,6 +356,7 @@ fn input_event<T>(
     window: &Window,
     held_mouse_button: &Option<MouseButton>,
     previous_cursor: &mut Option<NormalizedScreenPosition>,
+    modifiers: ModifiersState,
     invert_zoom: bool,
 ) -> Option<InputEvent> {
     match event {
@@ -251,15 +397,23 @@ fn input_event<T>(
             event: WindowEvent::MouseWheel { delta, .. },
             ..
         } => {
+            // Some platforms turn vertical scrolling into horizontal scrolling,
+            // while shift is held. Accept either direction.
             let delta = match delta {
-                MouseScrollDelta::LineDelta(_, y) => {
-                    f64::from(*y) * ZOOM_FACTOR_LINE
+                MouseScrollDelta::LineDelta(x, y) => {
+                    let delta = if *y != 0. { y } else { x };
+                    f64::from(*delta) * ZOOM_FACTOR_LINE
+                }
+                MouseScrollDelta::PixelDelta(PhysicalPosition { x, y }) => {
+                    let delta = if *y != 0. { y } else { x };
+                    delta * ZOOM_FACTOR_PIXEL
                 }
-                MouseScrollDelta::PixelDelta(PhysicalPosition {
-                    y, ..
-                }) => y * ZOOM_FACTOR_PIXEL,
             };
 
+            if modifiers.shift() {
+                return Some(InputEvent::ClippingPlanes(delta));
+            }
+
             let delta = if invert_zoom { -delta } else { delta };
 
             Some(InputEvent::Zoom(delta))
@@ -13,6 +13,7 @@
 
 #![warn(missing_docs)]
 
+pub mod recent_models;
 pub mod run;
 pub mod window;
 
@@ -0,0 +1,117 @@
+//! The list of recently opened models
+//!
+//! See [`RecentModels`].
+
+use std::{error::Error, path::PathBuf};
+
+/// The maximum number of models in [`RecentModels`]
+pub const MAX_RECENT_MODELS: usize = 10;
+
+/// The list of recently opened models
+///
+/// The list is shown in the GUI, to quickly switch between models. How the
+/// list is persisted is up to the application, which provides a function that
+/// is called with the updated list, whenever it changes.
+pub struct RecentModels {
+    paths: Vec<PathBuf>,
+    persist: Persist,
+}
+
+type Persist = Box<dyn FnMut(&[PathBuf]) -> Result<(), Box<dyn Error>>>;
+
+impl RecentModels {
+    /// Create a list of recently opened models, most recent first
+    pub fn new(
+        paths: Vec<PathBuf>,
+        persist: impl FnMut(&[PathBuf]) -> Result<(), Box<dyn Error>> + 'static,
+    ) -> Self {
+        Self {
+            paths,
+            persist: Box::new(persist),
+        }
+    }
+
+    /// Access the paths of the recently opened models, most recent first
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Add a model to the front of the list, then persist the list
+    ///
+    /// If the model is already in the list, it is moved to the front. If there
+    /// are more than [`MAX_RECENT_MODELS`] models, the oldest ones are dropped.
+    pub fn add(&mut self, path: PathBuf) -> Result<(), Box<dyn Error>> {
+        let path = path.canonicalize().unwrap_or(path);
+
+        self.paths.retain(|other| other != &path);
+        self.paths.insert(0, path);
+        self.paths.truncate(MAX_RECENT_MODELS);
+
+        (self.persist)(&self.paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+    use super::{RecentModels, MAX_RECENT_MODELS};
+
+    #[test]
+    fn add_existing_model() {
+        let (mut recent_models, _) = recent_models(&["a", "b", "c"]);
+
+        recent_models.add(path("c")).unwrap();
+        assert_eq!(recent_models.paths(), paths(&["c", "a", "b"]));
+    }
+
+    #[test]
+    fn drop_oldest_models() {
+        let names = (0..MAX_RECENT_MODELS)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+        let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+        let (mut recent_models, _) = recent_models(&names);
+
+        recent_models.add(path("new")).unwrap();
+
+        let paths = recent_models.paths();
+        assert_eq!(paths.len(), MAX_RECENT_MODELS);
+        assert_eq!(paths[0], path("new"));
+        assert_eq!(paths[1], path("0"));
+        assert!(!paths.contains(&path(names[MAX_RECENT_MODELS - 1])));
+    }
+
+    #[test]
+    fn persist_updated_list() {
+        let (mut recent_models, persisted) = recent_models(&["a"]);
+
+        recent_models.add(path("b")).unwrap();
+        assert_eq!(*persisted.borrow(), [paths(&["b", "a"])]);
+    }
+
+    fn recent_models(
+        names: &[&str],
+    ) -> (RecentModels, Rc<RefCell<Vec<Vec<PathBuf>>>>) {
+        let persisted = Rc::new(RefCell::new(Vec::new()));
+
+        let recent_models = RecentModels::new(paths(names), {
+            let persisted = persisted.clone();
+            move |paths| {
+                persisted.borrow_mut().push(paths.to_vec());
+                Ok(())
+            }
+        });
+
+        (recent_models, persisted)
+    }
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(|name| path(name)).collect()
+    }
+
+    /// A path that doesn't exist, so `add` leaves it as it is
+    fn path(name: &str) -> PathBuf {
+        PathBuf::from("fj-window-test-does-not-exist").join(name)
+    }
+}
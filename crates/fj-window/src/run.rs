@@ -6,25 +6,37 @@
 use std::{
     error,
     fmt::{self, Write},
+    path::{Path, PathBuf},
 };
 
-use fj_host::{Host, Model};
+use fj_host::{Host, Model, Profiler};
 use fj_operations::shape_processor::ShapeProcessor;
 use fj_viewer::{RendererInitError, StatusReport, Viewer};
 use futures::executor::block_on;
 use tracing::trace;
-use winit::event_loop::EventLoop;
+use winit::{event::ModifiersState, event_loop::EventLoop};
 
 use crate::{
     event_loop_handler::{self, EventLoopHandler},
+    recent_models::RecentModels,
     window::{self, Window},
 };
 
 /// Initializes a model viewer for a given model and enters its process loop.
+///
+/// Further models can be opened from within the viewer. Those are loaded using
+/// `load_model`, and added to `recent_models`.
+///
+/// Each time a model has been processed, the spans recorded by `profiler` are
+/// shown in the viewer, and written to `profile_path` as JSON, if provided.
 pub fn run(
     model: Option<Model>,
+    load_model: impl FnMut(&Path) -> Result<Model, fj_host::Error> + 'static,
     shape_processor: ShapeProcessor,
     invert_zoom: bool,
+    recent_models: RecentModels,
+    profiler: Profiler,
+    profile_path: Option<PathBuf>,
 ) -> Result<(), Error> {
     let event_loop = EventLoop::new();
     let window = Window::new(&event_loop)?;
@@ -41,8 +53,15 @@ pub fn run(
         viewer,
         egui_winit_state,
         host,
+        load_model: Box::new(load_model),
+        recent_models,
         status: StatusReport::new(),
         held_mouse_button: None,
+        profiler,
+        profile_path,
+        profile: None,
+        click_position: None,
+        modifiers: ModifiersState::empty(),
         new_size: None,
     };
 
@@ -3,6 +3,7 @@
 use std::{
     alloc::{GlobalAlloc, Layout, System},
     fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
     ops::Deref,
     ptr::NonNull,
 };
@@ -28,6 +29,12 @@ impl<T: PartialEq> PartialEq for Vec<T> {
     }
 }
 
+impl<T: Hash> Hash for Vec<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
 impl<T> From<std::vec::Vec<T>> for Vec<T> {
     fn from(mut items: std::vec::Vec<T>) -> Self {
         // Safety: To avoid accidental double-frees and other memory issues, we
@@ -164,7 +171,7 @@ where
 
 /// A FFI-safe version of `Box<str>`.
 #[repr(transparent)]
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Hash, Clone)]
 pub struct String(Vec<u8>);
 
 impl From<std::string::String> for String {
@@ -212,6 +219,29 @@ impl Deref for String {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for String {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        self.deref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserialize<'de> for String {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        Ok(std::string::String::deserialize(deserializer)?.into())
+    }
+}
+
 /// A version of `Result` that is `#[repr(C)]`.
 #[must_use]
 #[repr(C)]
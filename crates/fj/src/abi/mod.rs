@@ -43,7 +43,16 @@ mod host;
 mod metadata;
 mod model;
 
-use std::any::Any;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use std::{
+    any::Any,
+    backtrace::{Backtrace, BacktraceStatus},
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
 
 pub use self::{
     context::Context,
@@ -93,14 +102,62 @@ macro_rules! register_model {
                 $crate::models::Error,
             > = $init;
 
-            match init(&mut *host) {
-                Ok(meta) => $crate::abi::InitResult::Ok(meta.into()),
-                Err(e) => $crate::abi::InitResult::Err(e.into()),
+            match $crate::abi::catch_panic(|| init(&mut *host)) {
+                Ok(Ok(meta)) => $crate::abi::InitResult::Ok(meta.into()),
+                Ok(Err(e)) => $crate::abi::InitResult::Err(e.into()),
+                Err(e) => $crate::abi::InitResult::Err(e),
             }
         }
+
+        $crate::__register_wasm_model!($init);
+    };
+}
+
+/// Export the functions that a model compiled to WebAssembly needs
+///
+/// See `fj::abi::wasm`. Expands to nothing, unless the `wasm` feature is
+/// enabled.
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_wasm_model {
+    ($init:expr) => {
+        #[cfg(target_arch = "wasm32")]
+        #[no_mangle]
+        extern "C" fn fj_model_alloc(len: u32) -> u32 {
+            $crate::abi::wasm::exports::alloc(len)
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[no_mangle]
+        unsafe extern "C" fn fj_model_free(ptr: u32, len: u32) {
+            $crate::abi::wasm::exports::free(ptr, len)
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[no_mangle]
+        unsafe extern "C" fn fj_model_evaluate(ptr: u32, len: u32) -> u64 {
+            $crate::abi::wasm::exports::evaluate($init, ptr, len)
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        #[no_mangle]
+        extern "C" fn fj_model_panic() -> u64 {
+            $crate::abi::wasm::exports::last_panic()
+        }
     };
 }
 
+/// Export the functions that a model compiled to WebAssembly needs
+///
+/// Expands to nothing, as the `wasm` feature is disabled.
+#[cfg(not(feature = "wasm"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_wasm_model {
+    ($init:expr) => {};
+}
+
 /// The signature of the function generated by [`register_model`].
 ///
 /// ```rust
@@ -116,17 +173,93 @@ pub type ShapeResult = ffi_safe::Result<crate::Shape, ffi_safe::BoxedError>;
 ///
 pub const INIT_FUNCTION_NAME: &str = "fj_model_init";
 
+/// Run a function, converting a panic into an error
+///
+/// Panicking across the FFI boundary is UB, so panics in model code must be
+/// caught before they get there. Returning them as an error lets the host
+/// report them like any other error, instead of taking it down.
+///
+/// The error contains the panic message and location, as well as a backtrace,
+/// if backtraces are enabled via `RUST_BACKTRACE`.
+pub fn catch_panic<T>(
+    f: impl FnOnce() -> T,
+) -> Result<T, ffi_safe::BoxedError> {
+    PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            let backtrace = Backtrace::capture();
+            let details = PanicDetails {
+                location: info.location().map(ToString::to_string),
+                backtrace: match backtrace.status() {
+                    BacktraceStatus::Captured => Some(backtrace.to_string()),
+                    _ => None,
+                },
+            };
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(details));
+
+            default_hook(info);
+        }));
+    });
+
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let mut msg =
+            format!("Model panicked at '{}'", panic_message(&payload));
+
+        if let Some(details) = LAST_PANIC.with(|last| last.borrow_mut().take())
+        {
+            if let Some(location) = details.location {
+                msg.push_str(&format!(", {location}"));
+            }
+            if let Some(backtrace) = details.backtrace {
+                msg.push_str(&format!("\n\nstack backtrace:\n{backtrace}"));
+            }
+        }
+
+        crate::models::Error::from(msg).into()
+    })
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    /// Details about the last panic on this thread, recorded by the panic hook
+    static LAST_PANIC: RefCell<Option<PanicDetails>> =
+        const { RefCell::new(None) };
+}
+
+struct PanicDetails {
+    location: Option<String>,
+    backtrace: Option<String>,
+}
+
 fn on_panic(payload: Box<dyn Any + Send>) -> ! {
-    let msg: &str =
-        if let Some(s) = payload.downcast_ref::<std::string::String>() {
-            s.as_str()
-        } else if let Some(s) = payload.downcast_ref::<&str>() {
-            s
-        } else {
-            "A panic occurred"
-        };
-
-    eprintln!("{msg}");
+    eprintln!("{}", panic_message(&payload));
     // It's not ideal, but panicking across the FFI boundary is UB.
     std::process::abort();
 }
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(s) = payload.downcast_ref::<std::string::String>() {
+        s.as_str()
+    } else if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else {
+        "A panic occurred"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::catch_panic;
+
+    #[test]
+    fn catch_panic_returns_error() {
+        assert_eq!(catch_panic(|| 1).unwrap(), 1);
+
+        let err = catch_panic(|| panic!("Invalid model")).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.starts_with("Model panicked at 'Invalid model', "));
+        assert!(msg.contains("mod.rs"));
+    }
+}
@@ -56,12 +56,10 @@ impl From<Box<dyn crate::models::Model>> for Model {
         ) -> ShapeResult {
             let model = &*(user_data as *mut Box<dyn crate::models::Model>);
 
-            match std::panic::catch_unwind(AssertUnwindSafe(|| {
-                model.shape(&ctx)
-            })) {
+            match crate::abi::catch_panic(|| model.shape(&ctx)) {
                 Ok(Ok(shape)) => ShapeResult::Ok(shape),
                 Ok(Err(err)) => ShapeResult::Err(err.into()),
-                Err(payload) => crate::abi::on_panic(payload),
+                Err(err) => ShapeResult::Err(err),
             }
         }
 
@@ -0,0 +1,264 @@
+//! Interface between a model that is compiled to WebAssembly and its host
+//!
+//! A WebAssembly model can't share memory with the host, so the ABI that
+//! native models use doesn't work for it. The host and the model exchange
+//! JSON instead:
+//!
+//! 1. The host calls [`ALLOC_FUNCTION_NAME`] to allocate a buffer in the
+//!    model's memory, and writes the parameters into it, as a JSON object.
+//! 2. The host calls [`EVALUATE_FUNCTION_NAME`] with that buffer. The model
+//!    computes its shape, and returns a buffer that contains an
+//!    [`Evaluation`]. The address and length of that buffer are packed into a
+//!    single 64-bit integer, address first.
+//! 3. The host reads the result, then calls [`FREE_FUNCTION_NAME`] to free
+//!    it.
+//!
+//! If the model panics, WebAssembly execution stops. The host can then call
+//! [`PANIC_FUNCTION_NAME`], to get the panic message in the same form as the
+//! result of the evaluation.
+//!
+//! The exports are generated by [`register_model!`], if the `wasm` feature is
+//! enabled.
+//!
+//! [`register_model!`]: crate::register_model
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::{self, Metadata},
+    Shape,
+};
+
+/// The name of the function that allocates a buffer in the model's memory
+pub const ALLOC_FUNCTION_NAME: &str = "fj_model_alloc";
+
+/// The name of the function that evaluates the model
+pub const EVALUATE_FUNCTION_NAME: &str = "fj_model_evaluate";
+
+/// The name of the function that frees a buffer in the model's memory
+pub const FREE_FUNCTION_NAME: &str = "fj_model_free";
+
+/// The name of the function that returns the message of the last panic
+pub const PANIC_FUNCTION_NAME: &str = "fj_model_panic";
+
+/// The version of the format that [`Evaluation`] is serialized in
+///
+/// This must be incremented, whenever [`Evaluation`] or the serialized form of
+/// [`Shape`] change in a way that hosts built against a different version
+/// can't read.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The initialization routine of a model, as passed to [`register_model!`]
+///
+/// [`register_model!`]: crate::register_model
+pub type InitFn = fn(&mut dyn models::Host) -> Result<Metadata, models::Error>;
+
+/// The result of evaluating a WebAssembly model, as returned to the host
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Evaluation {
+    /// The version of the format that the evaluation is serialized in
+    ///
+    /// See [`FORMAT_VERSION`]. The host must check this before deserializing
+    /// the rest, as the serialized form of [`Shape`] might differ between
+    /// versions.
+    pub format: u32,
+
+    /// The shape of the model, or an error message
+    pub result: Result<Shape, String>,
+}
+
+/// Evaluate a model, using parameters and returning the result as JSON
+///
+/// This is what [`EVALUATE_FUNCTION_NAME`] does, minus the handling of the
+/// model's memory.
+pub fn evaluate(init: InitFn, parameters: &[u8]) -> Vec<u8> {
+    let result = super::catch_panic(|| compute_shape(init, parameters))
+        .map_err(|err| models::Error::from(err.to_string()))
+        .and_then(|result| result)
+        .map_err(|err| error_message(&*err));
+
+    let evaluation = Evaluation {
+        format: FORMAT_VERSION,
+        result,
+    };
+
+    serde_json::to_vec(&evaluation).unwrap_or_else(|err| {
+        let evaluation = Evaluation {
+            format: evaluation.format,
+            result: Err(format!("Failed to serialize shape: {err}")),
+        };
+        serde_json::to_vec(&evaluation).expect("Serializing error can't fail")
+    })
+}
+
+fn compute_shape(
+    init: InitFn,
+    parameters: &[u8],
+) -> Result<Shape, models::Error> {
+    let parameters = serde_json::from_slice(parameters)?;
+
+    let mut host = Host {
+        parameters,
+        model: None,
+    };
+    init(&mut host)?;
+
+    let model = host.model.take().ok_or("No model was registered")?;
+    model.shape(&host)
+}
+
+/// Render an error, including all of its sources
+fn error_message(err: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = err.to_string();
+
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(&format!("\n\nCaused by:\n    {err}"));
+        source = err.source();
+    }
+
+    message
+}
+
+struct Host {
+    parameters: HashMap<String, String>,
+    model: Option<Box<dyn models::Model>>,
+}
+
+impl models::Host for Host {
+    fn register_boxed_model(&mut self, model: Box<dyn models::Model>) {
+        self.model = Some(model);
+    }
+}
+
+impl models::Context for Host {
+    fn get_argument(&self, name: &str) -> Option<&str> {
+        self.parameters.get(name).map(String::as_str)
+    }
+}
+
+/// The implementations of the functions that [`register_model!`] exports
+///
+/// [`register_model!`]: crate::register_model
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+pub mod exports {
+    use std::{cell::RefCell, panic, sync::Once};
+
+    use super::InitFn;
+
+    pub fn alloc(len: u32) -> u32 {
+        let buffer = vec![0u8; len as usize].into_boxed_slice();
+        Box::into_raw(buffer) as *mut u8 as u32
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` and `len` must describe a buffer returned by [`alloc`] or
+    /// [`evaluate`], which hasn't been freed yet.
+    pub unsafe fn free(ptr: u32, len: u32) {
+        drop(take(ptr, len));
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` and `len` must describe a buffer returned by [`alloc`], which
+    /// hasn't been freed yet. The buffer is freed by this function.
+    pub unsafe fn evaluate(init: InitFn, ptr: u32, len: u32) -> u64 {
+        PANIC_HOOK.call_once(|| {
+            let default_hook = panic::take_hook();
+
+            panic::set_hook(Box::new(move |info| {
+                let message = format!("Model {info}");
+                LAST_PANIC.with(|last| *last.borrow_mut() = Some(message));
+
+                default_hook(info);
+            }));
+        });
+
+        let parameters = take(ptr, len);
+        give(super::evaluate(init, &parameters))
+    }
+
+    pub fn last_panic() -> u64 {
+        let message = LAST_PANIC.with(|last| last.borrow_mut().take());
+        message
+            .map(|message| give(message.into_bytes()))
+            .unwrap_or(0)
+    }
+
+    static PANIC_HOOK: Once = Once::new();
+
+    thread_local! {
+        static LAST_PANIC: RefCell<Option<String>> =
+            const { RefCell::new(None) };
+    }
+
+    /// Hand a buffer to the host, packing its address and length
+    fn give(buffer: Vec<u8>) -> u64 {
+        let len = buffer.len() as u64;
+        let ptr = Box::into_raw(buffer.into_boxed_slice()) as *mut u8 as u64;
+
+        ptr << 32 | len
+    }
+
+    unsafe fn take(ptr: u32, len: u32) -> Box<[u8]> {
+        let slice =
+            std::ptr::slice_from_raw_parts_mut(ptr as *mut u8, len as usize);
+        Box::from_raw(slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        Context, Error, HostExt, Metadata, Model, ModelMetadata,
+    };
+
+    use super::Evaluation;
+
+    #[test]
+    fn evaluate_returns_shape_or_error() {
+        struct Square;
+
+        impl Model for Square {
+            fn shape(&self, ctx: &dyn Context) -> Result<crate::Shape, Error> {
+                let size: f64 =
+                    ctx.get_argument("size").ok_or("No size")?.parse()?;
+                Ok(crate::Sketch::from_points(vec![
+                    [0., 0.],
+                    [size, 0.],
+                    [size, size],
+                ])
+                .into())
+            }
+
+            fn metadata(&self) -> ModelMetadata {
+                ModelMetadata::new("square")
+            }
+        }
+
+        let evaluate = |parameters: &str| -> Evaluation {
+            let result = super::evaluate(
+                |host| {
+                    host.register_model(Square);
+                    Ok(Metadata::new("square", "1.0.0"))
+                },
+                parameters.as_bytes(),
+            );
+            serde_json::from_slice(&result).unwrap()
+        };
+
+        let evaluation = evaluate(r#"{"size": "2"}"#);
+        assert_eq!(evaluation.format, super::FORMAT_VERSION);
+        assert!(evaluation.result.is_ok());
+
+        let evaluation = evaluate(r#"{"size": "large"}"#);
+        assert_eq!(evaluation.result.unwrap_err(), "invalid float literal");
+
+        let evaluation = evaluate("{}");
+        assert_eq!(evaluation.result.unwrap_err(), "No size");
+    }
+}
@@ -1,16 +1,34 @@
-use std::f64::consts::{PI, TAU};
+use std::{
+    f64::consts::{PI, TAU},
+    hash::{Hash, Hasher},
+};
 
 // One gon in radians
 const GON_RAD: f64 = PI / 200.;
 
 /// An angle
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Angle {
     // The value of the angle in radians
     rad: f64,
 }
 
+// Angles are compared by the bits of their value, like they are hashed. This
+// keeps `PartialEq` consistent with `Hash`, which shapes rely on to be usable
+// as cache keys.
+impl PartialEq for Angle {
+    fn eq(&self, other: &Self) -> bool {
+        self.rad.to_bits() == other.rad.to_bits()
+    }
+}
+
+impl Hash for Angle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rad.to_bits().hash(state);
+    }
+}
+
 impl Angle {
     /// Create a new angle specified in radians
     pub fn from_rad(rad: f64) -> Self {
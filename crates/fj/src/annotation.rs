@@ -0,0 +1,262 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{abi::ffi_safe, Shape};
+
+/// A 3-dimensional shape with annotations attached
+///
+/// Annotations don't affect the geometry of the shape. They communicate
+/// information about the shape, like the size of an important feature, and are
+/// displayed by the viewer and written to export formats that support them.
+///
+/// Annotations are given in the coordinates of the shape they are attached to.
+/// Any transformation that is applied to the shape, applies to its annotations
+/// too.
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+/// use fj::syntax::*;
+///
+/// // `shape` can be anything that converts to `fj::Shape`
+/// let annotated = shape
+///     .annotate(fj::Dimension::new("width", [0., 0., 0.], [1., 0., 0.]))
+///     .with_annotation(fj::Label::new("corner", [0., 1., 0.]));
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Annotated {
+    shape: Shape,
+    annotations: ffi_safe::Vec<Annotation>,
+}
+
+impl Annotated {
+    /// Create an `Annotated` from a shape, without any annotations
+    pub fn from_shape(shape: Shape) -> Self {
+        Self {
+            shape,
+            annotations: Vec::new().into(),
+        }
+    }
+
+    /// Attach another annotation to the shape
+    pub fn with_annotation(
+        mut self,
+        annotation: impl Into<Annotation>,
+    ) -> Self {
+        let mut annotations: Vec<_> = self.annotations.into();
+        annotations.push(annotation.into());
+
+        self.annotations = annotations.into();
+        self
+    }
+
+    /// Access the shape that the annotations are attached to
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the annotations
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+}
+
+impl From<Annotated> for Shape {
+    fn from(shape: Annotated) -> Self {
+        Self::Annotated(Box::new(shape))
+    }
+}
+
+/// An annotation that can be attached to a shape
+///
+/// See [`Annotated`].
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum Annotation {
+    /// A named dimension
+    Dimension(Dimension),
+
+    /// A text label
+    Label(Label),
+
+    /// A named reference point
+    ReferencePoint(ReferencePoint),
+}
+
+/// A named dimension, measured between two points
+///
+/// The value of the dimension is the distance between the two points, after any
+/// transformations have been applied.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Dimension {
+    name: ffi_safe::String,
+    from: [f64; 3],
+    to: [f64; 3],
+}
+
+impl Dimension {
+    fn key(&self) -> (&ffi_safe::String, [u64; 3], [u64; 3]) {
+        (
+            &self.name,
+            self.from.map(f64::to_bits),
+            self.to.map(f64::to_bits),
+        )
+    }
+}
+
+impl PartialEq for Dimension {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for Dimension {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Dimension {
+    /// Create a `Dimension` between the points `from` and `to`
+    pub fn new(name: impl Into<String>, from: [f64; 3], to: [f64; 3]) -> Self {
+        Self {
+            name: name.into().into(),
+            from,
+            to,
+        }
+    }
+
+    /// Access the name of the dimension
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Access the point the dimension is measured from
+    pub fn from(&self) -> [f64; 3] {
+        self.from
+    }
+
+    /// Access the point the dimension is measured to
+    pub fn to(&self) -> [f64; 3] {
+        self.to
+    }
+}
+
+impl From<Dimension> for Annotation {
+    fn from(annotation: Dimension) -> Self {
+        Self::Dimension(annotation)
+    }
+}
+
+/// A text label, attached to a point
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Label {
+    text: ffi_safe::String,
+    position: [f64; 3],
+}
+
+impl Label {
+    fn key(&self) -> (&ffi_safe::String, [u64; 3]) {
+        (&self.text, self.position.map(f64::to_bits))
+    }
+}
+
+impl PartialEq for Label {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for Label {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Label {
+    /// Create a `Label` at the given position
+    pub fn new(text: impl Into<String>, position: [f64; 3]) -> Self {
+        Self {
+            text: text.into().into(),
+            position,
+        }
+    }
+
+    /// Access the text of the label
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Access the point the label is attached to
+    pub fn position(&self) -> [f64; 3] {
+        self.position
+    }
+}
+
+impl From<Label> for Annotation {
+    fn from(annotation: Label) -> Self {
+        Self::Label(annotation)
+    }
+}
+
+/// A named reference point
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct ReferencePoint {
+    name: ffi_safe::String,
+    position: [f64; 3],
+}
+
+impl ReferencePoint {
+    fn key(&self) -> (&ffi_safe::String, [u64; 3]) {
+        (&self.name, self.position.map(f64::to_bits))
+    }
+}
+
+impl PartialEq for ReferencePoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for ReferencePoint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl ReferencePoint {
+    /// Create a `ReferencePoint` at the given position
+    pub fn new(name: impl Into<String>, position: [f64; 3]) -> Self {
+        Self {
+            name: name.into().into(),
+            position,
+        }
+    }
+
+    /// Access the name of the reference point
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Access the position of the reference point
+    pub fn position(&self) -> [f64; 3] {
+        self.position
+    }
+}
+
+impl From<ReferencePoint> for Annotation {
+    fn from(annotation: ReferencePoint) -> Self {
+        Self::ReferencePoint(annotation)
+    }
+}
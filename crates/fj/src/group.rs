@@ -21,7 +21,7 @@ use crate::Shape;
 /// # Limitations
 ///
 /// Whether the shapes in the group touch or overlap is not currently checked.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Group {
@@ -0,0 +1,378 @@
+//! Import of ASCII DXF files
+
+use std::f64::consts::PI;
+
+use super::{arc_points, Import, ImportError, Piece, Unit};
+
+pub(super) fn parse(
+    input: &str,
+    import: &Import,
+) -> Result<Vec<Piece>, ImportError> {
+    if input.starts_with("AutoCAD Binary DXF") {
+        return Err(ImportError::Parse(String::from(
+            "Binary DXF files are not supported",
+        )));
+    }
+
+    let groups = groups(input)?;
+    let (unit, entities) = sections(&groups);
+
+    let unit = match import.unit {
+        Some(unit) => unit,
+        None => unit
+            .map(dxf_unit)
+            .transpose()?
+            .flatten()
+            .unwrap_or(Unit::Millimeter),
+    };
+    let scale = unit.in_millimeters();
+    let tolerance = import.tolerance / scale;
+
+    let mut pieces = Vec::new();
+    let mut entities = entities.into_iter();
+
+    while let Some(entity) = entities.next() {
+        // The vertices of polylines follow them as separate entities.
+        let vertices = if entity.kind == "POLYLINE" {
+            entities
+                .by_ref()
+                .take_while(|entity| entity.kind != "SEQEND")
+                .filter(|entity| entity.kind == "VERTEX")
+                .map(|vertex| {
+                    Ok(Vertex {
+                        point: vertex.point(10)?,
+                        bulge: vertex.value(42).unwrap_or(0.),
+                    })
+                })
+                .collect::<Result<Vec<_>, ImportError>>()?
+        } else {
+            Vec::new()
+        };
+
+        let layer = entity.layer();
+        if !import.includes_layer(|name| name.eq_ignore_ascii_case(layer)) {
+            continue;
+        }
+
+        let piece = match entity.kind {
+            "LINE" => Piece::Path {
+                points: vec![entity.point(10)?, entity.point(11)?],
+                closed: false,
+            },
+            "CIRCLE" => Piece::Circle {
+                center: entity.point(10)?,
+                radius: entity.value(40)?,
+            },
+            "ARC" => {
+                let [start, end] =
+                    [entity.value(50)?, entity.value(51)?].map(f64::to_radians);
+
+                // Arcs run counterclockwise from start to end.
+                let sweep = (end - start).rem_euclid(2. * PI);
+                let sweep = if sweep == 0. { 2. * PI } else { sweep };
+
+                Piece::Path {
+                    points: arc_points(
+                        entity.point(10)?,
+                        entity.value(40)?,
+                        start,
+                        sweep,
+                        tolerance,
+                    ),
+                    closed: false,
+                }
+            }
+            "LWPOLYLINE" => {
+                polyline(&entity.vertices()?, entity.is_closed(), tolerance)
+            }
+            "POLYLINE" => polyline(&vertices, entity.is_closed(), tolerance),
+            _ => continue,
+        };
+
+        pieces.push(piece);
+    }
+
+    Ok(pieces
+        .into_iter()
+        .map(|piece| scaled(piece, scale))
+        .collect())
+}
+
+/// Split the file into its group codes and values
+fn groups(input: &str) -> Result<Vec<(i32, &str)>, ImportError> {
+    let mut lines = input.lines().map(str::trim).enumerate();
+    let mut groups = Vec::new();
+
+    while let Some((i, code)) = lines.next() {
+        // Some files end with empty lines.
+        if code.is_empty() {
+            continue;
+        }
+
+        let code = code.parse().map_err(|_| {
+            ImportError::Parse(format!(
+                "Invalid group code `{code}` in line {}",
+                i + 1
+            ))
+        })?;
+        let (_, value) = lines.next().ok_or_else(|| {
+            ImportError::Parse(String::from("Unexpected end of file"))
+        })?;
+
+        groups.push((code, value));
+    }
+
+    Ok(groups)
+}
+
+/// Find the unit in the header, and collect the entities
+///
+/// Entities in blocks are ignored, as are references to those blocks.
+fn sections<'a>(
+    groups: &'a [(i32, &'a str)],
+) -> (Option<&'a str>, Vec<Entity<'a>>) {
+    let mut unit = None;
+    let mut entities = Vec::new();
+
+    let mut section = None;
+    let mut groups = groups.iter().copied().peekable();
+
+    while let Some((code, value)) = groups.next() {
+        match (section, code, value) {
+            (_, 0, "SECTION") => {
+                section = groups.next_if(|&(code, _)| code == 2).map(|g| g.1);
+            }
+            (_, 0, "ENDSEC") => section = None,
+            (Some("HEADER"), 9, "$INSUNITS") => {
+                if let Some((_, value)) =
+                    groups.next_if(|&(code, _)| code == 70)
+                {
+                    unit = Some(value);
+                }
+            }
+            (Some("ENTITIES"), 0, kind) => {
+                let mut entity = Entity {
+                    kind,
+                    groups: Vec::new(),
+                };
+                while let Some(group) = groups.next_if(|&(code, _)| code != 0) {
+                    entity.groups.push(group);
+                }
+
+                entities.push(entity);
+            }
+            _ => {}
+        }
+    }
+
+    (unit, entities)
+}
+
+/// Interpret the value of the `$INSUNITS` header variable
+fn dxf_unit(value: &str) -> Result<Option<Unit>, ImportError> {
+    let unit = match value {
+        "0" => None,
+        "1" => Some(Unit::Inch),
+        "2" => Some(Unit::Foot),
+        "4" => Some(Unit::Millimeter),
+        "5" => Some(Unit::Centimeter),
+        "6" => Some(Unit::Meter),
+        value => {
+            return Err(ImportError::UnknownUnit(format!(
+                "DXF unit code {value}"
+            )))
+        }
+    };
+
+    Ok(unit)
+}
+
+struct Entity<'a> {
+    kind: &'a str,
+    groups: Vec<(i32, &'a str)>,
+}
+
+impl Entity<'_> {
+    fn layer(&self) -> &str {
+        self.groups
+            .iter()
+            .find(|(code, _)| *code == 8)
+            .map(|(_, layer)| *layer)
+            .unwrap_or("0")
+    }
+
+    fn value(&self, code: i32) -> Result<f64, ImportError> {
+        let value = self
+            .groups
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| {
+                ImportError::Parse(format!(
+                    "{} without group code {code}",
+                    self.kind
+                ))
+            })?;
+
+        number(value)
+    }
+
+    /// Read the point whose x-coordinate has the given group code
+    fn point(&self, code: i32) -> Result<[f64; 2], ImportError> {
+        Ok([self.value(code)?, self.value(code + 10)?])
+    }
+
+    fn is_closed(&self) -> bool {
+        let flags = self.value(70).unwrap_or(0.) as i32;
+        flags & 1 != 0
+    }
+
+    /// Read the vertices of a lightweight polyline
+    fn vertices(&self) -> Result<Vec<Vertex>, ImportError> {
+        let mut vertices: Vec<Vertex> = Vec::new();
+
+        for &(code, value) in &self.groups {
+            match (code, vertices.last_mut()) {
+                (10, _) => vertices.push(Vertex {
+                    point: [number(value)?, 0.],
+                    bulge: 0.,
+                }),
+                (20, Some(vertex)) => vertex.point[1] = number(value)?,
+                (42, Some(vertex)) => vertex.bulge = number(value)?,
+                _ => {}
+            }
+        }
+
+        Ok(vertices)
+    }
+}
+
+struct Vertex {
+    point: [f64; 2],
+
+    /// The tangent of a quarter of the angle of the arc to the next vertex
+    ///
+    /// Zero means a straight line. Positive values mean the arc runs
+    /// counterclockwise.
+    bulge: f64,
+}
+
+fn polyline(vertices: &[Vertex], closed: bool, tolerance: f64) -> Piece {
+    let mut points = Vec::new();
+
+    for (i, vertex) in vertices.iter().enumerate() {
+        let next = match vertices.get(i + 1) {
+            Some(next) => next,
+            None if closed => &vertices[0],
+            None => {
+                points.push(vertex.point);
+                break;
+            }
+        };
+
+        if vertex.bulge == 0. {
+            points.push(vertex.point);
+            continue;
+        }
+
+        let [a, b] = [vertex.point, next.point];
+        let chord = [b[0] - a[0], b[1] - a[1]];
+        let length = chord[0].hypot(chord[1]);
+        if length == 0. {
+            continue;
+        }
+
+        // The center is on the perpendicular bisector of the chord, to the
+        // left of it for arcs that run counterclockwise and are less than
+        // half a circle.
+        let angle = 4. * vertex.bulge.atan();
+        let offset = length / 2. / (angle / 2.).tan();
+        let center = [
+            (a[0] + b[0]) / 2. - chord[1] / length * offset,
+            (a[1] + b[1]) / 2. + chord[0] / length * offset,
+        ];
+        let radius = (a[0] - center[0]).hypot(a[1] - center[1]);
+        let start = (a[1] - center[1]).atan2(a[0] - center[0]);
+
+        // Use the exact vertex, instead of the first point of the arc.
+        let arc = arc_points(center, radius, start, angle, tolerance);
+        points.push(vertex.point);
+        points.extend(&arc[1..arc.len() - 1]);
+    }
+
+    Piece::Path { points, closed }
+}
+
+fn scaled(piece: Piece, scale: f64) -> Piece {
+    let scale_point = |[x, y]: [f64; 2]| [x * scale, y * scale];
+
+    match piece {
+        Piece::Circle { center, radius } => Piece::Circle {
+            center: scale_point(center),
+            radius: radius * scale,
+        },
+        Piece::Path { points, closed } => Piece::Path {
+            points: points.into_iter().map(scale_point).collect(),
+            closed,
+        },
+    }
+}
+
+fn number(value: &str) -> Result<f64, ImportError> {
+    value
+        .parse()
+        .map_err(|_| ImportError::Parse(format!("Invalid number `{value}`")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::{Import, Piece, Unit};
+
+    use super::parse;
+
+    #[test]
+    fn entities() {
+        let dxf = "\
+  0\nSECTION\n  2\nHEADER\n  9\n$INSUNITS\n 70\n1\n  0\nENDSEC\n\
+  0\nSECTION\n  2\nENTITIES\n\
+  0\nLINE\n  8\ncut\n 10\n0.0\n 20\n0.0\n 11\n1.0\n 21\n0.0\n\
+  0\nCIRCLE\n  8\nCUT\n 10\n1.0\n 20\n2.0\n 40\n0.5\n\
+  0\nLWPOLYLINE\n  8\nengrave\n 90\n2\n 70\n1\n\
+ 10\n0.0\n 20\n0.0\n 42\n1.0\n 10\n2.0\n 20\n0.0\n\
+  0\nENDSEC\n  0\nEOF\n";
+
+        // The unit is inches, and only one layer is selected.
+        let pieces = parse(dxf, &Import::new().with_layer("Cut")).unwrap();
+        assert_eq!(
+            pieces,
+            [
+                Piece::Path {
+                    points: vec![[0., 0.], [25.4, 0.]],
+                    closed: false,
+                },
+                Piece::Circle {
+                    center: [25.4, 50.8],
+                    radius: 12.7,
+                },
+            ]
+        );
+
+        // A bulge of 1 is a half circle. The polyline is closed, so the
+        // second vertex has a straight line back to the first.
+        let import = Import::new()
+            .with_layer("engrave")
+            .with_unit(Unit::Millimeter)
+            .with_tolerance(0.1);
+        let pieces = parse(dxf, &import).unwrap();
+        let [Piece::Path { points, closed: true }] = pieces.as_slice() else {
+            panic!("Expected closed path, got {pieces:?}");
+        };
+        for point in points {
+            let distance = (point[0] - 1.).hypot(point[1]);
+            assert!((distance - 1.).abs() < 1e-9);
+            assert!(point[1] <= 1e-9);
+        }
+        assert_eq!(points.first(), Some(&[0., 0.]));
+        assert!(points.len() > 2);
+    }
+}
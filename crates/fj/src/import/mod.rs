@@ -0,0 +1,550 @@
+//! Import of 2D outlines from DXF and SVG files
+//!
+//! See [`Import`].
+
+mod dxf;
+mod svg;
+
+use std::{error, f64::consts::PI, fmt, fs, io, path::Path};
+
+use crate::{Circle, Difference2d, Shape2d, Sketch, Union2d};
+
+/// Import the outlines in a DXF or SVG file as a 2D shape
+///
+/// This is a shorthand for `Import::new().load(path)`. See [`Import`] for the
+/// details, and for how to adjust the import.
+pub fn import(path: impl AsRef<Path>) -> Result<Shape2d, ImportError> {
+    Import::new().load(path)
+}
+
+/// Settings for importing the outlines in a DXF or SVG file
+///
+/// The closed outlines in the file become sketches. Outlines that lie within
+/// other outlines are subtracted from them, so the drawing of a plate with
+/// holes becomes exactly that. Lines and curves that end where others start,
+/// are joined into outlines.
+///
+/// Lines, arcs, circles, and polylines are imported from DXF files. Paths,
+/// lines, rectangles, circles, ellipses, polylines, and polygons are imported
+/// from SVG files. Everything else, like text or dimensions, is ignored.
+///
+/// Sketches consist of straight lines, or are circles around the origin.
+/// Everything else is approximated by straight lines, within the tolerance.
+///
+/// # Units
+///
+/// Models are measured in millimeters, and imported outlines are converted
+/// accordingly. DXF files declare their unit in the `$INSUNITS` header
+/// variable. Files without a unit are assumed to be in millimeters. SVG files
+/// are measured in pixels (1/96 inch), unless their `width` and `viewBox`
+/// attributes define another unit. Use [`Import::with_unit`], if the unit of
+/// the file is missing or wrong.
+///
+/// The y-axis of SVG files points down. Imported outlines are mirrored, so
+/// they keep looking the same way.
+///
+/// # Examples
+///
+/// ``` rust no_run
+/// use fj::syntax::*;
+///
+/// let profile = fj::import::Import::new()
+///     .with_layer("outline")
+///     .load(concat!(env!("CARGO_MANIFEST_DIR"), "/profile.dxf"))?;
+/// let extrusion = profile.sweep([0., 0., 5.]);
+/// # Ok::<(), fj::import::ImportError>(())
+/// ```
+///
+/// Relative paths are resolved against the working directory of the Fornjot
+/// application, not the model. As shown above, `CARGO_MANIFEST_DIR` can be
+/// used to refer to files next to the model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Import {
+    layers: Vec<String>,
+    unit: Option<Unit>,
+    tolerance: f64,
+}
+
+impl Import {
+    /// Create an `Import` with the default settings
+    ///
+    /// By default, all layers are imported, the unit is read from the file,
+    /// and the tolerance is 0.01 millimeters.
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            unit: None,
+            tolerance: 0.01,
+        }
+    }
+
+    /// Only import the given layer
+    ///
+    /// Can be called multiple times, to import multiple layers. Layers of DXF
+    /// files are matched by name, ignoring case. Layers of SVG files are groups
+    /// (`g` elements), matched by their Inkscape label or their `id`.
+    pub fn with_layer(mut self, layer: impl Into<String>) -> Self {
+        self.layers.push(layer.into());
+        self
+    }
+
+    /// Set the unit of the file, overriding what the file declares
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Set the tolerance, in millimeters
+    ///
+    /// The tolerance is how far the approximation of arcs and curves may
+    /// deviate from the original, and how far apart the ends of lines may be,
+    /// to still be joined.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `tolerance` is not positive.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        assert!(tolerance > 0., "Tolerance must be positive");
+
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Import the file at the given path
+    ///
+    /// The format is chosen by the case insensitive file extension, which must
+    /// be `dxf` or `svg`.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<Shape2d, ImportError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("dxf") => self.parse_dxf(&fs::read_to_string(path)?),
+            Some("svg") => self.parse_svg(&fs::read_to_string(path)?),
+            _ => Err(ImportError::UnknownFormat(path.display().to_string())),
+        }
+    }
+
+    /// Import the content of an ASCII DXF file
+    pub fn parse_dxf(&self, dxf: &str) -> Result<Shape2d, ImportError> {
+        let pieces = dxf::parse(dxf, self)?;
+        into_shape(pieces, self.tolerance)
+    }
+
+    /// Import the content of an SVG file
+    pub fn parse_svg(&self, svg: &str) -> Result<Shape2d, ImportError> {
+        let pieces = svg::parse(svg, self)?;
+        into_shape(pieces, self.tolerance)
+    }
+
+    fn includes_layer(&self, is_layer: impl Fn(&str) -> bool) -> bool {
+        self.layers.is_empty()
+            || self.layers.iter().any(|layer| is_layer(layer))
+    }
+}
+
+impl Default for Import {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A unit of length, that imported files can be measured in
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Unit {
+    /// Millimeters
+    Millimeter,
+
+    /// Centimeters
+    Centimeter,
+
+    /// Meters
+    Meter,
+
+    /// Inches
+    Inch,
+
+    /// Feet
+    Foot,
+
+    /// Points (1/72 inch)
+    Point,
+
+    /// Pixels (1/96 inch), as used by SVG
+    Pixel,
+}
+
+impl Unit {
+    /// The length of the unit, in millimeters
+    pub fn in_millimeters(self) -> f64 {
+        match self {
+            Self::Millimeter => 1.,
+            Self::Centimeter => 10.,
+            Self::Meter => 1000.,
+            Self::Inch => 25.4,
+            Self::Foot => 304.8,
+            Self::Point => 25.4 / 72.,
+            Self::Pixel => 25.4 / 96.,
+        }
+    }
+}
+
+/// An error that can occur while importing a file
+#[derive(Debug)]
+pub enum ImportError {
+    /// The file could not be read
+    Io(io::Error),
+
+    /// The file extension is neither `dxf` nor `svg`
+    UnknownFormat(String),
+
+    /// The file is not valid DXF or SVG
+    Parse(String),
+
+    /// The file declares a unit that is not supported
+    UnknownUnit(String),
+
+    /// An outline is not closed
+    ///
+    /// Contains the point, at which the outline ends.
+    OpenOutline([f64; 2]),
+
+    /// The file, or the selected layers, contain no outlines
+    Empty,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Error reading file: {err}"),
+            Self::UnknownFormat(path) => {
+                write!(f, "Unknown format of `{path}`, expected DXF or SVG")
+            }
+            Self::Parse(message) => write!(f, "Error parsing file: {message}"),
+            Self::UnknownUnit(unit) => write!(
+                f,
+                "Unsupported unit `{unit}`; specify the unit of the file instead"
+            ),
+            Self::OpenOutline([x, y]) => {
+                write!(f, "Outline is not closed; it ends at [{x}, {y}]")
+            }
+            Self::Empty => write!(f, "No outlines found"),
+        }
+    }
+}
+
+impl error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A piece of an outline, as read from a file, in millimeters
+#[derive(Debug, PartialEq)]
+enum Piece {
+    Circle { center: [f64; 2], radius: f64 },
+    Path { points: Vec<[f64; 2]>, closed: bool },
+}
+
+/// Approximate an arc by points, including the start and end point
+///
+/// Angles are in radians. A positive `sweep` runs counterclockwise.
+fn arc_points(
+    center: [f64; 2],
+    radius: f64,
+    start: f64,
+    sweep: f64,
+    tolerance: f64,
+) -> Vec<[f64; 2]> {
+    // This is the angle of a segment, that deviates from the arc by exactly
+    // the tolerance.
+    let max_angle = if tolerance < radius {
+        2. * (1. - tolerance / radius).acos()
+    } else {
+        PI
+    };
+    let n = (sweep.abs() / max_angle).ceil().max(1.) as usize;
+
+    (0..=n)
+        .map(|i| {
+            let angle = start + sweep * i as f64 / n as f64;
+            let (sin, cos) = angle.sin_cos();
+            [center[0] + radius * cos, center[1] + radius * sin]
+        })
+        .collect()
+}
+
+/// Join the pieces into outlines, and turn those into a shape
+fn into_shape(
+    pieces: Vec<Piece>,
+    tolerance: f64,
+) -> Result<Shape2d, ImportError> {
+    let mut outlines = join(pieces, tolerance)?;
+
+    // Sort from largest to smallest, so the outlines that contain an outline
+    // come before it.
+    outlines.sort_by(|a, b| b.area().total_cmp(&a.area()));
+
+    // The smallest outline that contains each outline. Outlines within
+    // outlines are holes, outlines within holes are not.
+    let mut parents: Vec<Option<usize>> = Vec::new();
+    let mut is_hole: Vec<bool> = Vec::new();
+    for (i, outline) in outlines.iter().enumerate() {
+        let point = outline.point();
+        let parent = (0..i).rev().find(|&j| outlines[j].contains(point));
+
+        parents.push(parent);
+        is_hole.push(parent.map(|parent| !is_hole[parent]).unwrap_or(false));
+    }
+
+    let shapes = (0..outlines.len()).filter(|&i| !is_hole[i]).map(|i| {
+        let sketch = outlines[i].to_sketch(tolerance);
+
+        (0..outlines.len())
+            .filter(|&hole| parents[hole] == Some(i))
+            .fold(Shape2d::from(sketch), |shape, hole| {
+                let hole = outlines[hole].to_sketch(tolerance).into();
+                Difference2d::from_shapes([shape, hole]).into()
+            })
+    });
+
+    shapes
+        .reduce(|a, b| Union2d::from_shapes([a, b]).into())
+        .ok_or(ImportError::Empty)
+}
+
+/// Join the paths, whose ends are within the tolerance, into closed outlines
+fn join(
+    pieces: Vec<Piece>,
+    tolerance: f64,
+) -> Result<Vec<Outline>, ImportError> {
+    let is_close = |a: [f64; 2], b: [f64; 2]| distance(a, b) <= tolerance;
+
+    let mut outlines = Vec::new();
+    let mut open = Vec::new();
+
+    for piece in pieces {
+        match piece {
+            Piece::Circle { center, radius } => {
+                outlines.push(Outline::Circle { center, radius });
+            }
+            Piece::Path { points, closed } => {
+                let is_closed = match points.as_slice() {
+                    [first, .., last] => closed || is_close(*first, *last),
+                    _ => continue,
+                };
+
+                if is_closed {
+                    outlines.extend(Outline::polygon(points, tolerance));
+                } else {
+                    open.push(points);
+                }
+            }
+        }
+    }
+
+    while let Some(mut points) = open.pop() {
+        while let (Some(&first), Some(&end)) = (points.first(), points.last()) {
+            if points.len() > 2 && is_close(first, end) {
+                break;
+            }
+
+            let next = open.iter().position(|next| {
+                let (Some(&start), Some(&last)) = (next.first(), next.last())
+                else {
+                    return false;
+                };
+                is_close(start, end) || is_close(last, end)
+            });
+            let Some(next) = next else {
+                return Err(ImportError::OpenOutline(end));
+            };
+
+            let mut next = open.swap_remove(next);
+            if !is_close(next[0], end) {
+                next.reverse();
+            }
+            points.extend(next.into_iter().skip(1));
+        }
+
+        outlines.extend(Outline::polygon(points, tolerance));
+    }
+
+    Ok(outlines)
+}
+
+/// A closed outline
+enum Outline {
+    Circle { center: [f64; 2], radius: f64 },
+    Polygon(Vec<[f64; 2]>),
+}
+
+impl Outline {
+    /// Create a polygon, dropping points that are too close to the previous
+    ///
+    /// Returns `None`, if the polygon is degenerate.
+    fn polygon(points: Vec<[f64; 2]>, tolerance: f64) -> Option<Self> {
+        let mut polygon: Vec<[f64; 2]> = Vec::new();
+        for point in points {
+            match polygon.last() {
+                Some(&last) if distance(last, point) <= tolerance => {}
+                _ => polygon.push(point),
+            }
+        }
+
+        // The outline is closed implicitly.
+        while polygon.len() > 1
+            && distance(polygon[0], polygon[polygon.len() - 1]) <= tolerance
+        {
+            polygon.pop();
+        }
+
+        if polygon.len() < 3 {
+            return None;
+        }
+
+        Some(Self::Polygon(polygon))
+    }
+
+    /// The signed area, which is positive for counterclockwise polygons
+    fn signed_area(&self) -> f64 {
+        match self {
+            Self::Circle { radius, .. } => PI * radius * radius,
+            Self::Polygon(points) => {
+                let mut area = 0.;
+                for (i, a) in points.iter().enumerate() {
+                    let b = points[(i + 1) % points.len()];
+                    area += a[0] * b[1] - b[0] * a[1];
+                }
+                area / 2.
+            }
+        }
+    }
+
+    fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// A point on the outline
+    fn point(&self) -> [f64; 2] {
+        match self {
+            Self::Circle { center, radius } => [center[0] + radius, center[1]],
+            Self::Polygon(points) => points[0],
+        }
+    }
+
+    fn contains(&self, point: [f64; 2]) -> bool {
+        match self {
+            Self::Circle { center, radius } => {
+                distance(*center, point) < *radius
+            }
+            Self::Polygon(points) => {
+                // Count how often a ray in positive x-direction crosses the
+                // polygon.
+                let mut inside = false;
+                for (i, a) in points.iter().enumerate() {
+                    let b = points[(i + 1) % points.len()];
+                    if (a[1] > point[1]) != (b[1] > point[1]) {
+                        let x = a[0]
+                            + (point[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+                        if point[0] < x {
+                            inside = !inside;
+                        }
+                    }
+                }
+                inside
+            }
+        }
+    }
+
+    fn to_sketch(&self, tolerance: f64) -> Sketch {
+        match self {
+            Self::Circle { center, radius }
+                if distance(*center, [0., 0.]) <= tolerance =>
+            {
+                Sketch::from_circle(Circle::from_radius(*radius))
+            }
+            Self::Circle { center, radius } => {
+                let mut points =
+                    arc_points(*center, *radius, 0., 2. * PI, tolerance);
+                points.pop();
+                Sketch::from_points(points)
+            }
+            Self::Polygon(points) => {
+                let mut points = points.clone();
+                if self.signed_area() < 0. {
+                    points.reverse();
+                }
+                Sketch::from_points(points)
+            }
+        }
+    }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (a[0] - b[0]).hypot(a[1] - b[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Difference2d, Shape2d, Sketch, Union2d};
+
+    use super::{into_shape, Piece};
+
+    #[test]
+    fn nested_outlines() {
+        let square = |min: f64, max: f64| Piece::Path {
+            points: vec![[min, min], [max, min], [max, max], [min, max]],
+            closed: true,
+        };
+        let sketch = |min: f64, max: f64| {
+            Shape2d::from(Sketch::from_points(vec![
+                [min, min],
+                [max, min],
+                [max, max],
+                [min, max],
+            ]))
+        };
+
+        // A square with a square hole, with another square in that hole. The
+        // pieces of the outer square are neither joined nor ordered.
+        let pieces = vec![
+            square(2., 3.),
+            Piece::Path {
+                points: vec![[0., 5.], [0., 0.], [5., 0.]],
+                closed: false,
+            },
+            square(1., 4.),
+            Piece::Path {
+                points: vec![[5., 5.], [5., 0.]],
+                closed: false,
+            },
+            Piece::Path {
+                points: vec![[5., 5.], [0., 5.]],
+                closed: false,
+            },
+        ];
+
+        let outer = Shape2d::from(Sketch::from_points(vec![
+            [5., 5.],
+            [0., 5.],
+            [0., 0.],
+            [5., 0.],
+        ]));
+        let plate = Difference2d::from_shapes([outer, sketch(1., 4.)]);
+        let expected = Union2d::from_shapes([plate.into(), sketch(2., 3.)]);
+
+        assert_eq!(into_shape(pieces, 0.01).unwrap(), expected.into());
+    }
+}
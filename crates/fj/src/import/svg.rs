@@ -0,0 +1,988 @@
+//! Import of SVG files
+
+use std::f64::consts::PI;
+
+use quick_xml::{events::Event, Reader};
+
+use super::{arc_points, Import, ImportError, Piece, Unit};
+
+pub(super) fn parse(
+    input: &str,
+    import: &Import,
+) -> Result<Vec<Piece>, ImportError> {
+    let mut pieces = Vec::new();
+
+    // The context of each element that hasn't been closed yet.
+    let mut stack: Vec<Context> = Vec::new();
+
+    for tag in tags(input)? {
+        let Tag::Start {
+            name,
+            attributes,
+            is_empty,
+        } = tag
+        else {
+            stack.pop();
+            continue;
+        };
+
+        let context = match stack.last() {
+            Some(parent) => parent.child(&name, &attributes, import)?,
+            None => Context::root(&name, &attributes, import)?,
+        };
+
+        if context.is_drawn() {
+            let tolerance = import.tolerance;
+            pieces.extend(element(&name, &attributes, &context, tolerance)?);
+        }
+
+        if !is_empty {
+            stack.push(context);
+        }
+    }
+
+    Ok(pieces)
+}
+
+/// The context that an element is drawn in
+struct Context {
+    /// The transformation from the element's coordinates to millimeters
+    transform: Matrix,
+
+    /// Whether the element is part of a selected layer
+    is_in_layer: bool,
+
+    /// Whether the element is not rendered
+    is_hidden: bool,
+}
+
+impl Context {
+    fn root(
+        name: &str,
+        attributes: &Attributes,
+        import: &Import,
+    ) -> Result<Self, ImportError> {
+        if name != "svg" {
+            return Err(ImportError::Parse(format!(
+                "Expected `svg` element, found `{name}`"
+            )));
+        }
+
+        let scale = match import.unit {
+            Some(unit) => unit.in_millimeters(),
+            None => user_unit(attributes)?,
+        };
+        let [x, y, ..] = view_box(attributes).unwrap_or_default();
+
+        // Mirror the y-axis, which points down in SVG.
+        let transform = Matrix([scale, 0., 0., -scale, -x * scale, y * scale]);
+
+        Ok(Self {
+            transform,
+            is_in_layer: import.layers.is_empty(),
+            is_hidden: false,
+        })
+    }
+
+    fn child(
+        &self,
+        name: &str,
+        attributes: &Attributes,
+        import: &Import,
+    ) -> Result<Self, ImportError> {
+        let transform = match attribute(attributes, "transform") {
+            Some(transform) => self.transform.then(parse_transform(transform)?),
+            None => self.transform,
+        };
+
+        let is_layer = name == "g"
+            && import.layers.iter().any(|layer| {
+                ["inkscape:label", "id"]
+                    .into_iter()
+                    .any(|name| attribute(attributes, name) == Some(layer))
+            });
+
+        let style = attribute(attributes, "style").unwrap_or_default();
+        let is_hidden = self.is_hidden
+            || NOT_RENDERED.contains(&name)
+            || attribute(attributes, "display") == Some("none")
+            || style.replace(' ', "").contains("display:none");
+
+        Ok(Self {
+            transform,
+            is_in_layer: self.is_in_layer || is_layer,
+            is_hidden,
+        })
+    }
+
+    fn is_drawn(&self) -> bool {
+        self.is_in_layer && !self.is_hidden
+    }
+}
+
+/// Elements, whose content is only rendered when referenced from elsewhere
+const NOT_RENDERED: &[&str] =
+    &["clipPath", "defs", "marker", "mask", "pattern", "symbol"];
+
+/// The length of a user unit in millimeters, as defined by the root element
+fn user_unit(attributes: &Attributes) -> Result<f64, ImportError> {
+    let pixel = Unit::Pixel.in_millimeters();
+
+    let (Some(width), Some([.., view_box_width, _])) =
+        (attribute(attributes, "width"), view_box(attributes))
+    else {
+        return Ok(pixel);
+    };
+
+    let split = width
+        .find(|c: char| c.is_ascii_alphabetic() || c == '%')
+        .unwrap_or(width.len());
+    let (value, unit) = width.split_at(split);
+    let unit = match unit.trim() {
+        "" | "px" => pixel,
+        "mm" => Unit::Millimeter.in_millimeters(),
+        "cm" => Unit::Centimeter.in_millimeters(),
+        "in" => Unit::Inch.in_millimeters(),
+        "pt" => Unit::Point.in_millimeters(),
+        "pc" => Unit::Point.in_millimeters() * 12.,
+        "%" => return Ok(pixel),
+        unit => return Err(ImportError::UnknownUnit(unit.to_owned())),
+    };
+
+    if view_box_width <= 0. {
+        return Ok(pixel);
+    }
+
+    Ok(number(value.trim())? * unit / view_box_width)
+}
+
+fn view_box(attributes: &Attributes) -> Option<[f64; 4]> {
+    let mut lexer = Lexer::new(attribute(attributes, "viewBox")?);
+    let view_box = [
+        lexer.number()?,
+        lexer.number()?,
+        lexer.number()?,
+        lexer.number()?,
+    ];
+    Some(view_box)
+}
+
+/// Convert a shape element into pieces of outlines
+fn element(
+    name: &str,
+    attributes: &Attributes,
+    context: &Context,
+    tolerance: f64,
+) -> Result<Vec<Piece>, ImportError> {
+    let transform = context.transform;
+    let length = |name: &str| -> Result<f64, ImportError> {
+        attribute(attributes, name).map(length).unwrap_or(Ok(0.))
+    };
+
+    let piece = match name {
+        "path" => {
+            let d = attribute(attributes, "d").unwrap_or_default();
+            return path(d, transform, tolerance);
+        }
+        "line" => Piece::Path {
+            points: vec![
+                transform.apply([length("x1")?, length("y1")?]),
+                transform.apply([length("x2")?, length("y2")?]),
+            ],
+            closed: false,
+        },
+        "polyline" | "polygon" => {
+            let points = attribute(attributes, "points").unwrap_or_default();
+            let mut lexer = Lexer::new(points);
+
+            let mut points = Vec::new();
+            while let (Some(x), Some(y)) = (lexer.number(), lexer.number()) {
+                points.push(transform.apply([x, y]));
+            }
+
+            Piece::Path {
+                points,
+                closed: name == "polygon",
+            }
+        }
+        "rect" => {
+            let [x, y, width, height] = [
+                length("x")?,
+                length("y")?,
+                length("width")?,
+                length("height")?,
+            ];
+            if width <= 0. || height <= 0. {
+                return Ok(Vec::new());
+            }
+
+            // A missing corner radius is the same as the other one.
+            let [rx, ry] = match [
+                attribute(attributes, "rx"),
+                attribute(attributes, "ry"),
+            ] {
+                [None, None] => [0., 0.],
+                [Some(_), None] => [length("rx")?; 2],
+                [None, Some(_)] => [length("ry")?; 2],
+                [Some(_), Some(_)] => [length("rx")?, length("ry")?],
+            };
+            let [rx, ry] = [rx.min(width / 2.), ry.min(height / 2.)];
+
+            let points = if rx > 0. && ry > 0. {
+                let corners = [
+                    [x + width - rx, y + ry],
+                    [x + width - rx, y + height - ry],
+                    [x + rx, y + height - ry],
+                    [x + rx, y + ry],
+                ];
+                corners
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(i, center)| {
+                        let start = -PI / 2. + PI / 2. * i as f64;
+                        ellipse(
+                            center,
+                            [rx, ry],
+                            0.,
+                            start,
+                            PI / 2.,
+                            transform,
+                            tolerance,
+                        )
+                    })
+                    .collect()
+            } else {
+                [
+                    [x, y],
+                    [x + width, y],
+                    [x + width, y + height],
+                    [x, y + height],
+                ]
+                .map(|point| transform.apply(point))
+                .to_vec()
+            };
+
+            Piece::Path {
+                points,
+                closed: true,
+            }
+        }
+        "circle" | "ellipse" => {
+            let center = [length("cx")?, length("cy")?];
+            let [rx, ry] = if name == "circle" {
+                [length("r")?; 2]
+            } else {
+                [length("rx")?, length("ry")?]
+            };
+            if rx <= 0. || ry <= 0. {
+                return Ok(Vec::new());
+            }
+
+            match transform.uniform_scale() {
+                Some(scale) if rx == ry => Piece::Circle {
+                    center: transform.apply(center),
+                    radius: rx * scale,
+                },
+                _ => {
+                    let mut points = ellipse(
+                        center,
+                        [rx, ry],
+                        0.,
+                        0.,
+                        2. * PI,
+                        transform,
+                        tolerance,
+                    );
+                    points.pop();
+
+                    Piece::Path {
+                        points,
+                        closed: true,
+                    }
+                }
+            }
+        }
+        _ => return Ok(Vec::new()),
+    };
+
+    Ok(vec![piece])
+}
+
+/// Convert path data into pieces of outlines, one for each subpath
+fn path(
+    d: &str,
+    transform: Matrix,
+    tolerance: f64,
+) -> Result<Vec<Piece>, ImportError> {
+    let mut pieces = Vec::new();
+    let mut lexer = Lexer::new(d);
+
+    // The current subpath, transformed to millimeters.
+    let mut points: Vec<[f64; 2]> = Vec::new();
+
+    let mut current = [0., 0.];
+    let mut start = [0., 0.];
+
+    // The last control point of the previous command, if that was a curve,
+    // and whether it was a cubic curve.
+    let mut control: Option<([f64; 2], bool)> = None;
+
+    let mut command = None;
+
+    loop {
+        if let Some(next) = lexer.command() {
+            command = Some(next);
+        } else if lexer.is_at_end() {
+            break;
+        }
+        let Some(c) = command else {
+            return Err(ImportError::Parse(format!(
+                "Expected command in path data `{d}`"
+            )));
+        };
+
+        let relative = c.is_ascii_lowercase();
+        let offset = if relative { current } else { [0., 0.] };
+        let point = |lexer: &mut Lexer| -> Result<[f64; 2], ImportError> {
+            let x = lexer.expect_number(d)?;
+            let y = lexer.expect_number(d)?;
+            Ok([offset[0] + x, offset[1] + y])
+        };
+
+        let mut next_control = None;
+
+        match c.to_ascii_uppercase() {
+            'M' => {
+                finish(&mut pieces, &mut points, false);
+
+                current = point(&mut lexer)?;
+                start = current;
+                points.push(transform.apply(current));
+
+                // Further coordinates are implicit line commands.
+                command = Some(if relative { 'l' } else { 'L' });
+            }
+            'L' => {
+                current = point(&mut lexer)?;
+                points.push(transform.apply(current));
+            }
+            'H' => {
+                let x = lexer.expect_number(d)?;
+                current[0] = if relative { current[0] + x } else { x };
+                points.push(transform.apply(current));
+            }
+            'V' => {
+                let y = lexer.expect_number(d)?;
+                current[1] = if relative { current[1] + y } else { y };
+                points.push(transform.apply(current));
+            }
+            kind @ ('C' | 'S' | 'Q' | 'T') => {
+                let is_cubic = matches!(kind, 'C' | 'S');
+
+                // The first control point of the smooth variants is the
+                // reflection of the previous one.
+                let reflected = match control {
+                    Some((control, was_cubic)) if was_cubic == is_cubic => [
+                        2. * current[0] - control[0],
+                        2. * current[1] - control[1],
+                    ],
+                    _ => current,
+                };
+
+                let controls = match kind {
+                    'C' => vec![
+                        current,
+                        point(&mut lexer)?,
+                        point(&mut lexer)?,
+                        point(&mut lexer)?,
+                    ],
+                    'S' => vec![
+                        current,
+                        reflected,
+                        point(&mut lexer)?,
+                        point(&mut lexer)?,
+                    ],
+                    'Q' => {
+                        vec![current, point(&mut lexer)?, point(&mut lexer)?]
+                    }
+                    _ => vec![current, reflected, point(&mut lexer)?],
+                };
+
+                next_control = Some((controls[controls.len() - 2], is_cubic));
+                current = controls[controls.len() - 1];
+
+                let controls = controls
+                    .into_iter()
+                    .map(|point| transform.apply(point))
+                    .collect::<Vec<_>>();
+                points.extend(bezier(&controls, tolerance));
+            }
+            'A' => {
+                let radii = [lexer.expect_number(d)?, lexer.expect_number(d)?];
+                let rotation = lexer.expect_number(d)?.to_radians();
+                let large_arc = lexer.expect_flag(d)?;
+                let sweep = lexer.expect_flag(d)?;
+                let end = point(&mut lexer)?;
+
+                points.extend(arc(
+                    current,
+                    end,
+                    radii,
+                    rotation,
+                    [large_arc, sweep],
+                    transform,
+                    tolerance,
+                ));
+                current = end;
+            }
+            'Z' => {
+                finish(&mut pieces, &mut points, true);
+
+                current = start;
+                points.push(transform.apply(current));
+
+                command = None;
+            }
+            c => {
+                return Err(ImportError::Parse(format!(
+                    "Unknown path command `{c}` in `{d}`"
+                )))
+            }
+        }
+
+        control = next_control;
+    }
+
+    finish(&mut pieces, &mut points, false);
+
+    Ok(pieces)
+}
+
+/// Finish the current subpath
+fn finish(pieces: &mut Vec<Piece>, points: &mut Vec<[f64; 2]>, closed: bool) {
+    let points = std::mem::take(points);
+
+    // A lone point, from moving without drawing, is not a subpath.
+    if points.len() > 1 {
+        pieces.push(Piece::Path { points, closed });
+    }
+}
+
+/// Approximate an elliptical arc of a path, excluding its start point
+///
+/// See the SVG specification, appendix B.2.4, for how this is converted from
+/// the endpoints to the center of the ellipse.
+fn arc(
+    from: [f64; 2],
+    to: [f64; 2],
+    [rx, ry]: [f64; 2],
+    rotation: f64,
+    [large_arc, sweep]: [bool; 2],
+    transform: Matrix,
+    tolerance: f64,
+) -> Vec<[f64; 2]> {
+    if from == to {
+        return Vec::new();
+    }
+
+    let [mut rx, mut ry] = [rx.abs(), ry.abs()];
+    if rx == 0. || ry == 0. {
+        return vec![transform.apply(to)];
+    }
+
+    let (sin, cos) = rotation.sin_cos();
+    let [dx, dy] = [(from[0] - to[0]) / 2., (from[1] - to[1]) / 2.];
+    let [x, y] = [cos * dx + sin * dy, -sin * dx + cos * dy];
+
+    // Radii that are too small are scaled up, until the arc fits.
+    let lambda = (x * x) / (rx * rx) + (y * y) / (ry * ry);
+    if lambda > 1. {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = rx * rx * ry * ry - rx * rx * y * y - ry * ry * x * x;
+    let denominator = rx * rx * y * y + ry * ry * x * x;
+    let sign = if large_arc == sweep { -1. } else { 1. };
+    let coefficient = sign * (numerator / denominator).max(0.).sqrt();
+    let [cx, cy] = [coefficient * rx * y / ry, -coefficient * ry * x / rx];
+
+    let center = [
+        cos * cx - sin * cy + (from[0] + to[0]) / 2.,
+        sin * cx + cos * cy + (from[1] + to[1]) / 2.,
+    ];
+
+    let angle = |u: f64, v: f64| v.atan2(u);
+    let start = angle((x - cx) / rx, (y - cy) / ry);
+    let end = angle((-x - cx) / rx, (-y - cy) / ry);
+
+    let mut delta = (end - start).rem_euclid(2. * PI);
+    if !sweep && delta > 0. {
+        delta -= 2. * PI;
+    }
+
+    let mut points = ellipse(
+        center,
+        [rx, ry],
+        rotation,
+        start,
+        delta,
+        transform,
+        tolerance,
+    );
+    points.remove(0);
+
+    // Make sure the arc ends exactly where the next command starts.
+    if let Some(last) = points.last_mut() {
+        *last = transform.apply(to);
+    }
+
+    points
+}
+
+/// Approximate part of an ellipse, including its start and end point
+///
+/// The angles are those of the circle, that the ellipse is a scaled version
+/// of. The points are transformed, after approximating the ellipse.
+fn ellipse(
+    center: [f64; 2],
+    [rx, ry]: [f64; 2],
+    rotation: f64,
+    start: f64,
+    sweep: f64,
+    transform: Matrix,
+    tolerance: f64,
+) -> Vec<[f64; 2]> {
+    // Scaling a circle down along one axis can't make its approximation
+    // deviate more, so the larger radius determines the number of points.
+    let radius = rx.max(ry);
+    let tolerance = tolerance / transform.max_scale() / radius;
+
+    let (sin, cos) = rotation.sin_cos();
+    arc_points([0., 0.], 1., start, sweep, tolerance)
+        .into_iter()
+        .map(|[u, v]| {
+            let [u, v] = [u * rx, v * ry];
+            transform.apply([
+                center[0] + cos * u - sin * v,
+                center[1] + sin * u + cos * v,
+            ])
+        })
+        .collect()
+}
+
+/// Approximate a Bézier curve, excluding its start point
+fn bezier(controls: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    // The distance between a curve and a line between two of its points is at
+    // most an eighth of the maximum second derivative, times the square of
+    // their distance in the curve's parameter. The second derivative is
+    // bounded by the second differences of the control points.
+    let degree = controls.len() as f64 - 1.;
+    let second_difference = controls
+        .windows(3)
+        .map(|p| {
+            (p[0][0] - 2. * p[1][0] + p[2][0])
+                .hypot(p[0][1] - 2. * p[1][1] + p[2][1])
+        })
+        .fold(0., f64::max);
+    let max_derivative = degree * (degree - 1.) * second_difference;
+
+    let n = (max_derivative / (8. * tolerance)).sqrt().ceil().max(1.) as usize;
+
+    (1..=n)
+        .map(|i| {
+            // Evaluate the curve using de Casteljau's algorithm.
+            let t = i as f64 / n as f64;
+            let mut points = controls.to_vec();
+            while points.len() > 1 {
+                points = points
+                    .windows(2)
+                    .map(|p| {
+                        [
+                            p[0][0] + (p[1][0] - p[0][0]) * t,
+                            p[0][1] + (p[1][1] - p[0][1]) * t,
+                        ]
+                    })
+                    .collect();
+            }
+            points[0]
+        })
+        .collect()
+}
+
+/// An affine transformation, in the form of SVG's `matrix(a b c d e f)`
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Matrix([f64; 6]);
+
+impl Matrix {
+    fn apply(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let [a, b, c, d, e, f] = self.0;
+        [a * x + c * y + e, b * x + d * y + f]
+    }
+
+    /// Combine this transformation with another, that is applied first
+    fn then(&self, other: Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = other.0;
+
+        Self([
+            a * a2 + c * b2,
+            b * a2 + d * b2,
+            a * c2 + c * d2,
+            b * c2 + d * d2,
+            a * e2 + c * f2 + e,
+            b * e2 + d * f2 + f,
+        ])
+    }
+
+    /// The largest factor, by which the transformation scales any length
+    fn max_scale(&self) -> f64 {
+        let [a, b, c, d, ..] = self.0;
+
+        // This is the largest singular value of the linear part.
+        let sum = a * a + b * b + c * c + d * d;
+        let determinant = a * d - b * c;
+        let root = (sum * sum - 4. * determinant * determinant).max(0.).sqrt();
+        ((sum + root) / 2.).sqrt()
+    }
+
+    /// The scale factor, if the transformation scales uniformly
+    ///
+    /// Only transformations that scale uniformly keep circles circular.
+    fn uniform_scale(&self) -> Option<f64> {
+        let [a, b, c, d, ..] = self.0;
+        let scale = self.max_scale();
+        let epsilon = scale * 1e-9;
+
+        let is_rotation = (a - d).abs() <= epsilon && (b + c).abs() <= epsilon;
+        let is_reflection =
+            (a + d).abs() <= epsilon && (b - c).abs() <= epsilon;
+
+        (is_rotation || is_reflection).then_some(scale)
+    }
+}
+
+fn parse_transform(transform: &str) -> Result<Matrix, ImportError> {
+    let invalid =
+        || ImportError::Parse(format!("Invalid transform `{transform}`"));
+
+    let mut matrix = Matrix([1., 0., 0., 1., 0., 0.]);
+
+    for definition in transform.split_terminator(')') {
+        let (name, arguments) =
+            definition.split_once('(').ok_or_else(invalid)?;
+        let name = name.trim_matches(|c: char| c.is_whitespace() || c == ',');
+
+        let mut lexer = Lexer::new(arguments);
+        let mut arguments = Vec::new();
+        while let Some(argument) = lexer.number() {
+            arguments.push(argument);
+        }
+
+        let next = match (name, arguments.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Matrix([a, b, c, d, e, f]),
+            ("translate", &[x]) => Matrix([1., 0., 0., 1., x, 0.]),
+            ("translate", &[x, y]) => Matrix([1., 0., 0., 1., x, y]),
+            ("scale", &[s]) => Matrix([s, 0., 0., s, 0., 0.]),
+            ("scale", &[x, y]) => Matrix([x, 0., 0., y, 0., 0.]),
+            ("rotate", &[angle, ref center @ ..])
+                if center.is_empty() || center.len() == 2 =>
+            {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let rotation = Matrix([cos, sin, -sin, cos, 0., 0.]);
+
+                match center {
+                    &[x, y] => Matrix([1., 0., 0., 1., x, y])
+                        .then(rotation)
+                        .then(Matrix([1., 0., 0., 1., -x, -y])),
+                    _ => rotation,
+                }
+            }
+            ("skewX", &[angle]) => {
+                Matrix([1., 0., angle.to_radians().tan(), 1., 0., 0.])
+            }
+            ("skewY", &[angle]) => {
+                Matrix([1., angle.to_radians().tan(), 0., 1., 0., 0.])
+            }
+            _ => return Err(invalid()),
+        };
+
+        matrix = matrix.then(next);
+    }
+
+    Ok(matrix)
+}
+
+/// Reads numbers, flags, and commands from attribute values
+struct Lexer<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input: input.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(c) = self.input.get(self.position) {
+            if !(c.is_ascii_whitespace() || *c == b',') {
+                break;
+            }
+            self.position += 1;
+        }
+    }
+
+    fn is_at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.position >= self.input.len()
+    }
+
+    fn command(&mut self) -> Option<char> {
+        self.skip_separators();
+
+        let c = *self.input.get(self.position)?;
+        if !c.is_ascii_alphabetic() {
+            return None;
+        }
+
+        self.position += 1;
+        Some(char::from(c))
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+
+        let start = self.position;
+        let digits = |lexer: &mut Self| {
+            let start = lexer.position;
+            while matches!(lexer.input.get(lexer.position), Some(c) if c.is_ascii_digit())
+            {
+                lexer.position += 1;
+            }
+            lexer.position > start
+        };
+        let accept = |lexer: &mut Self, chars: &[u8]| {
+            let accepted = matches!(
+                lexer.input.get(lexer.position),
+                Some(c) if chars.contains(c)
+            );
+            if accepted {
+                lexer.position += 1;
+            }
+            accepted
+        };
+
+        accept(self, b"+-");
+        let mut has_digits = digits(self);
+        if accept(self, b".") {
+            has_digits |= digits(self);
+        }
+        if !has_digits {
+            self.position = start;
+            return None;
+        }
+
+        // Only consume the exponent, if it's complete.
+        let mantissa_end = self.position;
+        if accept(self, b"eE") {
+            accept(self, b"+-");
+            if !digits(self) {
+                self.position = mantissa_end;
+            }
+        }
+
+        let number = std::str::from_utf8(&self.input[start..self.position])
+            .ok()?
+            .parse()
+            .ok();
+        if number.is_none() {
+            self.position = start;
+        }
+        number
+    }
+
+    fn expect_number(&mut self, input: &str) -> Result<f64, ImportError> {
+        self.number().ok_or_else(|| {
+            ImportError::Parse(format!("Expected number in `{input}`"))
+        })
+    }
+
+    /// Read a flag, which doesn't need to be separated from what follows
+    fn expect_flag(&mut self, input: &str) -> Result<bool, ImportError> {
+        self.skip_separators();
+
+        let flag = match self.input.get(self.position) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => {
+                return Err(ImportError::Parse(format!(
+                    "Expected flag in `{input}`"
+                )))
+            }
+        };
+
+        self.position += 1;
+        Ok(flag)
+    }
+}
+
+/// Parse a length attribute, ignoring its unit
+fn length(value: &str) -> Result<f64, ImportError> {
+    Lexer::new(value)
+        .number()
+        .ok_or_else(|| ImportError::Parse(format!("Invalid length `{value}`")))
+}
+
+fn number(value: &str) -> Result<f64, ImportError> {
+    value
+        .parse()
+        .map_err(|_| ImportError::Parse(format!("Invalid number `{value}`")))
+}
+
+type Attributes = Vec<(String, String)>;
+
+fn attribute<'a>(attributes: &'a Attributes, name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value.as_str())
+}
+
+enum Tag {
+    Start {
+        name: String,
+        attributes: Attributes,
+        is_empty: bool,
+    },
+    End,
+}
+
+/// Split an XML document into its tags
+///
+/// Names are kept with their namespace prefix, as SVG editors put information
+/// into attributes like `inkscape:label`.
+fn tags(input: &str) -> Result<Vec<Tag>, ImportError> {
+    let parse_error =
+        |err: quick_xml::Error| ImportError::Parse(err.to_string());
+
+    let mut reader = Reader::from_str(input);
+    let mut tags = Vec::new();
+
+    loop {
+        let (start, is_empty) =
+            match reader.read_event().map_err(parse_error)? {
+                Event::Start(start) => (start, false),
+                Event::Empty(start) => (start, true),
+                Event::End(_) => {
+                    tags.push(Tag::End);
+                    continue;
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+        let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+        let attributes = start
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute.map_err(quick_xml::Error::from)?;
+                let name = String::from_utf8_lossy(attribute.key.as_ref());
+
+                // References to entities that are declared in the document
+                // are left as they are. None of the attributes that are read
+                // are expected to contain them.
+                let value = match attribute.unescape_value() {
+                    Ok(value) => value.into_owned(),
+                    Err(_) => String::from_utf8_lossy(&attribute.value).into(),
+                };
+
+                Ok((name.into_owned(), value))
+            })
+            .collect::<Result<_, _>>()
+            .map_err(parse_error)?;
+
+        tags.push(Tag::Start {
+            name,
+            attributes,
+            is_empty,
+        });
+    }
+
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::{Import, Piece};
+
+    use super::parse;
+
+    #[test]
+    fn units_layers_and_transforms() {
+        let svg = r#"<?xml version="1.0"?>
+            <!-- A comment -->
+            <svg xmlns="http://www.w3.org/2000/svg" width="10mm" height="10mm" viewBox="0 0 100 100">
+                <g id="cut" transform="translate(10,20)">
+                    <path d="M0,0 h50 v-10 H0z"/>
+                    <circle cx="10" cy="10" r="5" transform="scale(2)"/>
+                </g>
+                <g id="engrave">
+                    <line x1="0" y1="0" x2="10" y2="10"/>
+                </g>
+                <defs><rect width="10" height="10"/></defs>
+            </svg>"#;
+
+        // A user unit is 0.1 millimeters. The y-axis is mirrored.
+        let pieces = parse(svg, &Import::new().with_layer("cut")).unwrap();
+        assert_eq!(
+            pieces,
+            [
+                Piece::Path {
+                    points: vec![[1., -2.], [6., -2.], [6., -1.], [1., -1.]],
+                    closed: true,
+                },
+                Piece::Circle {
+                    center: [3., -4.],
+                    radius: 1.,
+                },
+            ]
+        );
+
+        let pieces = parse(svg, &Import::new()).unwrap();
+        assert_eq!(pieces.len(), 3);
+    }
+
+    #[test]
+    fn curves() {
+        let svg = r#"<svg viewBox="0 0 10 10" width="10mm">
+            <path d="M 0 0 A 1 1 0 0 1 2 0 Q 2 2 0 2 c -1 0 -1-1-1-1.5e0"/>
+        </svg>"#;
+        let tolerance = 1e-3;
+
+        let pieces =
+            parse(svg, &Import::new().with_tolerance(tolerance)).unwrap();
+        let [Piece::Path { points, closed: false }] = pieces.as_slice() else {
+            panic!("Expected open path, got {pieces:?}");
+        };
+
+        // The arc runs clockwise in SVG's coordinates, which is through the
+        // negative y-axis. That's above the x-axis, after mirroring.
+        let arc = points
+            .iter()
+            .take_while(|point| point[0] < 2. - 1e-9 || point[1] > 1e-9);
+        for point in arc {
+            let distance = (point[0] - 1.).hypot(point[1]);
+            assert!((distance - 1.).abs() < 1e-9);
+            assert!(point[1] >= -1e-9);
+        }
+
+        assert!(points.contains(&[2., 0.]));
+        assert!(points.contains(&[0., -2.]));
+        assert_eq!(points.last(), Some(&[-1., -0.5]));
+        assert!(points.len() > 10);
+    }
+}
@@ -23,26 +23,54 @@ pub mod syntax;
 #[doc(hidden)]
 pub mod abi;
 mod angle;
+mod annotation;
 mod group;
+pub mod import;
 pub mod models;
+mod pattern;
+mod reference;
 mod shape_2d;
 mod sweep;
 mod transform;
 pub mod version;
 
 pub use self::{
-    angle::*, group::Group, shape_2d::*, sweep::Sweep, transform::Transform,
+    angle::*,
+    annotation::{Annotated, Annotation, Dimension, Label, ReferencePoint},
+    group::Group,
+    pattern::{CircularPattern, LinearPattern},
+    reference::{Reference, Referenced},
+    shape_2d::*,
+    sweep::Sweep,
+    transform::Transform,
 };
 pub use fj_proc::*;
 
 /// A shape
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Shapes can be hashed, to recognize shapes that haven't changed.
+/// Floating-point numbers are hashed by their bits, so numbers that are equal
+/// but have different representations, like `0.0` and `-0.0`, hash
+/// differently.
+#[derive(Clone, Debug, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub enum Shape {
+    /// A 3-dimensional shape with annotations attached
+    Annotated(Box<Annotated>),
+
+    /// A circular pattern of a 3-dimensional shape
+    CircularPattern(Box<CircularPattern>),
+
     /// A group of two 3-dimensional shapes
     Group(Box<Group>),
 
+    /// A linear pattern of a 3-dimensional shape
+    LinearPattern(Box<LinearPattern>),
+
+    /// A 3-dimensional shape with reference bodies placed next to it
+    Referenced(Box<Referenced>),
+
     /// A 2D shape
     Shape2d(Shape2d),
 
@@ -0,0 +1,418 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{abi::ffi_safe, Angle, Shape, Transform};
+
+/// A linear pattern of a 3-dimensional shape
+///
+/// The pattern consists of `count` instances of the shape. The first instance
+/// is the original shape, each further instance is translated by `offset`
+/// relative to the previous one.
+///
+/// Instances are addressed by their index, starting at `0` for the original
+/// shape. See [`LinearPattern::instance`] and
+/// [`LinearPattern::with_instance_color`].
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+/// use fj::syntax::*;
+///
+/// // `shape` can be anything that converts to `fj::Shape`
+/// let row = shape
+///     .linear_pattern([2., 0., 0.], 5)
+///     .with_instance_color(2, [0, 255, 0, 255]);
+/// ```
+///
+/// # Limitations
+///
+/// Like for [`crate::Group`], the instances are not allowed to touch or
+/// overlap, but this is not currently checked.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct LinearPattern {
+    shape: Shape,
+    offset: [f64; 3],
+    count: u32,
+    colors: ffi_safe::Vec<InstanceColor>,
+}
+
+impl LinearPattern {
+    fn key(&self) -> (&Shape, [u64; 3], u32, &ffi_safe::Vec<InstanceColor>) {
+        (
+            &self.shape,
+            self.offset.map(f64::to_bits),
+            self.count,
+            &self.colors,
+        )
+    }
+}
+
+impl PartialEq for LinearPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for LinearPattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl LinearPattern {
+    /// Create a `LinearPattern` from a shape
+    pub fn from_shape(shape: Shape, offset: [f64; 3], count: u32) -> Self {
+        Self {
+            shape,
+            offset,
+            count,
+            colors: Vec::new().into(),
+        }
+    }
+
+    /// Override the rendering color of an instance in RGBA
+    pub fn with_instance_color(mut self, index: u32, color: [u8; 4]) -> Self {
+        self.colors = with_instance_color(&self.colors, index, color);
+        self
+    }
+
+    /// Access the shape that is repeated
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the offset between two neighboring instances
+    pub fn offset(&self) -> [f64; 3] {
+        self.offset
+    }
+
+    /// Access the number of instances
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Get the rendering color override of an instance in RGBA, if any
+    pub fn instance_color(&self, index: u32) -> Option<[u8; 4]> {
+        instance_color(&self.colors, index)
+    }
+
+    /// Create the instance with the given index as a standalone shape
+    ///
+    /// This ignores any color overrides.
+    pub fn instance(&self, index: u32) -> Transform {
+        let offset = self.offset.map(|offset| offset * f64::from(index));
+
+        Transform::from_shape(self.shape.clone()).with_offset(offset)
+    }
+}
+
+impl From<LinearPattern> for Shape {
+    fn from(shape: LinearPattern) -> Self {
+        Self::LinearPattern(Box::new(shape))
+    }
+}
+
+/// A circular pattern of a 3-dimensional shape
+///
+/// The pattern consists of `count` instances of the shape. The first instance
+/// is the original shape, each further instance is rotated by `angle` relative
+/// to the previous one. By default, the instances are evenly distributed over
+/// a full turn around the z-axis.
+///
+/// Instances are addressed by their index, starting at `0` for the original
+/// shape. See [`CircularPattern::instance`] and
+/// [`CircularPattern::with_instance_color`].
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let hole = fj::Sketch::from_points(vec![[4., 0.], [5., 0.], [4., 1.]]);
+/// use fj::syntax::*;
+///
+/// // `hole` can be anything that converts to `fj::Shape`
+/// let bolt_circle = hole
+///     .circular_pattern([0., 0., 1.], 6)
+///     .with_center([1., 1., 0.]);
+/// ```
+///
+/// # Limitations
+///
+/// Like for [`crate::Group`], the instances are not allowed to touch or
+/// overlap, but this is not currently checked.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct CircularPattern {
+    shape: Shape,
+    center: [f64; 3],
+    axis: [f64; 3],
+    angle: Angle,
+    count: u32,
+    colors: ffi_safe::Vec<InstanceColor>,
+}
+
+impl CircularPattern {
+    fn key(
+        &self,
+    ) -> (
+        &Shape,
+        [u64; 3],
+        [u64; 3],
+        Angle,
+        u32,
+        &ffi_safe::Vec<InstanceColor>,
+    ) {
+        (
+            &self.shape,
+            self.center.map(f64::to_bits),
+            self.axis.map(f64::to_bits),
+            self.angle,
+            self.count,
+            &self.colors,
+        )
+    }
+}
+
+impl PartialEq for CircularPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for CircularPattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl CircularPattern {
+    /// Create a `CircularPattern` from a shape
+    ///
+    /// The instances are evenly distributed over a full turn around `axis`,
+    /// which passes through the origin. `axis` must not be zero, or computing
+    /// the pattern fails, and [`CircularPattern::instance`] returns `None`.
+    pub fn from_shape(shape: Shape, axis: [f64; 3], count: u32) -> Self {
+        Self {
+            shape,
+            center: [0.; 3],
+            axis,
+            angle: Angle::from_rev(1. / f64::from(count.max(1))),
+            count,
+            colors: Vec::new().into(),
+        }
+    }
+
+    /// Move the axis of the pattern, so it passes through `center`
+    pub fn with_center(mut self, center: [f64; 3]) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Set the angle between two neighboring instances
+    pub fn with_angle(mut self, angle: Angle) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Override the rendering color of an instance in RGBA
+    pub fn with_instance_color(mut self, index: u32, color: [u8; 4]) -> Self {
+        self.colors = with_instance_color(&self.colors, index, color);
+        self
+    }
+
+    /// Access the shape that is repeated
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the point that the axis of the pattern passes through
+    pub fn center(&self) -> [f64; 3] {
+        self.center
+    }
+
+    /// Access the axis of the pattern
+    pub fn axis(&self) -> [f64; 3] {
+        self.axis
+    }
+
+    /// Access the angle between two neighboring instances
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+
+    /// Access the number of instances
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Get the rendering color override of an instance in RGBA, if any
+    pub fn instance_color(&self, index: u32) -> Option<[u8; 4]> {
+        instance_color(&self.colors, index)
+    }
+
+    /// Create the instance with the given index as a standalone shape
+    ///
+    /// This ignores any color overrides. Returns `None`, if the axis of the
+    /// pattern is zero, or not finite.
+    pub fn instance(&self, index: u32) -> Option<Transform> {
+        let angle = self.angle * f64::from(index);
+
+        // `Transform` rotates around the origin, then translates. Rotating
+        // around `center` instead is the same as rotating around the origin,
+        // then moving `center` back to where it was.
+        let rotated_center = rotate(self.center, self.axis, angle)?;
+        let offset = [0, 1, 2].map(|i| self.center[i] - rotated_center[i]);
+
+        let transform = Transform::from_shape(self.shape.clone())
+            .with_rotation(self.axis, angle)
+            .with_offset(offset);
+
+        Some(transform)
+    }
+}
+
+impl From<CircularPattern> for Shape {
+    fn from(shape: CircularPattern) -> Self {
+        Self::CircularPattern(Box::new(shape))
+    }
+}
+
+/// A color override for a single instance of a pattern
+#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+struct InstanceColor {
+    index: u32,
+    color: [u8; 4],
+}
+
+fn with_instance_color(
+    colors: &[InstanceColor],
+    index: u32,
+    color: [u8; 4],
+) -> ffi_safe::Vec<InstanceColor> {
+    colors
+        .iter()
+        .copied()
+        .filter(|instance| instance.index != index)
+        .chain([InstanceColor { index, color }])
+        .collect()
+}
+
+fn instance_color(colors: &[InstanceColor], index: u32) -> Option<[u8; 4]> {
+    colors
+        .iter()
+        .find(|instance| instance.index == index)
+        .map(|instance| instance.color)
+}
+
+/// Rotate `point` around `axis`, which passes through the origin
+///
+/// Uses Rodrigues' rotation formula. Returns `None`, if `axis` doesn't define
+/// a direction.
+fn rotate(point: [f64; 3], axis: [f64; 3], angle: Angle) -> Option<[f64; 3]> {
+    let length = axis.iter().map(|c| c * c).sum::<f64>().sqrt();
+    if !(length.is_finite() && length > 0.) {
+        return None;
+    }
+
+    let [x, y, z] = axis.map(|c| c / length);
+    let [px, py, pz] = point;
+
+    let (sin, cos) = angle.rad().sin_cos();
+
+    let cross = [y * pz - z * py, z * px - x * pz, x * py - y * px];
+    let dot = x * px + y * py + z * pz;
+
+    let rotated = [0, 1, 2].map(|i| {
+        let k = [x, y, z][i];
+        point[i] * cos + cross[i] * sin + k * dot * (1. - cos)
+    });
+
+    Some(rotated)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Angle, Shape, Sketch};
+
+    use super::{rotate, CircularPattern, LinearPattern};
+
+    #[test]
+    fn linear_pattern_instance() {
+        let pattern = LinearPattern::from_shape(triangle(), [1., 2., 3.], 3);
+
+        assert_eq!(pattern.instance(0).offset, [0., 0., 0.]);
+        assert_eq!(pattern.instance(2).offset, [2., 4., 6.]);
+    }
+
+    #[test]
+    fn circular_pattern_instance() {
+        let pattern = CircularPattern::from_shape(triangle(), [0., 0., 1.], 4)
+            .with_center([1., 0., 0.]);
+
+        // A quarter turn around `[1., 0., 0.]` moves the origin to
+        // `[1., -1., 0.]`.
+        let instance = pattern.instance(1).unwrap();
+        let origin =
+            rotate([0., 0., 0.], instance.axis, instance.angle).unwrap();
+        let moved = [0, 1, 2].map(|i| origin[i] + instance.offset[i]);
+
+        assert_approx_eq(moved, [1., -1., 0.]);
+        assert_approx_eq([instance.angle.rev(), 0., 0.], [0.25, 0., 0.]);
+    }
+
+    #[test]
+    fn circular_pattern_instance_with_invalid_axis() {
+        for axis in [[0., 0., 0.], [f64::INFINITY, 0., 0.]] {
+            let pattern = CircularPattern::from_shape(triangle(), axis, 4);
+            assert_eq!(pattern.instance(1), None);
+        }
+    }
+
+    #[test]
+    fn instance_color_overrides() {
+        let pattern = LinearPattern::from_shape(triangle(), [1., 0., 0.], 3)
+            .with_instance_color(1, [0, 0, 255, 255])
+            .with_instance_color(1, [0, 255, 0, 255]);
+
+        assert_eq!(pattern.instance_color(0), None);
+        assert_eq!(pattern.instance_color(1), Some([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn compare_like_hash() {
+        // Patterns are used in cache keys, so they must be equal exactly if
+        // they hash the same.
+        let pattern = |offset| LinearPattern::from_shape(triangle(), offset, 3);
+
+        assert_ne!(pattern([0., 0., 0.]), pattern([-0., 0., 0.]));
+
+        let nan = pattern([f64::NAN, 0., 0.]);
+        assert_eq!(nan, nan.clone());
+    }
+
+    #[test]
+    fn rotate_around_arbitrary_axis() {
+        let rotated =
+            rotate([1., 0., 0.], [1., 1., 1.], Angle::from_rev(1. / 3.))
+                .unwrap();
+        assert_approx_eq(rotated, [0., 1., 0.]);
+    }
+
+    fn triangle() -> Shape {
+        Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]).into()
+    }
+
+    fn assert_approx_eq(a: [f64; 3], b: [f64; 3]) {
+        for (a, b) in a.into_iter().zip(b) {
+            assert!((a - b).abs() < 1e-12, "{a} != {b}");
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{abi::ffi_safe, Shape};
+
+/// A 3-dimensional shape with reference bodies placed next to it
+///
+/// Reference bodies are triangle meshes, loaded from STL or 3MF files, like
+/// scans of existing parts or models provided by a vendor. They are not part
+/// of the shape, and don't take part in any operations. The viewer shows them
+/// next to the shape, which helps with designing parts that mate with them.
+///
+/// Reference bodies are placed in the coordinates of the shape they are
+/// attached to. Any transformation that is applied to the shape, applies to
+/// its reference bodies too.
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+/// use fj::syntax::*;
+///
+/// // `shape` can be anything that converts to `fj::Shape`
+/// let referenced = shape.with_reference(
+///     fj::Reference::from_path(concat!(
+///         env!("CARGO_MANIFEST_DIR"),
+///         "/vendor-part.stl"
+///     ))
+///     .with_color([0, 0, 255, 128]),
+/// );
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Referenced {
+    shape: Shape,
+    references: ffi_safe::Vec<Reference>,
+}
+
+impl Referenced {
+    /// Create a `Referenced` from a shape, without any reference bodies
+    pub fn from_shape(shape: Shape) -> Self {
+        Self {
+            shape,
+            references: Vec::new().into(),
+        }
+    }
+
+    /// Place another reference body next to the shape
+    pub fn with_reference(mut self, reference: Reference) -> Self {
+        let mut references: Vec<_> = self.references.into();
+        references.push(reference);
+
+        self.references = references.into();
+        self
+    }
+
+    /// Access the shape that the reference bodies are attached to
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the reference bodies
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+}
+
+impl From<Referenced> for Shape {
+    fn from(shape: Referenced) -> Self {
+        Self::Referenced(Box::new(shape))
+    }
+}
+
+/// A reference body, loaded from an STL or 3MF file
+///
+/// See [`Referenced`].
+///
+/// Relative paths are resolved against the working directory of the Fornjot
+/// application, not the model. `CARGO_MANIFEST_DIR` can be used to refer to
+/// files next to the model, as shown in the example of [`Referenced`].
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Reference {
+    path: ffi_safe::String,
+    color: [u8; 4],
+    is_exported: bool,
+}
+
+impl Reference {
+    /// Create a `Reference` from the file at the given path
+    ///
+    /// The file type is chosen based on the case insensitive file extension,
+    /// which must be `stl` or `3mf`. STL files don't specify a unit, and are
+    /// assumed to be in millimeters.
+    ///
+    /// By default, the reference body is semi-transparent gray, and is not
+    /// exported.
+    pub fn from_path(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into().into(),
+            color: [128, 128, 128, 128],
+            is_exported: false,
+        }
+    }
+
+    /// Set the rendering color of the reference body in RGBA
+    ///
+    /// An alpha value below 255 makes the reference body semi-transparent.
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set whether the reference body is included, when exporting the shape
+    pub fn with_export(mut self, is_exported: bool) -> Self {
+        self.is_exported = is_exported;
+        self
+    }
+
+    /// Access the path of the file that the reference body is loaded from
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the rendering color of the reference body in RGBA
+    pub fn color(&self) -> [u8; 4] {
+        self.color
+    }
+
+    /// Indicate whether the reference body is included in exports
+    pub fn is_exported(&self) -> bool {
+        self.is_exported
+    }
+}
@@ -1,15 +1,26 @@
+use std::hash::{Hash, Hasher};
+
 use crate::{abi::ffi_safe, Shape};
 
 /// A 2-dimensional shape
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub enum Shape2d {
     /// A difference between two shapes
     Difference(Box<Difference2d>),
 
+    /// An intersection of two shapes
+    Intersection(Box<Intersection2d>),
+
+    /// An offset of a shape
+    Offset(Box<Offset2d>),
+
     /// A sketch
     Sketch(Sketch),
+
+    /// A union of two shapes
+    Union(Box<Union2d>),
 }
 
 impl Shape2d {
@@ -18,6 +29,9 @@ impl Shape2d {
         match &self {
             Self::Sketch(s) => s.color(),
             Self::Difference(d) => d.color(),
+            Self::Intersection(i) => i.color(),
+            Self::Offset(o) => o.color(),
+            Self::Union(u) => u.color(),
         }
     }
 }
@@ -36,7 +50,7 @@ impl Shape2d {
 /// // `a` and `b` can be anything that converts to `fj::Shape2d`
 /// let difference = a.difference(&b);
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Difference2d {
@@ -72,6 +86,213 @@ impl From<Difference2d> for Shape2d {
     }
 }
 
+/// An intersection of two shapes
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let a = fj::Sketch::from_points(vec![[0., 0.], [2., 0.], [0., 2.]]);
+/// # let b = fj::Sketch::from_points(vec![[1., 0.], [3., 0.], [1., 2.]]);
+/// use fj::syntax::*;
+///
+/// // `a` and `b` can be anything that converts to `fj::Shape2d`
+/// let intersection = a.intersection(&b);
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Intersection2d {
+    shapes: [Shape2d; 2],
+}
+
+impl Intersection2d {
+    /// Create an `Intersection2d` from two shapes
+    pub fn from_shapes(shapes: [Shape2d; 2]) -> Self {
+        Self { shapes }
+    }
+
+    /// Get the rendering color of the first object in RGBA
+    pub fn color(&self) -> [u8; 4] {
+        self.shapes[0].color()
+    }
+
+    /// Access the shapes that make up the intersection
+    pub fn shapes(&self) -> &[Shape2d; 2] {
+        &self.shapes
+    }
+}
+
+impl From<Intersection2d> for Shape {
+    fn from(shape: Intersection2d) -> Self {
+        Self::Shape2d(shape.into())
+    }
+}
+
+impl From<Intersection2d> for Shape2d {
+    fn from(shape: Intersection2d) -> Self {
+        Self::Intersection(Box::new(shape))
+    }
+}
+
+/// An offset of a shape
+///
+/// A positive distance grows the shape, a negative distance shrinks it. By
+/// default, the corners of the grown shape are rounded. Use
+/// [`Offset2d::with_join`] to change that.
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+/// use fj::syntax::*;
+///
+/// // `shape` can be anything that converts to `fj::Shape2d`
+/// let clearance = shape.offset(0.1).with_join(fj::Join::Miter);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Offset2d {
+    shape: Shape2d,
+    distance: f64,
+    join: Join,
+}
+
+impl Offset2d {
+    fn key(&self) -> (&Shape2d, u64, Join) {
+        (&self.shape, self.distance.to_bits(), self.join)
+    }
+}
+
+impl PartialEq for Offset2d {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for Offset2d {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Offset2d {
+    /// Create an `Offset2d` from a shape and a distance
+    pub fn from_shape(shape: Shape2d, distance: f64) -> Self {
+        Self {
+            shape,
+            distance,
+            join: Join::Round,
+        }
+    }
+
+    /// Set how the corners of the offset shape are joined
+    pub fn with_join(mut self, join: Join) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Get the rendering color of the offset object in RGBA
+    pub fn color(&self) -> [u8; 4] {
+        self.shape.color()
+    }
+
+    /// Access the shape that is offset
+    pub fn shape(&self) -> &Shape2d {
+        &self.shape
+    }
+
+    /// Access the distance of the offset
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// Access how the corners of the offset shape are joined
+    pub fn join(&self) -> Join {
+        self.join
+    }
+}
+
+impl From<Offset2d> for Shape {
+    fn from(shape: Offset2d) -> Self {
+        Self::Shape2d(shape.into())
+    }
+}
+
+impl From<Offset2d> for Shape2d {
+    fn from(shape: Offset2d) -> Self {
+        Self::Offset(Box::new(shape))
+    }
+}
+
+/// How the corners of an [`Offset2d`] are joined
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum Join {
+    /// Corners are rounded, with the offset distance as the radius
+    Round,
+
+    /// Corners are sharp, extending the adjacent edges until they meet
+    ///
+    /// Very sharp corners are cut off, to keep them from extending too far.
+    Miter,
+}
+
+/// A union of two shapes
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let a = fj::Sketch::from_points(vec![[0., 0.], [2., 0.], [0., 2.]]);
+/// # let b = fj::Sketch::from_points(vec![[1., 0.], [3., 0.], [1., 2.]]);
+/// use fj::syntax::*;
+///
+/// // `a` and `b` can be anything that converts to `fj::Shape2d`
+/// let union = a.union(&b);
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Union2d {
+    shapes: [Shape2d; 2],
+}
+
+impl Union2d {
+    /// Create a `Union2d` from two shapes
+    pub fn from_shapes(shapes: [Shape2d; 2]) -> Self {
+        Self { shapes }
+    }
+
+    /// Get the rendering color of the first object in RGBA
+    pub fn color(&self) -> [u8; 4] {
+        self.shapes[0].color()
+    }
+
+    /// Access the shapes that make up the union
+    pub fn shapes(&self) -> &[Shape2d; 2] {
+        &self.shapes
+    }
+}
+
+impl From<Union2d> for Shape {
+    fn from(shape: Union2d) -> Self {
+        Self::Shape2d(shape.into())
+    }
+}
+
+impl From<Union2d> for Shape2d {
+    fn from(shape: Union2d) -> Self {
+        Self::Union(Box::new(shape))
+    }
+}
+
 /// A sketch
 ///
 /// Sketches are currently limited to a single cycle of straight lines,
@@ -92,7 +313,7 @@ impl From<Difference2d> for Shape2d {
 /// // `a` and `b` can be anything that converts to `fj::Shape`
 /// let sketch = [[0., 0.], [1., 0.], [0., 1.]].sketch();
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Sketch {
@@ -147,7 +368,7 @@ impl From<Sketch> for Shape2d {
 }
 
 /// A chain of elements that is part of a [`Sketch`]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub enum Chain {
@@ -159,7 +380,7 @@ pub enum Chain {
 }
 
 /// A circle that is part of a [`Sketch`]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Circle {
@@ -167,6 +388,24 @@ pub struct Circle {
     radius: f64,
 }
 
+impl Circle {
+    fn key(&self) -> u64 {
+        self.radius.to_bits()
+    }
+}
+
+impl PartialEq for Circle {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for Circle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
 impl Circle {
     /// Construct a new circle with a specific radius
     pub fn from_radius(radius: f64) -> Self {
@@ -180,7 +419,7 @@ impl Circle {
 }
 
 /// A polygonal chain that is part of a [`Sketch`]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct PolyChain {
@@ -206,7 +445,7 @@ impl PolyChain {
 /// A segment of a sketch
 ///
 /// Each segment starts at the previous point of the sketch.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub enum SketchSegment {
@@ -216,3 +455,23 @@ pub enum SketchSegment {
         point: [f64; 2],
     },
 }
+
+impl SketchSegment {
+    fn key(&self) -> [u64; 2] {
+        match self {
+            Self::LineTo { point } => point.map(f64::to_bits),
+        }
+    }
+}
+
+impl PartialEq for SketchSegment {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for SketchSegment {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
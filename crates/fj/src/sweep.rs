@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use crate::{Shape, Shape2d};
 
 /// A sweep of a 2-dimensional shape along straight path
@@ -13,7 +15,7 @@ use crate::{Shape, Shape2d};
 /// // `shape` can be anything that converts to `fj::Shape2d`
 /// let group = shape.sweep([0., 0., 1.]);
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Sweep {
@@ -24,6 +26,24 @@ pub struct Sweep {
     path: [f64; 3],
 }
 
+impl Sweep {
+    fn key(&self) -> (&Shape2d, [u64; 3]) {
+        (&self.shape, self.path.map(f64::to_bits))
+    }
+}
+
+impl PartialEq for Sweep {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for Sweep {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
 impl Sweep {
     /// Create a `Sweep` along a straight path
     pub fn from_path(shape: Shape2d, path: [f64; 3]) -> Self {
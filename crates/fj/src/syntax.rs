@@ -3,6 +3,59 @@
 //! This model defines extension traits, which provide convenient syntax for
 //! the various operations defined in this trait.
 
+/// Convenient syntax to create an [`fj::Annotated`]
+///
+/// [`fj::Annotated`]: crate::Annotated
+pub trait Annotate {
+    /// Attach an annotation to `self`
+    fn annotate(
+        &self,
+        annotation: impl Into<crate::Annotation>,
+    ) -> crate::Annotated;
+}
+
+impl<T> Annotate for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn annotate(
+        &self,
+        annotation: impl Into<crate::Annotation>,
+    ) -> crate::Annotated {
+        let shape = self.clone().into();
+        crate::Annotated::from_shape(shape).with_annotation(annotation)
+    }
+}
+
+/// Convenient syntax to create an [`fj::CircularPattern`]
+///
+/// [`fj::CircularPattern`]: crate::CircularPattern
+pub trait CircularPattern {
+    /// Create a circular pattern of `self`
+    ///
+    /// Creates `count` instances, evenly distributed over a full turn around
+    /// `axis`.
+    fn circular_pattern(
+        &self,
+        axis: [f64; 3],
+        count: u32,
+    ) -> crate::CircularPattern;
+}
+
+impl<T> CircularPattern for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn circular_pattern(
+        &self,
+        axis: [f64; 3],
+        count: u32,
+    ) -> crate::CircularPattern {
+        let shape = self.clone().into();
+        crate::CircularPattern::from_shape(shape, axis, count)
+    }
+}
+
 /// Convenient syntax to create an [`fj::Difference2d`]
 ///
 /// [`fj::Difference2d`]: crate::Difference2d
@@ -53,6 +106,96 @@ where
     }
 }
 
+/// Convenient syntax to create an [`fj::Intersection2d`]
+///
+/// [`fj::Intersection2d`]: crate::Intersection2d
+pub trait Intersection {
+    /// Create an intersection of `self` and `other`
+    fn intersection<Other>(&self, other: &Other) -> crate::Intersection2d
+    where
+        Other: Clone + Into<crate::Shape2d>;
+}
+
+impl<T> Intersection for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn intersection<Other>(&self, other: &Other) -> crate::Intersection2d
+    where
+        Other: Clone + Into<crate::Shape2d>,
+    {
+        let a = self.clone().into();
+        let b = other.clone().into();
+
+        crate::Intersection2d::from_shapes([a, b])
+    }
+}
+
+/// Convenient syntax to create an [`fj::LinearPattern`]
+///
+/// [`fj::LinearPattern`]: crate::LinearPattern
+pub trait LinearPattern {
+    /// Create a linear pattern of `self`
+    ///
+    /// Creates `count` instances, each translated by `offset` relative to the
+    /// previous one.
+    fn linear_pattern(
+        &self,
+        offset: [f64; 3],
+        count: u32,
+    ) -> crate::LinearPattern;
+}
+
+impl<T> LinearPattern for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn linear_pattern(
+        &self,
+        offset: [f64; 3],
+        count: u32,
+    ) -> crate::LinearPattern {
+        let shape = self.clone().into();
+        crate::LinearPattern::from_shape(shape, offset, count)
+    }
+}
+
+/// Convenient syntax to create an [`fj::Offset2d`]
+///
+/// [`fj::Offset2d`]: crate::Offset2d
+pub trait Offset {
+    /// Offset `self` by `distance`
+    fn offset(&self, distance: f64) -> crate::Offset2d;
+}
+
+impl<T> Offset for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn offset(&self, distance: f64) -> crate::Offset2d {
+        let shape = self.clone().into();
+        crate::Offset2d::from_shape(shape, distance)
+    }
+}
+
+/// Convenient syntax to create an [`fj::Referenced`]
+///
+/// [`fj::Referenced`]: crate::Referenced
+pub trait Reference {
+    /// Place a reference body next to `self`
+    fn with_reference(&self, reference: crate::Reference) -> crate::Referenced;
+}
+
+impl<T> Reference for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn with_reference(&self, reference: crate::Reference) -> crate::Referenced {
+        let shape = self.clone().into();
+        crate::Referenced::from_shape(shape).with_reference(reference)
+    }
+}
+
 /// Convenient syntax to create an [`fj::Sketch`]
 ///
 /// [`fj::Sketch`]: crate::Sketch
@@ -95,12 +238,24 @@ where
 ///
 /// [`fj::Transform`]: crate::Transform
 pub trait Transform {
+    /// Create a mirroring
+    ///
+    /// Create a mirroring that mirrors `shape` across the plane that passes
+    /// through the origin and is perpendicular to `normal`.
+    fn mirror(&self, normal: [f64; 3]) -> crate::Transform;
+
     /// Create a rotation
     ///
     /// Create a rotation that rotates `shape` by `angle` around an axis defined
     /// by `axis`.
     fn rotate(&self, axis: [f64; 3], angle: crate::Angle) -> crate::Transform;
 
+    /// Create a scaling
+    ///
+    /// Create a scaling that scales `shape` by the respective factor along each
+    /// axis, relative to the origin.
+    fn scale(&self, factors: [f64; 3]) -> crate::Transform;
+
     /// Create a translation
     ///
     /// Create a translation that translates `shape` by `offset`.
@@ -111,23 +266,53 @@ impl<T> Transform for T
 where
     T: Clone + Into<crate::Shape>,
 {
+    fn mirror(&self, normal: [f64; 3]) -> crate::Transform {
+        let shape = self.clone().into();
+
+        // Mirroring across a plane is the same as rotating by half a turn
+        // around the plane's normal, then inverting through the origin.
+        crate::Transform::from_shape(shape)
+            .with_rotation(normal, crate::Angle::from_rev(0.5))
+            .with_scale([-1.; 3])
+    }
+
     fn rotate(&self, axis: [f64; 3], angle: crate::Angle) -> crate::Transform {
         let shape = self.clone().into();
-        crate::Transform {
-            shape,
-            axis,
-            angle,
-            offset: [0.; 3],
-        }
+        crate::Transform::from_shape(shape).with_rotation(axis, angle)
+    }
+
+    fn scale(&self, factors: [f64; 3]) -> crate::Transform {
+        let shape = self.clone().into();
+        crate::Transform::from_shape(shape).with_scale(factors)
     }
 
     fn translate(&self, offset: [f64; 3]) -> crate::Transform {
         let shape = self.clone().into();
-        crate::Transform {
-            shape,
-            axis: [1., 0., 0.],
-            angle: crate::Angle::from_rad(0.),
-            offset,
-        }
+        crate::Transform::from_shape(shape).with_offset(offset)
+    }
+}
+
+/// Convenient syntax to create an [`fj::Union2d`]
+///
+/// [`fj::Union2d`]: crate::Union2d
+pub trait Union {
+    /// Create a union of `self` and `other`
+    fn union<Other>(&self, other: &Other) -> crate::Union2d
+    where
+        Other: Clone + Into<crate::Shape2d>;
+}
+
+impl<T> Union for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn union<Other>(&self, other: &Other) -> crate::Union2d
+    where
+        Other: Clone + Into<crate::Shape2d>,
+    {
+        let a = self.clone().into();
+        let b = other.clone().into();
+
+        crate::Union2d::from_shapes([a, b])
     }
 }
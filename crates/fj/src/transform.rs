@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use crate::{Angle, Shape};
 
 /// A transformed 3-dimensional shape
@@ -13,16 +15,18 @@ use crate::{Angle, Shape};
 /// // `shape` can be anything that converts to `fj::Shape`
 /// let rotated = shape.rotate([0., 0., 1.], fj::Angle::from_rev(0.5));
 /// let translated = shape.translate([1., 2., 3.]);
+/// let scaled = shape.scale([2., 2., 2.]);
+/// let mirrored = shape.mirror([1., 0., 0.]);
 /// ```
 ///
 /// # Limitations
 ///
-/// Transformations are currently limited to a rotation, followed by a
-/// translation.
+/// Transformations are currently limited to a scaling, followed by a rotation,
+/// followed by a translation.
 ///
 /// See issue:
 /// <https://github.com/hannobraun/Fornjot/issues/101>
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Transform {
@@ -37,6 +41,84 @@ pub struct Transform {
 
     /// The offset of the translation
     pub offset: [f64; 3],
+
+    /// The factors of the scaling along each axis
+    ///
+    /// Defaults to `[1., 1., 1.]` when deserializing, for data that was
+    /// serialized before scaling was supported.
+    #[cfg_attr(feature = "serde", serde(default = "no_scale"))]
+    pub scale: [f64; 3],
+}
+
+impl Transform {
+    fn key(&self) -> (&Shape, [u64; 3], Angle, [u64; 3], [u64; 3]) {
+        (
+            &self.shape,
+            self.axis.map(f64::to_bits),
+            self.angle,
+            self.offset.map(f64::to_bits),
+            self.scale.map(f64::to_bits),
+        )
+    }
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for Transform {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Transform {
+    /// Create a `Transform` from a shape
+    ///
+    /// The transform leaves the shape as it is, until a scaling, rotation, or
+    /// translation is added.
+    pub fn from_shape(shape: Shape) -> Self {
+        Self {
+            shape,
+            axis: [1., 0., 0.],
+            angle: Angle::from_rad(0.),
+            offset: [0.; 3],
+            scale: [1.; 3],
+        }
+    }
+
+    /// Rotate the shape by `angle` around `axis`, which passes through the
+    /// origin
+    ///
+    /// `axis` must not be zero, or computing the transform fails.
+    pub fn with_rotation(mut self, axis: [f64; 3], angle: Angle) -> Self {
+        self.axis = axis;
+        self.angle = angle;
+        self
+    }
+
+    /// Translate the shape by `offset`
+    pub fn with_offset(mut self, offset: [f64; 3]) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Scale the shape by the respective factor along each axis
+    ///
+    /// The scaling is relative to the origin. Negative factors mirror the shape
+    /// along the respective axis. Factors must be finite and non-zero, or
+    /// computing the transform fails.
+    pub fn with_scale(mut self, factors: [f64; 3]) -> Self {
+        self.scale = factors;
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+fn no_scale() -> [f64; 3] {
+    [1.; 3]
 }
 
 impl From<Transform> for Shape {
@@ -44,3 +126,24 @@ impl From<Transform> for Shape {
         Self::Transform(Box::new(shape))
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::Sketch;
+
+    use super::Transform;
+
+    #[test]
+    fn deserialize_without_scale() {
+        let transform = Transform::from_shape(
+            Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]).into(),
+        )
+        .with_offset([1., 2., 3.]);
+
+        let mut value = serde_json::to_value(&transform).unwrap();
+        value.as_object_mut().unwrap().remove("scale");
+
+        let deserialized: Transform = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, transform);
+    }
+}